@@ -0,0 +1,199 @@
+//! Analytics API handlers (trends and test durations).
+
+use actix_web::{HttpResponse, web};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    DurationRegression, Framework, SlowTestsResponse, SlowestTest, SlowestTestsQuery,
+    SlowestTestsResponse, TrendPoint, TrendsQuery, TrendsResponse,
+};
+
+/// Ratio helper that treats an empty denominator as zero.
+fn rate(count: i64, total: i64) -> f64 {
+    if total > 0 {
+        count as f64 / total as f64
+    } else {
+        0.0
+    }
+}
+
+/// Get pass-rate and duration trends.
+///
+/// Totals come from per-report rollups maintained at extraction time and are
+/// grouped by report creation time into day or week buckets.
+#[utoipa::path(
+    get,
+    path = "/analytics/trends",
+    tag = "Analytics",
+    params(
+        ("repository" = Option<String>, Query, description = "Filter by GitHub repository"),
+        ("workflow" = Option<String>, Query, description = "Filter by workflow name"),
+        ("ref" = Option<String>, Query, description = "Filter by git ref"),
+        ("framework" = Option<String>, Query, description = "Filter by framework"),
+        ("label" = Option<String>, Query, description = "Filter by labels (key:value[,key:value...])"),
+        ("bucket" = Option<String>, Query, description = "Time bucket: day (default) or week"),
+        ("from" = Option<String>, Query, description = "Start of time range (RFC 3339)"),
+        ("to" = Option<String>, Query, description = "End of time range (RFC 3339)")
+    ),
+    responses(
+        (status = 200, description = "Time-bucketed trend points", body = TrendsResponse),
+        (status = 400, description = "Invalid query", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_trends(
+    pool: web::Data<DbPool>,
+    query: web::Query<TrendsQuery>,
+) -> AppResult<HttpResponse> {
+    let query = query.into_inner();
+
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        return Err(AppError::InvalidInput(
+            "'from' must be before 'to'".to_string(),
+        ));
+    }
+
+    let rows = pool.query_trends(&query).await?;
+
+    let points = rows
+        .into_iter()
+        .map(|row| TrendPoint {
+            bucket_start: row.bucket_start,
+            report_count: row.report_count,
+            pass_rate: rate(row.passed, row.total),
+            fail_rate: rate(row.failed, row.total),
+            flaky_rate: rate(row.flaky, row.total),
+            total: row.total,
+            passed: row.passed,
+            failed: row.failed,
+            skipped: row.skipped,
+            flaky: row.flaky,
+            duration_ms: row.duration_ms,
+            wall_clock_ms: row.wall_clock_ms,
+        })
+        .collect();
+
+    let response = TrendsResponse {
+        bucket: query.bucket,
+        points,
+    };
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Get tests in a report that regressed against their duration baseline.
+///
+/// Regressions are computed when the report completes, comparing each passing
+/// test with the median of its recent successful runs.
+#[utoipa::path(
+    get,
+    path = "/reports/{report_id}/slow-tests",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID")
+    ),
+    responses(
+        (status = 200, description = "Duration regressions for the report", body = SlowTestsResponse),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_report_slow_tests(
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    let report_id = path.into_inner();
+
+    // Verify report exists
+    let _report = pool
+        .get_report_by_id(report_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report {}", report_id)))?;
+
+    let mut regressions: Vec<DurationRegression> = pool
+        .get_duration_regressions(report_id)
+        .await?
+        .into_iter()
+        .map(|row| DurationRegression {
+            slowdown_pct: if row.baseline_median_ms > 0 {
+                (row.duration_ms - row.baseline_median_ms) as f64 * 100.0
+                    / row.baseline_median_ms as f64
+            } else {
+                0.0
+            },
+            test_case_id: row.test_case_id,
+            full_title: row.full_title,
+            duration_ms: row.duration_ms,
+            baseline_median_ms: row.baseline_median_ms,
+            baseline_p90_ms: row.baseline_p90_ms,
+            sample_count: row.sample_count,
+        })
+        .collect();
+
+    regressions.sort_by(|a, b| b.slowdown_pct.total_cmp(&a.slowdown_pct));
+
+    let response = SlowTestsResponse {
+        report_id,
+        threshold_pct: config.features.regression_threshold_pct,
+        regressions,
+    };
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Rank the slowest tests by median duration of successful runs.
+///
+/// Results are grouped per repository and framework; the window defaults to
+/// the last 30 days.
+#[utoipa::path(
+    get,
+    path = "/analytics/slowest-tests",
+    tag = "Analytics",
+    params(
+        ("repository" = Option<String>, Query, description = "Filter by GitHub repository"),
+        ("framework" = Option<String>, Query, description = "Filter by framework"),
+        ("from" = Option<String>, Query, description = "Start of time range (RFC 3339, default 30 days ago)"),
+        ("to" = Option<String>, Query, description = "End of time range (RFC 3339)"),
+        ("limit" = Option<i32>, Query, description = "Results to return (default 20, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Slowest tests", body = SlowestTestsResponse),
+    )
+)]
+pub async fn get_slowest_tests(
+    pool: web::Data<DbPool>,
+    query: web::Query<SlowestTestsQuery>,
+) -> AppResult<HttpResponse> {
+    let query = query.into_inner();
+
+    let tests = pool
+        .get_slowest_tests(&query)
+        .await?
+        .into_iter()
+        .map(|row| SlowestTest {
+            repository: row.repository,
+            framework: Framework::parse(&row.framework).unwrap_or(Framework::Playwright),
+            full_title: row.full_title,
+            run_count: row.run_count,
+            median_ms: row.median_ms,
+            p90_ms: row.p90_ms,
+            max_ms: row.max_ms,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(SlowestTestsResponse { tests }))
+}
+
+/// Configure analytics routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/analytics/trends").route(web::get().to(get_trends)))
+        .service(web::resource("/analytics/slowest-tests").route(web::get().to(get_slowest_tests)))
+        .service(
+            web::resource("/reports/{report_id}/slow-tests")
+                .route(web::get().to(get_report_slow_tests)),
+        );
+}
@@ -0,0 +1,205 @@
+//! Failure clustering API handlers.
+//!
+//! Groups failed test cases by their normalized error signature, per report
+//! and across reports over time.
+
+use std::collections::{HashMap, HashSet};
+
+use actix_web::{HttpResponse, web};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    FailureGroup, FailureGroupTest, FailureGroupsResponse, FailureSignatureBucket,
+    FailureSignatureSummary, FailureSignaturesQuery, FailureSignaturesResponse,
+};
+use crate::services::failure_signature::normalize_error;
+
+/// Maximum number of test cases listed per failure group.
+const MAX_TESTS_PER_GROUP: usize = 100;
+
+/// Get failure groups for a report.
+///
+/// Failed test cases are clustered by failure signature, largest group first.
+#[utoipa::path(
+    get,
+    path = "/reports/{report_id}/failure-groups",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID")
+    ),
+    responses(
+        (status = 200, description = "Failure groups for the report", body = FailureGroupsResponse),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_report_failure_groups(
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    let report_id = path.into_inner();
+
+    // Verify report exists
+    let _report = pool
+        .get_report_by_id(report_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report {}", report_id)))?;
+
+    let failures = pool.get_signed_failures_by_report(report_id).await?;
+    let total_failures = failures.len() as i64;
+
+    // Group by signature, preserving first-seen order for representatives
+    let mut order: Vec<String> = Vec::new();
+    let mut grouped: HashMap<String, Vec<crate::entity::test_case::Model>> = HashMap::new();
+    for case in failures {
+        let Some(signature) = case.failure_signature.clone() else {
+            continue;
+        };
+        grouped
+            .entry(signature.clone())
+            .or_insert_with(|| {
+                order.push(signature);
+                Vec::new()
+            })
+            .push(case);
+    }
+
+    let mut groups: Vec<FailureGroup> = order
+        .into_iter()
+        .filter_map(|signature| {
+            let cases = grouped.remove(&signature)?;
+            let representative_error = cases.first().and_then(|c| c.error_message.clone());
+            let normalized_message = representative_error
+                .as_deref()
+                .map(normalize_error)
+                .unwrap_or_default();
+            let test_count = cases
+                .iter()
+                .map(|c| c.full_title.as_str())
+                .collect::<HashSet<_>>()
+                .len() as i64;
+
+            Some(FailureGroup {
+                signature,
+                normalized_message,
+                representative_error,
+                occurrences: cases.len() as i64,
+                test_count,
+                tests_truncated: cases.len() > MAX_TESTS_PER_GROUP,
+                tests: cases
+                    .into_iter()
+                    .take(MAX_TESTS_PER_GROUP)
+                    .map(|c| FailureGroupTest {
+                        test_case_id: c.id,
+                        suite_id: c.test_suite_id,
+                        job_id: c.test_job_id,
+                        title: c.title,
+                        full_title: c.full_title,
+                        status: c.status,
+                        retry_count: c.retry_count,
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+
+    // Stable sort keeps first-seen order among equally sized groups
+    groups.sort_by_key(|g| std::cmp::Reverse(g.occurrences));
+
+    let response = FailureGroupsResponse {
+        report_id,
+        total_failures,
+        groups,
+    };
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// List the most common failure signatures across reports.
+///
+/// Each signature includes occurrence counts bucketed by day or week.
+#[utoipa::path(
+    get,
+    path = "/failure-signatures",
+    tag = "Test Results",
+    params(
+        ("repository" = Option<String>, Query, description = "Filter by GitHub repository"),
+        ("ref" = Option<String>, Query, description = "Filter by git ref"),
+        ("framework" = Option<String>, Query, description = "Filter by framework"),
+        ("from" = Option<String>, Query, description = "Start of time range (RFC 3339)"),
+        ("to" = Option<String>, Query, description = "End of time range (RFC 3339)"),
+        ("bucket" = Option<String>, Query, description = "Time bucket: day (default) or week"),
+        ("limit" = Option<i32>, Query, description = "Number of signatures (default 10, max 50)")
+    ),
+    responses(
+        (status = 200, description = "Most common failure signatures", body = FailureSignaturesResponse),
+        (status = 400, description = "Invalid query", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn list_failure_signatures(
+    pool: web::Data<DbPool>,
+    query: web::Query<FailureSignaturesQuery>,
+) -> AppResult<HttpResponse> {
+    let query = query.into_inner();
+
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        return Err(AppError::InvalidInput(
+            "'from' must be before 'to'".to_string(),
+        ));
+    }
+
+    let totals = pool.get_top_failure_signatures(&query).await?;
+    let signatures: Vec<String> = totals.iter().map(|t| t.failure_signature.clone()).collect();
+
+    let bucket_counts = pool
+        .get_failure_signature_buckets(&query, &signatures)
+        .await?;
+
+    let mut buckets_map: HashMap<String, Vec<FailureSignatureBucket>> = HashMap::new();
+    for b in bucket_counts {
+        buckets_map
+            .entry(b.failure_signature)
+            .or_default()
+            .push(FailureSignatureBucket {
+                bucket_start: b.bucket_start,
+                occurrences: b.occurrences,
+            });
+    }
+
+    let signatures = totals
+        .into_iter()
+        .map(|t| FailureSignatureSummary {
+            normalized_message: t
+                .representative_error
+                .as_deref()
+                .map(normalize_error)
+                .unwrap_or_default(),
+            buckets: buckets_map.remove(&t.failure_signature).unwrap_or_default(),
+            signature: t.failure_signature,
+            representative_error: t.representative_error,
+            occurrences: t.occurrences,
+            report_count: t.report_count,
+            first_seen: t.first_seen,
+            last_seen: t.last_seen,
+        })
+        .collect();
+
+    let response = FailureSignaturesResponse {
+        bucket: query.bucket,
+        signatures,
+    };
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Configure failure clustering routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/reports/{report_id}/failure-groups")
+            .route(web::get().to(get_report_failure_groups)),
+    )
+    .service(web::resource("/failure-signatures").route(web::get().to(list_failure_signatures)));
+}
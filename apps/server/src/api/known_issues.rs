@@ -0,0 +1,325 @@
+//! Known issue API handlers.
+//!
+//! Known issues link failures to tracked issues (e.g., Jira keys) by failure
+//! signature, error regex and/or title pattern. Matching failures are tagged
+//! when results are extracted. Managing rules requires admin or contributor
+//! role; OIDC tokens are not accepted.
+
+use actix_web::{HttpResponse, web};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::auth::ApiKeyAuth;
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    ApiKeyRole, AuthenticatedCaller, CreateKnownIssueRequest, KnownIssue, KnownIssueListQuery,
+    KnownIssueListResponse, OIDC_ADMIN_DENIED_MSG, UpdateKnownIssueRequest,
+};
+use crate::services::known_issue::compile_error_pattern;
+use crate::services::quarantine::is_valid_fingerprint;
+use crate::services::text::non_empty;
+
+/// Maximum length of patterns and descriptions.
+const MAX_TEXT_LENGTH: usize = 1000;
+
+/// Maximum length of an issue key.
+const MAX_ISSUE_KEY_LENGTH: usize = 100;
+
+/// Maximum length of an issue link.
+const MAX_ISSUE_URL_LENGTH: usize = 2048;
+
+/// List known issues.
+#[utoipa::path(
+    get,
+    path = "/known-issues",
+    tag = "Known Issues",
+    params(
+        ("repository" = Option<String>, Query, description = "Only rules applying to this repository")
+    ),
+    responses(
+        (status = 200, description = "Known issues in evaluation order", body = KnownIssueListResponse),
+        (status = 401, description = "Unauthorized - admin or contributor role required", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn list_known_issues(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    query: web::Query<KnownIssueListQuery>,
+) -> AppResult<HttpResponse> {
+    require_manager(&auth.caller)?;
+
+    let known_issues =
+        crate::db::known_issues::list(pool.connection(), query.repository.as_deref(), false)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(KnownIssueListResponse { known_issues }))
+}
+
+/// Get a known issue.
+#[utoipa::path(
+    get,
+    path = "/known-issues/{id}",
+    tag = "Known Issues",
+    params(("id" = Uuid, Path, description = "Known issue UUID")),
+    responses(
+        (status = 200, description = "Known issue", body = KnownIssue),
+        (status = 401, description = "Unauthorized - admin or contributor role required", body = crate::error::ErrorResponse),
+        (status = 404, description = "Known issue not found", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn get_known_issue(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    require_manager(&auth.caller)?;
+
+    let id = path.into_inner();
+    let issue = crate::db::known_issues::find_by_id(pool.connection(), id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Known issue {}", id)))?;
+
+    Ok(HttpResponse::Ok().json(issue))
+}
+
+/// Register a known issue.
+///
+/// Applies to results extracted afterwards.
+#[utoipa::path(
+    post,
+    path = "/known-issues",
+    tag = "Known Issues",
+    request_body = CreateKnownIssueRequest,
+    responses(
+        (status = 201, description = "Known issue created", body = KnownIssue),
+        (status = 400, description = "Invalid input", body = crate::error::ErrorResponse),
+        (status = 401, description = "Unauthorized - admin or contributor role required", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn create_known_issue(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    body: web::Json<CreateKnownIssueRequest>,
+) -> AppResult<HttpResponse> {
+    require_manager(&auth.caller)?;
+
+    let body = body.into_inner();
+    let now = Utc::now();
+    let issue = KnownIssue {
+        id: Uuid::now_v7(),
+        issue_key: body.issue_key.trim().to_string(),
+        issue_url: non_empty(body.issue_url),
+        description: non_empty(body.description),
+        repository: non_empty(body.repository),
+        failure_signature: non_empty(body.failure_signature),
+        error_pattern: non_empty(body.error_pattern),
+        title_pattern: non_empty(body.title_pattern),
+        enabled: true,
+        created_by: Some(auth.caller.key_id.clone()),
+        created_at: now,
+        updated_at: now,
+    };
+
+    validate_known_issue(&issue)?;
+    crate::db::known_issues::insert(pool.connection(), &issue).await?;
+
+    Ok(HttpResponse::Created().json(issue))
+}
+
+/// Update a known issue.
+///
+/// Omitted fields are left unchanged; an empty string clears an optional
+/// field. Applies to results extracted afterwards.
+#[utoipa::path(
+    put,
+    path = "/known-issues/{id}",
+    tag = "Known Issues",
+    params(("id" = Uuid, Path, description = "Known issue UUID")),
+    request_body = UpdateKnownIssueRequest,
+    responses(
+        (status = 200, description = "Known issue updated", body = KnownIssue),
+        (status = 400, description = "Invalid input", body = crate::error::ErrorResponse),
+        (status = 401, description = "Unauthorized - admin or contributor role required", body = crate::error::ErrorResponse),
+        (status = 404, description = "Known issue not found", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn update_known_issue(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateKnownIssueRequest>,
+) -> AppResult<HttpResponse> {
+    require_manager(&auth.caller)?;
+
+    let id = path.into_inner();
+    let body = body.into_inner();
+
+    let mut issue = crate::db::known_issues::find_by_id(pool.connection(), id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Known issue {}", id)))?;
+
+    if let Some(key) = body.issue_key {
+        issue.issue_key = key.trim().to_string();
+    }
+    if let Some(url) = body.issue_url {
+        issue.issue_url = non_empty(Some(url));
+    }
+    if let Some(description) = body.description {
+        issue.description = non_empty(Some(description));
+    }
+    if let Some(signature) = body.failure_signature {
+        issue.failure_signature = non_empty(Some(signature));
+    }
+    if let Some(pattern) = body.error_pattern {
+        issue.error_pattern = non_empty(Some(pattern));
+    }
+    if let Some(pattern) = body.title_pattern {
+        issue.title_pattern = non_empty(Some(pattern));
+    }
+    if let Some(enabled) = body.enabled {
+        issue.enabled = enabled;
+    }
+
+    validate_known_issue(&issue)?;
+
+    let issue = crate::db::known_issues::update(pool.connection(), &issue)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Known issue {}", id)))?;
+
+    Ok(HttpResponse::Ok().json(issue))
+}
+
+/// Delete a known issue.
+///
+/// Failures already tagged keep their issue key.
+#[utoipa::path(
+    delete,
+    path = "/known-issues/{id}",
+    tag = "Known Issues",
+    params(("id" = Uuid, Path, description = "Known issue UUID")),
+    responses(
+        (status = 200, description = "Known issue deleted"),
+        (status = 401, description = "Unauthorized - admin or contributor role required", body = crate::error::ErrorResponse),
+        (status = 404, description = "Known issue not found", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn delete_known_issue(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    require_manager(&auth.caller)?;
+
+    let id = path.into_inner();
+    let deleted = crate::db::known_issues::delete(pool.connection(), id).await?;
+
+    if deleted {
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "message": "Known issue deleted",
+            "id": id,
+        })))
+    } else {
+        Err(AppError::NotFound(format!("Known issue {}", id)))
+    }
+}
+
+/// Require an admin or contributor caller authenticated by API key or session.
+fn require_manager(caller: &AuthenticatedCaller) -> AppResult<()> {
+    if caller.is_oidc() {
+        return Err(AppError::Unauthorized(OIDC_ADMIN_DENIED_MSG.to_string()));
+    }
+    if caller.role == ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Admin or contributor role required to manage known issues".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_known_issue(issue: &KnownIssue) -> AppResult<()> {
+    if issue.issue_key.is_empty() || issue.issue_key.len() > MAX_ISSUE_KEY_LENGTH {
+        return Err(AppError::InvalidInput(format!(
+            "issue_key is required (max {} chars)",
+            MAX_ISSUE_KEY_LENGTH
+        )));
+    }
+
+    if let Some(ref url) = issue.issue_url
+        && (url.len() > MAX_ISSUE_URL_LENGTH
+            || !(url.starts_with("https://") || url.starts_with("http://")))
+    {
+        return Err(AppError::InvalidInput(
+            "issue_url must be an http(s) URL of at most 2048 characters".to_string(),
+        ));
+    }
+
+    if issue.failure_signature.is_none()
+        && issue.error_pattern.is_none()
+        && issue.title_pattern.is_none()
+    {
+        return Err(AppError::InvalidInput(
+            "At least one of failure_signature, error_pattern or title_pattern is required"
+                .to_string(),
+        ));
+    }
+
+    if let Some(ref signature) = issue.failure_signature
+        && !is_valid_fingerprint(signature)
+    {
+        return Err(AppError::InvalidInput(
+            "failure_signature must be a lowercase SHA-256 hex digest".to_string(),
+        ));
+    }
+
+    let too_long = [
+        &issue.description,
+        &issue.error_pattern,
+        &issue.title_pattern,
+    ]
+    .iter()
+    .any(|v| v.as_ref().is_some_and(|s| s.len() > MAX_TEXT_LENGTH))
+        || issue.repository.as_ref().is_some_and(|r| r.len() > 255);
+    if too_long {
+        return Err(AppError::InvalidInput(
+            "Field exceeds maximum length".to_string(),
+        ));
+    }
+
+    if let Some(ref pattern) = issue.error_pattern {
+        compile_error_pattern(pattern)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid error_pattern: {}", e)))?;
+    }
+
+    if let Some(ref pattern) = issue.title_pattern
+        && issue.failure_signature.is_none()
+        && issue.error_pattern.is_none()
+        && pattern.chars().all(|c| c == '*' || c.is_whitespace())
+    {
+        return Err(AppError::InvalidInput(
+            "title_pattern must contain more than wildcards".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Configure known issue routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/known-issues")
+            .route(web::get().to(list_known_issues))
+            .route(web::post().to(create_known_issue)),
+    )
+    .service(
+        web::resource("/known-issues/{id}")
+            .route(web::get().to(get_known_issue))
+            .route(web::put().to(update_known_issue))
+            .route(web::delete().to(delete_known_issue)),
+    );
+}
@@ -0,0 +1,79 @@
+//! Environment matrix API handler.
+//!
+//! Shows each test's status across the report's environment cells so
+//! failures confined to one browser, OS, project or device stand out.
+
+use actix_web::{HttpResponse, web};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{EnvironmentMetadata, Framework, MatrixQuery, ReportMatrixResponse};
+use crate::services::matrix::{MatrixEntry, build_matrix};
+
+/// Get test statuses across a report's environment cells.
+///
+/// Cells combine job environment (browser, OS, device) with the project of
+/// Playwright reports. Tests failing in exactly one cell while passing
+/// elsewhere are flagged and listed first.
+#[utoipa::path(
+    get,
+    path = "/reports/{report_id}/matrix",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID"),
+        ("failures_only" = Option<bool>, Query, description = "Only tests that failed in at least one cell (default false)")
+    ),
+    responses(
+        (status = 200, description = "Environment matrix", body = ReportMatrixResponse),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_report_matrix(
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+    query: web::Query<MatrixQuery>,
+) -> AppResult<HttpResponse> {
+    let report_id = path.into_inner();
+
+    let report = pool
+        .get_report_by_id(report_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report {}", report_id)))?;
+
+    let rows = pool.get_matrix_statuses(report_id).await?;
+    let environments: Vec<EnvironmentMetadata> = rows
+        .iter()
+        .map(|r| EnvironmentMetadata::from_json(r.environment.as_ref()))
+        .collect();
+    let entries: Vec<MatrixEntry<'_>> = rows
+        .iter()
+        .zip(&environments)
+        .map(|(row, environment)| MatrixEntry {
+            environment,
+            full_title: &row.full_title,
+            status: &row.status,
+        })
+        .collect();
+
+    let framework = Framework::parse(&report.framework).unwrap_or(Framework::Playwright);
+    let (cells, mut tests) = build_matrix(framework, &entries);
+    let single_cell_failures = tests.iter().filter(|t| t.single_cell_failure).count() as i32;
+    if query.failures_only {
+        tests.retain(|t| t.failed_cells > 0);
+    }
+
+    Ok(HttpResponse::Ok().json(ReportMatrixResponse {
+        report_id,
+        cells,
+        tests,
+        single_cell_failures,
+    }))
+}
+
+/// Configure environment matrix routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/reports/{report_id}/matrix").route(web::get().to(get_report_matrix)),
+    );
+}
@@ -1,17 +1,49 @@
 //! API endpoint modules.
 
+pub mod analytics;
+pub mod failure_groups;
 pub mod files;
 pub mod health;
+pub mod known_issues;
+pub mod matrix;
 pub mod openapi;
+pub mod ownership;
+pub mod pipelines;
+pub mod quarantine;
+pub mod releases;
+pub mod report_bundles;
+pub mod rerun;
+pub mod results_export;
+pub mod retention;
+pub mod runs;
+pub mod search;
+pub mod sharding;
 pub mod test_jobs;
 pub mod test_reports;
 pub mod test_results;
+pub mod triage;
 pub mod websocket;
 
+pub use analytics::configure_routes as configure_analytics_routes;
+pub use failure_groups::configure_routes as configure_failure_group_routes;
 pub use files::configure_routes as configure_file_routes;
 pub use health::configure_health_routes;
+pub use known_issues::configure_routes as configure_known_issue_routes;
+pub use matrix::configure_routes as configure_matrix_routes;
 pub use openapi::ApiDoc;
+pub use ownership::configure_routes as configure_ownership_routes;
+pub use pipelines::configure_routes as configure_pipeline_routes;
+pub use quarantine::configure_routes as configure_quarantine_routes;
+pub use releases::configure_routes as configure_release_routes;
+pub use report_bundles::configure_routes as configure_report_bundle_routes;
+pub use rerun::configure_routes as configure_rerun_routes;
+pub use results_export::configure_routes as configure_results_export_routes;
+pub use retention::configure_routes as configure_retention_routes;
+pub use runs::configure_routes as configure_run_routes;
+pub use search::configure_routes as configure_search_routes;
+pub use sharding::configure_routes as configure_sharding_routes;
 pub use test_jobs::configure_routes as configure_job_routes;
 pub use test_reports::configure_routes as configure_report_routes;
 pub use test_results::configure_routes as configure_test_results_routes;
+pub use triage::configure_routes as configure_triage_routes;
 pub use websocket::configure_routes as configure_websocket_routes;
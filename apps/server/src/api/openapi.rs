@@ -23,7 +23,15 @@ use crate::{api, error, models, services};
         // Report endpoints
         api::test_reports::register_report,
         api::test_reports::list_reports,
+        api::test_reports::update_report_metadata,
         api::test_reports::get_report,
+        api::test_reports::delete_report,
+        api::test_reports::update_report,
+        api::test_reports::finalize_report,
+        api::test_reports::cancel_report,
+        api::test_reports::reopen_report,
+        api::report_bundles::export_report,
+        api::report_bundles::import_report,
         // Job endpoints
         api::test_jobs::init_job,
         api::test_jobs::init_html,
@@ -36,12 +44,62 @@ use crate::{api, error, models, services};
         api::test_jobs::get_json_progress,
         api::test_jobs::query_jobs,
         api::test_jobs::get_job,
+        api::test_jobs::delete_job,
         // Test results endpoints
         api::test_results::query_test_suites,
         api::test_results::query_test_cases,
         api::test_results::get_job_test_suites,
         api::test_results::get_job_test_cases,
         api::test_results::get_suite_test_cases,
+        // Failure clustering endpoints
+        api::failure_groups::get_report_failure_groups,
+        api::failure_groups::list_failure_signatures,
+        // Analytics endpoints
+        api::analytics::get_trends,
+        api::analytics::get_slowest_tests,
+        api::analytics::get_report_slow_tests,
+        // Quarantine endpoints
+        api::quarantine::list_quarantine,
+        api::quarantine::create_quarantine,
+        api::quarantine::delete_quarantine,
+        // Ownership endpoints
+        api::ownership::get_ownership,
+        api::ownership::put_ownership,
+        api::ownership::delete_ownership,
+        // Triage endpoints
+        api::triage::list_triage,
+        api::triage::get_triage,
+        api::triage::create_triage,
+        api::triage::update_triage,
+        api::triage::delete_triage,
+        // Known issue endpoints
+        api::known_issues::list_known_issues,
+        api::known_issues::get_known_issue,
+        api::known_issues::create_known_issue,
+        api::known_issues::update_known_issue,
+        api::known_issues::delete_known_issue,
+        // Sharding endpoints
+        api::sharding::plan,
+        // Rerun endpoints
+        api::rerun::get_rerun_manifest,
+        // Results export endpoints
+        api::results_export::get_report_junit,
+        api::results_export::get_report_csv,
+        api::results_export::get_job_junit,
+        api::results_export::get_job_csv,
+        // Run lineage endpoints
+        api::runs::get_run,
+        // Pipeline run endpoints
+        api::pipelines::list_pipelines,
+        api::pipelines::get_pipeline,
+        // Release readiness endpoints
+        api::releases::get_release_readiness,
+        // Search endpoints
+        api::search::search,
+        // Environment matrix endpoints
+        api::matrix::get_report_matrix,
+        // Retention endpoints
+        api::retention::preview_retention,
         // Auth endpoints
         services::auth_admin::create_api_key,
         services::auth_admin::list_api_keys,
@@ -63,10 +121,18 @@ use crate::{api, error, models, services};
             models::GitHubMetadata,
             models::RegisterReportRequest,
             models::RegisterReportResponse,
+            models::CloseReportResponse,
+            models::ImportReportResponse,
             models::ReportSummary,
             models::ReportListResponse,
             models::ReportDetailResponse,
             models::ListReportsQuery,
+            models::UpdateReportMetadataRequest,
+            models::UpdateReportRequest,
+            models::UpdateReportResponse,
+            models::ReportMetadataResponse,
+            models::ReportSort,
+            models::SortOrder,
             // Jobs
             models::JobStatus,
             models::UploadStatus,
@@ -110,6 +176,94 @@ use crate::{api, error, models, services};
             api::test_results::TestCaseResponse,
             api::test_results::TestSuitesListResponse,
             api::test_results::TestCasesListResponse,
+            // Failure clustering
+            models::FailureGroupTest,
+            models::FailureGroup,
+            models::FailureGroupsResponse,
+            models::FailureSignaturesQuery,
+            models::FailureSignatureBucket,
+            models::FailureSignatureSummary,
+            models::FailureSignaturesResponse,
+            // Analytics
+            models::TimeBucket,
+            models::TrendsQuery,
+            models::TrendPoint,
+            models::TrendsResponse,
+            models::DurationRegression,
+            models::SlowTestsResponse,
+            models::SlowestTestsQuery,
+            models::SlowestTest,
+            models::SlowestTestsResponse,
+            // Quarantine
+            models::CreateQuarantineRequest,
+            models::QuarantineEntryResponse,
+            models::QuarantineListResponse,
+            models::QuarantineFormat,
+            models::QuarantineListQuery,
+            // Ownership
+            models::TitleOwnerRule,
+            models::OwnershipMapping,
+            models::PutOwnershipRequest,
+            models::OwnerFailureSummary,
+            // Triage
+            models::TriageClassification,
+            models::TriageRecord,
+            models::TriageState,
+            models::CreateTriageRequest,
+            models::UpdateTriageRequest,
+            models::TriageListQuery,
+            models::TriageListResponse,
+            // Known issues
+            models::KnownIssue,
+            models::CreateKnownIssueRequest,
+            models::UpdateKnownIssueRequest,
+            models::KnownIssueListQuery,
+            models::KnownIssueListResponse,
+            // Sharding
+            models::ShardPlanRequest,
+            models::ShardPlanResponse,
+            models::Shard,
+            models::ShardFile,
+            // Rerun
+            models::RerunFormat,
+            models::RerunQuery,
+            models::RerunTest,
+            models::RerunManifest,
+            // Run lineage
+            models::RunLineageQuery,
+            models::RunAttempt,
+            models::TestAttemptStatus,
+            models::RunTestResult,
+            models::RunSummary,
+            models::RunLineageResponse,
+            models::ReportLineage,
+            // Pipeline runs
+            models::PipelineStatus,
+            models::ListPipelinesQuery,
+            models::PipelineReport,
+            models::PipelineSummary,
+            models::PipelineListResponse,
+            models::PipelineDetailResponse,
+            // Release readiness
+            models::ReadinessQuery,
+            models::ReadinessDecision,
+            models::KnownIssueFailure,
+            models::ReadinessCombination,
+            models::MissingCombination,
+            models::ReleaseReadinessResponse,
+            // Search
+            models::GlobalSearchQuery,
+            models::SearchHit,
+            models::GlobalSearchResponse,
+            // Environment matrix
+            models::MatrixQuery,
+            models::EnvironmentCell,
+            models::MatrixTest,
+            models::ReportMatrixResponse,
+            // Retention
+            models::ReportClass,
+            models::RetentionClassPreview,
+            models::RetentionPreview,
         )
     ),
     tags(
@@ -117,6 +271,16 @@ use crate::{api, error, models, services};
         (name = "Reports", description = "Report registration and management"),
         (name = "Jobs", description = "Job initialization and uploads"),
         (name = "Test Results", description = "Query test suites and test cases"),
+        (name = "Analytics", description = "Trends and aggregates across reports"),
+        (name = "Quarantine", description = "Known-flaky test quarantine list"),
+        (name = "Ownership", description = "Test ownership mappings per repository"),
+        (name = "Triage", description = "Failure classifications and notes"),
+        (name = "Known Issues", description = "Rules linking failures to tracked issues"),
+        (name = "Sharding", description = "Shard planning from historical durations"),
+        (name = "Runs", description = "Reports linked across workflow run attempts"),
+        (name = "Pipelines", description = "Reports of one CI run grouped across frameworks"),
+        (name = "Releases", description = "Go/no-go readiness of release refs"),
+        (name = "Retention", description = "Expiry of old reports and artifacts"),
         (name = "Auth", description = "API key management")
     ),
     modifiers(&SecurityAddon, &VersionFromCargo)
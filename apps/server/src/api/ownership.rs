@@ -0,0 +1,198 @@
+//! Test ownership mapping API handlers.
+//!
+//! Each repository can have one mapping that assigns owners to tests, either
+//! via CODEOWNERS rules over suite file paths or via title patterns. Owners
+//! are resolved when results are extracted.
+
+use actix_web::{HttpResponse, web};
+
+use crate::auth::ApiKeyAuth;
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{ApiKeyRole, OwnershipMapping, PutOwnershipRequest, TitleOwnerRule};
+use crate::services::ownership::parse_codeowners;
+
+/// Maximum CODEOWNERS content size in bytes.
+const MAX_CODEOWNERS_SIZE: usize = 256 * 1024;
+
+/// Maximum number of title rules per repository.
+const MAX_TITLE_RULES: usize = 500;
+
+/// Path parameters identifying a repository.
+#[derive(serde::Deserialize)]
+pub struct RepositoryPath {
+    pub org: String,
+    pub repo: String,
+}
+
+impl RepositoryPath {
+    fn repository(&self) -> String {
+        format!("{}/{}", self.org, self.repo)
+    }
+}
+
+/// Get the ownership mapping for a repository.
+#[utoipa::path(
+    get,
+    path = "/ownership/{org}/{repo}",
+    tag = "Ownership",
+    params(
+        ("org" = String, Path, description = "Repository owner (organization or user)"),
+        ("repo" = String, Path, description = "Repository name")
+    ),
+    responses(
+        (status = 200, description = "Ownership mapping", body = OwnershipMapping),
+        (status = 404, description = "No mapping for repository", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_ownership(
+    pool: web::Data<DbPool>,
+    path: web::Path<RepositoryPath>,
+) -> AppResult<HttpResponse> {
+    let repository = path.repository();
+
+    let mapping = crate::db::ownership_mappings::find_by_repository(pool.connection(), &repository)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Ownership mapping for {}", repository)))?;
+
+    Ok(HttpResponse::Ok().json(mapping))
+}
+
+/// Set the ownership mapping for a repository.
+///
+/// Replaces any existing mapping. Applies to results extracted afterwards.
+/// Requires contributor or admin role.
+#[utoipa::path(
+    put,
+    path = "/ownership/{org}/{repo}",
+    tag = "Ownership",
+    params(
+        ("org" = String, Path, description = "Repository owner (organization or user)"),
+        ("repo" = String, Path, description = "Repository name")
+    ),
+    request_body = PutOwnershipRequest,
+    responses(
+        (status = 200, description = "Ownership mapping saved", body = OwnershipMapping),
+        (status = 400, description = "Invalid mapping", body = crate::error::ErrorResponse),
+        (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn put_ownership(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    path: web::Path<RepositoryPath>,
+    body: web::Json<PutOwnershipRequest>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.role == ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Viewer role cannot change ownership mappings".to_string(),
+        ));
+    }
+
+    let repository = path.repository();
+    let body = body.into_inner();
+
+    let codeowners = body.codeowners.filter(|c| !c.trim().is_empty());
+    if let Some(ref content) = codeowners {
+        if content.len() > MAX_CODEOWNERS_SIZE {
+            return Err(AppError::InvalidInput(format!(
+                "codeowners exceeds {} bytes",
+                MAX_CODEOWNERS_SIZE
+            )));
+        }
+        parse_codeowners(content)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid codeowners: {}", e)))?;
+    }
+
+    if body.title_rules.len() > MAX_TITLE_RULES {
+        return Err(AppError::InvalidInput(format!(
+            "At most {} title rules are allowed",
+            MAX_TITLE_RULES
+        )));
+    }
+
+    let title_rules: Vec<TitleOwnerRule> = body
+        .title_rules
+        .into_iter()
+        .map(|r| TitleOwnerRule {
+            pattern: r.pattern.trim().to_string(),
+            owner: r.owner.trim().to_string(),
+        })
+        .collect();
+
+    if title_rules.iter().any(|r| {
+        r.pattern.is_empty() || r.owner.is_empty() || r.pattern.len() > 1000 || r.owner.len() > 255
+    }) {
+        return Err(AppError::InvalidInput(
+            "Each title rule needs a pattern (max 1000 chars) and an owner (max 255 chars)"
+                .to_string(),
+        ));
+    }
+
+    let mapping = crate::db::ownership_mappings::upsert(
+        pool.connection(),
+        &repository,
+        codeowners,
+        &title_rules,
+        &auth.caller.key_id,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(mapping))
+}
+
+/// Remove the ownership mapping for a repository.
+///
+/// Requires contributor or admin role.
+#[utoipa::path(
+    delete,
+    path = "/ownership/{org}/{repo}",
+    tag = "Ownership",
+    params(
+        ("org" = String, Path, description = "Repository owner (organization or user)"),
+        ("repo" = String, Path, description = "Repository name")
+    ),
+    responses(
+        (status = 200, description = "Ownership mapping removed"),
+        (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+        (status = 404, description = "No mapping for repository", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn delete_ownership(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    path: web::Path<RepositoryPath>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.role == ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Viewer role cannot change ownership mappings".to_string(),
+        ));
+    }
+
+    let repository = path.repository();
+    let deleted = crate::db::ownership_mappings::delete(pool.connection(), &repository).await?;
+
+    if deleted {
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "message": "Ownership mapping removed",
+            "repository": repository,
+        })))
+    } else {
+        Err(AppError::NotFound(format!(
+            "Ownership mapping for {}",
+            repository
+        )))
+    }
+}
+
+/// Configure ownership routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/ownership/{org}/{repo}")
+            .route(web::get().to(get_ownership))
+            .route(web::put().to(put_ownership))
+            .route(web::delete().to(delete_ownership)),
+    );
+}
@@ -0,0 +1,98 @@
+//! Pipeline run API handlers.
+//!
+//! A pipeline run groups the reports of one CI run across frameworks, with
+//! status and test stats aggregated from its member reports.
+
+use actix_web::{HttpResponse, web};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{ListPipelinesQuery, PipelineDetailResponse, PipelineListResponse};
+use crate::services::pipeline::{load_pipeline, load_reports, summarize};
+
+/// List pipeline runs, newest first.
+#[utoipa::path(
+    get,
+    path = "/pipelines",
+    tag = "Pipelines",
+    params(
+        ("repository" = Option<String>, Query, description = "Filter by GitHub repository"),
+        ("ref" = Option<String>, Query, description = "Filter by git ref"),
+        ("sha" = Option<String>, Query, description = "Filter by commit SHA (full or at least 7 characters)"),
+        ("group_key" = Option<String>, Query, description = "Filter by explicit group key"),
+        ("limit" = Option<i32>, Query, description = "Results per page (default 20, max 100)"),
+        ("offset" = Option<i32>, Query, description = "Pagination offset")
+    ),
+    responses(
+        (status = 200, description = "Pipeline runs with aggregated status", body = PipelineListResponse),
+        (status = 400, description = "Invalid filter", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn list_pipelines(
+    pool: web::Data<DbPool>,
+    query: web::Query<ListPipelinesQuery>,
+) -> AppResult<HttpResponse> {
+    let query = query.into_inner();
+
+    if let Some(ref sha) = query.sha
+        && (!(7..=40).contains(&sha.len()) || !sha.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        return Err(AppError::InvalidInput(
+            "sha must be 7 to 40 hexadecimal characters".to_string(),
+        ));
+    }
+
+    let (pipelines, total) = pool.list_pipeline_runs(&query).await?;
+
+    let ids: Vec<Uuid> = pipelines.iter().map(|p| p.id).collect();
+    let mut reports = load_reports(&pool, &ids).await?;
+
+    let pipelines = pipelines
+        .iter()
+        .map(|p| summarize(p, &reports.remove(&p.id).unwrap_or_default()))
+        .collect();
+
+    Ok(HttpResponse::Ok().json(PipelineListResponse {
+        pipelines,
+        total: total as i64,
+        limit: query.limit.clamp(1, 100),
+        offset: query.offset.max(0),
+    }))
+}
+
+/// Get a pipeline run with its member reports.
+///
+/// Reports replaced by a later attempt of the same workflow, framework and
+/// labels are listed as `superseded` and left out of the aggregates.
+#[utoipa::path(
+    get,
+    path = "/pipelines/{pipeline_id}",
+    tag = "Pipelines",
+    params(("pipeline_id" = Uuid, Path, description = "Pipeline run UUID")),
+    responses(
+        (status = 200, description = "Pipeline run with reports", body = PipelineDetailResponse),
+        (status = 404, description = "Pipeline run not found", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_pipeline(
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    let pipeline_id = path.into_inner();
+
+    let (pipeline, reports) = load_pipeline(&pool, pipeline_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Pipeline run {}", pipeline_id)))?;
+
+    Ok(HttpResponse::Ok().json(PipelineDetailResponse {
+        summary: summarize(&pipeline, &reports),
+        reports,
+    }))
+}
+
+/// Configure pipeline run routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/pipelines").route(web::get().to(list_pipelines)))
+        .service(web::resource("/pipelines/{pipeline_id}").route(web::get().to(get_pipeline)));
+}
@@ -0,0 +1,246 @@
+//! Quarantine registry API handlers.
+//!
+//! Known-flaky tests can be quarantined by fingerprint or title pattern.
+//! Their failures are counted separately from regular failures, and CI can
+//! fetch the list to skip them.
+
+use actix_web::{HttpResponse, web};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::auth::ApiKeyAuth;
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    ApiKeyRole, CreateQuarantineRequest, QuarantineEntry, QuarantineEntryResponse,
+    QuarantineFormat, QuarantineListQuery, QuarantineListResponse,
+};
+use crate::services::quarantine::{
+    is_valid_fingerprint, pattern_to_regex, test_fingerprint, title_to_regex,
+};
+use crate::services::text::non_empty;
+
+/// Maximum length of free-text fields.
+const MAX_TEXT_LENGTH: usize = 1000;
+
+/// List quarantined tests.
+///
+/// Returns active entries as JSON, as plain text (one title or pattern per
+/// line), or as a single regular expression suitable for Playwright's
+/// `--grep-invert`. The expression matches titles anywhere, without their
+/// `[project]` prefix. Entries known only by fingerprint are omitted from the
+/// text formats.
+#[utoipa::path(
+    get,
+    path = "/quarantine",
+    tag = "Quarantine",
+    params(
+        ("repository" = Option<String>, Query, description = "Only entries applying to this repository"),
+        ("framework" = Option<String>, Query, description = "Only entries applying to this framework"),
+        ("include_expired" = Option<bool>, Query, description = "Include expired entries (default false)"),
+        ("format" = Option<String>, Query, description = "Output format: json (default), text or grep")
+    ),
+    responses(
+        (status = 200, description = "Quarantined tests", body = QuarantineListResponse),
+    )
+)]
+pub async fn list_quarantine(
+    pool: web::Data<DbPool>,
+    query: web::Query<QuarantineListQuery>,
+) -> AppResult<HttpResponse> {
+    let query = query.into_inner();
+
+    let entries = crate::db::quarantined_tests::list(
+        pool.connection(),
+        query.repository.as_deref(),
+        query.framework,
+        query.include_expired,
+    )
+    .await?;
+
+    match query.format {
+        QuarantineFormat::Json => {
+            let entries = entries
+                .into_iter()
+                .map(QuarantineEntryResponse::from)
+                .collect();
+            Ok(HttpResponse::Ok().json(QuarantineListResponse { entries }))
+        }
+        QuarantineFormat::Text => {
+            let lines: Vec<&str> = entries
+                .iter()
+                .filter_map(|e| e.full_title.as_deref().or(e.title_pattern.as_deref()))
+                .collect();
+            Ok(HttpResponse::Ok()
+                .content_type("text/plain; charset=utf-8")
+                .body(lines.join("\n")))
+        }
+        QuarantineFormat::Grep => {
+            let alternatives: Vec<String> = entries
+                .iter()
+                .filter_map(|e| {
+                    e.title_pattern
+                        .as_deref()
+                        .map(pattern_to_regex)
+                        .or_else(|| e.full_title.as_deref().map(title_to_regex))
+                })
+                .collect();
+            Ok(HttpResponse::Ok()
+                .content_type("text/plain; charset=utf-8")
+                .body(alternatives.join("|")))
+        }
+    }
+}
+
+/// Quarantine a test.
+///
+/// Requires contributor or admin role.
+#[utoipa::path(
+    post,
+    path = "/quarantine",
+    tag = "Quarantine",
+    request_body = CreateQuarantineRequest,
+    responses(
+        (status = 201, description = "Test quarantined", body = QuarantineEntryResponse),
+        (status = 400, description = "Invalid input", body = crate::error::ErrorResponse),
+        (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn create_quarantine(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    body: web::Json<CreateQuarantineRequest>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.role == ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Viewer role cannot quarantine tests".to_string(),
+        ));
+    }
+
+    let body = body.into_inner();
+
+    let full_title = non_empty(body.full_title);
+    let fingerprint = non_empty(body.fingerprint);
+    let title_pattern = non_empty(body.title_pattern);
+
+    let identifiers = [&full_title, &fingerprint, &title_pattern]
+        .iter()
+        .filter(|v| v.is_some())
+        .count();
+    if identifiers != 1 {
+        return Err(AppError::InvalidInput(
+            "Exactly one of full_title, fingerprint or title_pattern is required".to_string(),
+        ));
+    }
+
+    if let Some(ref fp) = fingerprint
+        && !is_valid_fingerprint(fp)
+    {
+        return Err(AppError::InvalidInput(
+            "fingerprint must be a lowercase SHA-256 hex digest".to_string(),
+        ));
+    }
+
+    if let Some(ref pattern) = title_pattern
+        && pattern.chars().all(|c| c == '*' || c.is_whitespace())
+    {
+        return Err(AppError::InvalidInput(
+            "title_pattern must contain more than wildcards".to_string(),
+        ));
+    }
+
+    let reason = body.reason.trim().to_string();
+    let owner = body.owner.trim().to_string();
+    if reason.is_empty() || owner.is_empty() {
+        return Err(AppError::InvalidInput(
+            "reason and owner are required".to_string(),
+        ));
+    }
+
+    let too_long = [&full_title, &title_pattern]
+        .iter()
+        .any(|v| v.as_ref().is_some_and(|s| s.len() > MAX_TEXT_LENGTH))
+        || reason.len() > MAX_TEXT_LENGTH
+        || owner.len() > 255;
+    if too_long {
+        return Err(AppError::InvalidInput(
+            "Field exceeds maximum length".to_string(),
+        ));
+    }
+
+    if let Some(expires_at) = body.expires_at
+        && expires_at <= Utc::now()
+    {
+        return Err(AppError::InvalidInput(
+            "expires_at must be in the future".to_string(),
+        ));
+    }
+
+    let entry = QuarantineEntry {
+        id: Uuid::now_v7(),
+        repository: non_empty(body.repository),
+        framework: body.framework,
+        fingerprint: fingerprint.or_else(|| full_title.as_deref().map(test_fingerprint)),
+        full_title,
+        title_pattern,
+        reason,
+        owner,
+        expires_at: body.expires_at,
+        created_by: Some(auth.caller.key_id.clone()),
+        created_at: Utc::now(),
+    };
+
+    crate::db::quarantined_tests::insert(pool.connection(), &entry).await?;
+
+    Ok(HttpResponse::Created().json(QuarantineEntryResponse::from(entry)))
+}
+
+/// Remove a test from quarantine.
+///
+/// Requires contributor or admin role.
+#[utoipa::path(
+    delete,
+    path = "/quarantine/{id}",
+    tag = "Quarantine",
+    params(("id" = Uuid, Path, description = "Quarantine entry UUID")),
+    responses(
+        (status = 200, description = "Quarantine entry removed"),
+        (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+        (status = 404, description = "Entry not found", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn delete_quarantine(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.role == ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Viewer role cannot remove quarantined tests".to_string(),
+        ));
+    }
+
+    let id = path.into_inner();
+    let deleted = crate::db::quarantined_tests::delete(pool.connection(), id).await?;
+
+    if deleted {
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "message": "Quarantine entry removed",
+            "id": id,
+        })))
+    } else {
+        Err(AppError::NotFound(format!("Quarantine entry {}", id)))
+    }
+}
+
+/// Configure quarantine routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/quarantine")
+            .route(web::get().to(list_quarantine))
+            .route(web::post().to(create_quarantine)),
+    )
+    .service(web::resource("/quarantine/{id}").route(web::delete().to(delete_quarantine)));
+}
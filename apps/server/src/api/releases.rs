@@ -0,0 +1,284 @@
+//! Release readiness API handlers.
+//!
+//! Rolls up the latest complete report of every framework and environment on
+//! a release ref into a go/no-go decision, with failures classified against
+//! the previous release ref.
+
+use std::collections::{BTreeMap, HashMap};
+
+use actix_web::{HttpResponse, web};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::entity::test_report;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    Framework, GitHubMetadata, MissingCombination, ReadinessCombination, ReadinessDecision,
+    ReadinessQuery, ReleaseReadinessResponse, labels_from_json,
+};
+use crate::services::pipeline::add_stats;
+use crate::services::quarantine::QuarantineMatcher;
+use crate::services::readiness::{
+    ReportFailures, assess, describe, failures_by_report, parse_release_ref, pass_rate,
+    previous_release_ref,
+};
+
+/// Repository, framework, workflow and labels of a report.
+#[derive(PartialEq, Eq, Hash)]
+struct CombinationKey {
+    repository: Option<String>,
+    framework: &'static str,
+    workflow: Option<String>,
+    labels: BTreeMap<String, String>,
+}
+
+impl CombinationKey {
+    fn of(report: &test_report::Model) -> (Self, Framework, GitHubMetadata) {
+        let metadata = GitHubMetadata::from_json(report.github_metadata.as_ref());
+        let framework = Framework::parse(&report.framework).unwrap_or(Framework::Playwright);
+        let key = Self {
+            repository: metadata.repository.clone(),
+            framework: framework.as_str(),
+            workflow: metadata.workflow.clone(),
+            labels: labels_from_json(&report.labels),
+        };
+        (key, framework, metadata)
+    }
+}
+
+fn report_url(report_id: Uuid) -> String {
+    format!("/reports/{}", report_id)
+}
+
+/// Get the go/no-go readiness of a release ref.
+///
+/// Each repository, framework, workflow and label combination contributes
+/// its latest complete report on the ref. Failures that didn't fail in the
+/// same combination on the previous release ref make it `no_go`; failures
+/// that already failed there, tests still quarantined, failures matching an
+/// enabled known issue, and combinations of the previous release without a
+/// report on this one make it `caution`.
+#[utoipa::path(
+    get,
+    path = "/releases/{ref}/readiness",
+    tag = "Releases",
+    params(
+        ("ref" = String, Path, description = "Release ref, as a branch name (e.g. release-10.5) or full ref"),
+        ("repository" = Option<String>, Query, description = "Only reports from this repository"),
+        ("previous_ref" = Option<String>, Query, description = "Release ref to compare with (default: closest lower version with the same prefix)")
+    ),
+    responses(
+        (status = 200, description = "Release readiness", body = ReleaseReadinessResponse),
+        (status = 404, description = "No complete reports on the ref", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_release_readiness(
+    pool: web::Data<DbPool>,
+    path: web::Path<String>,
+    query: web::Query<ReadinessQuery>,
+) -> AppResult<HttpResponse> {
+    let git_ref = path.into_inner();
+    let query = query.into_inner();
+    let repository = query.repository.as_deref();
+
+    let reports = pool
+        .get_latest_complete_reports(&git_ref, repository)
+        .await?;
+    if reports.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "Complete reports on {}",
+            git_ref
+        )));
+    }
+
+    let previous_ref = match query.previous_ref {
+        Some(previous_ref) => Some(previous_ref),
+        None => match parse_release_ref(&git_ref) {
+            Some((prefix, _)) => {
+                let candidates = pool.get_complete_report_refs(prefix, repository).await?;
+                previous_release_ref(&git_ref, &candidates)
+            }
+            None => None,
+        },
+    };
+    let previous_reports = match previous_ref.as_deref() {
+        Some(previous_ref) => {
+            pool.get_latest_complete_reports(previous_ref, repository)
+                .await?
+        }
+        None => Vec::new(),
+    };
+
+    let ids: Vec<_> = reports.iter().map(|r| r.id).collect();
+    let mut stats = pool.get_test_stats_by_report_ids(&ids).await?;
+
+    let ids: Vec<_> = ids
+        .into_iter()
+        .chain(previous_reports.iter().map(|r| r.id))
+        .collect();
+    let failures = failures_by_report(&pool.get_run_test_statuses(&ids).await?);
+
+    let quarantine =
+        crate::db::quarantined_tests::list(pool.connection(), repository, None, false).await?;
+    let open_issues: HashMap<String, Option<String>> =
+        crate::db::known_issues::list(pool.connection(), repository, true)
+            .await?
+            .into_iter()
+            .map(|issue| (issue.issue_key, issue.issue_url))
+            .collect();
+
+    let mut previous: HashMap<CombinationKey, &test_report::Model> = previous_reports
+        .iter()
+        .map(|r| (CombinationKey::of(r).0, r))
+        .collect();
+
+    let no_failures = ReportFailures::default();
+    let mut reasons = Vec::new();
+
+    let mut combinations = Vec::with_capacity(reports.len());
+    for report in &reports {
+        let (key, framework, metadata) = CombinationKey::of(report);
+        let previous_report = previous.remove(&key);
+
+        let matcher =
+            QuarantineMatcher::new(&quarantine, key.repository.as_deref(), Some(framework));
+        let assessment = assess(
+            failures.get(&report.id).unwrap_or(&no_failures),
+            previous_report.map(|p| failures.get(&p.id).unwrap_or(&no_failures)),
+            |title| matcher.is_quarantined(title),
+            &open_issues,
+        );
+        let decision = assessment.decision();
+
+        let description = describe(
+            key.repository.as_deref(),
+            framework,
+            key.workflow.as_deref(),
+            &key.labels,
+        );
+        if !assessment.new_failures.is_empty() {
+            reasons.push(format!(
+                "{} new failures in {}",
+                assessment.new_failures.len(),
+                description
+            ));
+        }
+        if !assessment.existing_failures.is_empty() {
+            reasons.push(format!(
+                "{} failures in {} also failed on {}",
+                assessment.existing_failures.len(),
+                description,
+                previous_ref.as_deref().unwrap_or_default()
+            ));
+        }
+        if !assessment.quarantined_failures.is_empty() {
+            reasons.push(format!(
+                "{} quarantined failures in {}",
+                assessment.quarantined_failures.len(),
+                description
+            ));
+        }
+        if !assessment.known_issue_failures.is_empty() {
+            reasons.push(format!(
+                "{} known issue failures in {}",
+                assessment.known_issue_failures.len(),
+                description
+            ));
+        }
+
+        let test_stats = stats.remove(&report.id).unwrap_or_default();
+        combinations.push(ReadinessCombination {
+            run_url: match (&metadata.repository, &metadata.run_id) {
+                (Some(repository), Some(run_id)) => Some(format!(
+                    "https://github.com/{}/actions/runs/{}",
+                    repository, run_id
+                )),
+                _ => None,
+            },
+            repository: key.repository,
+            framework,
+            workflow: key.workflow,
+            labels: key.labels,
+            decision,
+            report_id: report.id,
+            report_url: report_url(report.id),
+            created_at: report.created_at,
+            pass_rate: pass_rate(&test_stats),
+            test_stats,
+            previous_report_id: previous_report.map(|p| p.id),
+            new_failures: assessment.new_failures,
+            existing_failures: assessment.existing_failures,
+            quarantined_failures: assessment.quarantined_failures,
+            known_issue_failures: assessment.known_issue_failures,
+        });
+    }
+
+    // Whatever is left ran on the previous release but not on this one
+    let mut missing_combinations: Vec<MissingCombination> = previous
+        .into_iter()
+        .map(|(key, r)| MissingCombination {
+            repository: key.repository,
+            framework: Framework::parse(key.framework).unwrap_or(Framework::Playwright),
+            workflow: key.workflow,
+            labels: key.labels,
+            previous_report_id: r.id,
+            previous_report_url: report_url(r.id),
+        })
+        .collect();
+    missing_combinations.sort_by_key(|m| m.previous_report_id);
+    for missing in &missing_combinations {
+        reasons.push(format!(
+            "No complete report for {}",
+            describe(
+                missing.repository.as_deref(),
+                missing.framework,
+                missing.workflow.as_deref(),
+                &missing.labels,
+            )
+        ));
+    }
+
+    combinations.sort_by(|a, b| {
+        b.decision
+            .cmp(&a.decision)
+            .then(a.pass_rate.total_cmp(&b.pass_rate))
+            .then(a.report_id.cmp(&b.report_id))
+    });
+
+    let mut decision = combinations
+        .iter()
+        .map(|c| c.decision)
+        .max()
+        .unwrap_or(ReadinessDecision::Go);
+    if !missing_combinations.is_empty() {
+        decision = decision.max(ReadinessDecision::Caution);
+    }
+    if previous_ref.is_none() && decision != ReadinessDecision::Go {
+        reasons.insert(
+            0,
+            "No previous release ref to compare with; all failures count as new".to_string(),
+        );
+    }
+
+    let test_stats = add_stats(combinations.iter().map(|c| &c.test_stats));
+
+    Ok(HttpResponse::Ok().json(ReleaseReadinessResponse {
+        git_ref,
+        repository: query.repository,
+        previous_ref,
+        decision,
+        reasons,
+        pass_rate: pass_rate(&test_stats),
+        test_stats,
+        combinations,
+        missing_combinations,
+    }))
+}
+
+/// Configure release readiness routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    // Release refs may contain slashes (refs/heads/release-10.5)
+    cfg.service(
+        web::resource("/releases/{ref:.+}/readiness").route(web::get().to(get_release_readiness)),
+    );
+}
@@ -0,0 +1,128 @@
+//! Report bundle API handlers.
+//!
+//! Exports a report with its results and stored files as a zip, and imports
+//! such a bundle as a new report, e.g. to move a report between instances.
+
+use actix_web::http::header;
+use actix_web::{HttpResponse, web};
+use futures_util::StreamExt;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+use crate::auth::ApiKeyAuth;
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{ImportReportResponse, OIDC_ADMIN_DENIED_MSG, WsEvent, WsEventMessage};
+use crate::services::report_bundle::{self, MAX_BUNDLE_SIZE, TempFile, bundle_filename};
+use crate::services::{EventBroadcaster, Storage};
+
+/// Download a report as a zip bundle.
+///
+/// The bundle holds the report, its jobs, test suites, test cases, triage
+/// records, duration regressions and OIDC claims as JSON plus every stored file (HTML report, screenshots, JSON results). It's
+/// streamed while being built; see `POST /reports/import` to load it into
+/// another instance.
+#[utoipa::path(
+    get,
+    path = "/reports/{report_id}/export",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID")
+    ),
+    responses(
+        (status = 200, description = "Report bundle", content_type = "application/zip"),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn export_report(
+    pool: web::Data<DbPool>,
+    storage: web::Data<Storage>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    let report_id = path.into_inner();
+
+    let report = pool
+        .get_report_by_id(report_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report {}", report_id)))?;
+
+    let stream = report_bundle::export(pool.get_ref().clone(), storage.get_ref().clone(), report);
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/zip")
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", bundle_filename(report_id)),
+        ))
+        .streaming(stream))
+}
+
+/// Import a report bundle as a new report.
+///
+/// Send the zip from `GET /reports/{report_id}/export` as the request body.
+/// The report, its jobs, results and stored files are recreated under fresh
+/// IDs. Requires the admin role.
+#[utoipa::path(
+    post,
+    path = "/reports/import",
+    tag = "Reports",
+    request_body(content = Vec<u8>, description = "Report bundle zip", content_type = "application/zip"),
+    responses(
+        (status = 201, description = "Report imported", body = ImportReportResponse),
+        (status = 400, description = "Invalid or unsupported bundle", body = crate::error::ErrorResponse),
+        (status = 401, description = "Admin role required", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn import_report(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    storage: web::Data<Storage>,
+    broadcaster: web::Data<EventBroadcaster>,
+    mut payload: web::Payload,
+) -> AppResult<HttpResponse> {
+    if auth.caller.is_oidc() {
+        return Err(AppError::Unauthorized(OIDC_ADMIN_DENIED_MSG.to_string()));
+    }
+    if !auth.caller.is_admin() {
+        return Err(AppError::Unauthorized(
+            "Admin role required to import reports".to_string(),
+        ));
+    }
+
+    // Zip archives are read from the end, so spool the upload to disk first
+    let spool = TempFile::new("tsio-import", ".zip");
+    let spool_err = |e: std::io::Error| AppError::Storage(format!("Failed to spool bundle: {}", e));
+    let mut file = tokio::fs::File::create(&spool.0).await.map_err(spool_err)?;
+    let mut size = 0u64;
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(|e| AppError::InvalidInput(format!("Read error: {}", e)))?;
+        size += chunk.len() as u64;
+        if size > MAX_BUNDLE_SIZE {
+            return Err(AppError::InvalidInput(format!(
+                "Bundle exceeds maximum size of {} bytes",
+                MAX_BUNDLE_SIZE
+            )));
+        }
+        file.write_all(&chunk).await.map_err(spool_err)?;
+    }
+    file.flush().await.map_err(spool_err)?;
+    drop(file);
+
+    let response = report_bundle::import(&pool, &storage, &spool.0).await?;
+
+    broadcaster.send(WsEventMessage::new(WsEvent::report_created(
+        response.report_id,
+    )));
+
+    Ok(HttpResponse::Created().json(response))
+}
+
+/// Configure report bundle routes.
+///
+/// Must be registered before the report routes, so `/reports/import` isn't
+/// matched as `/reports/{report_id}`.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/reports/import").route(web::post().to(import_report)))
+        .service(web::resource("/reports/{report_id}/export").route(web::get().to(export_report)));
+}
@@ -0,0 +1,121 @@
+//! Rerun manifest API handler.
+//!
+//! Lets CI rerun only the tests that failed in a report.
+
+use std::collections::HashSet;
+
+use actix_web::{HttpResponse, web};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{Framework, RerunFormat, RerunManifest, RerunQuery, RerunTest};
+use crate::services::rerun::{rerun_args, rerun_files};
+use crate::services::text::split_project;
+
+/// Get a rerun manifest for a report's failed tests.
+///
+/// `json` returns the tests, their spec files and CLI arguments for the
+/// report's framework. The framework formats return only the CLI arguments as
+/// plain text, one argument per line, e.g.
+/// `mapfile -t args < <(curl ...) && npx playwright test "${args[@]}"`.
+/// An empty body means there is nothing to rerun.
+#[utoipa::path(
+    get,
+    path = "/reports/{report_id}/rerun",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID"),
+        ("format" = Option<String>, Query, description = "playwright, cypress, detox or json (default)"),
+        ("include_flaky" = Option<bool>, Query, description = "Also rerun tests that passed after retries (default false)"),
+        ("job_id" = Option<Uuid>, Query, description = "Only tests from this job"),
+        ("project" = Option<String>, Query, description = "Only tests from this Playwright project")
+    ),
+    responses(
+        (status = 200, description = "Rerun manifest", body = RerunManifest),
+        (status = 400, description = "Invalid query", body = crate::error::ErrorResponse),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_rerun_manifest(
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+    query: web::Query<RerunQuery>,
+) -> AppResult<HttpResponse> {
+    let report_id = path.into_inner();
+    let query = query.into_inner();
+
+    let report = pool
+        .get_report_by_id(report_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report {}", report_id)))?;
+    let framework = Framework::parse(&report.framework).unwrap_or(Framework::Playwright);
+
+    let format = query.format.unwrap_or(RerunFormat::Json);
+    if let Some(requested) = format.framework()
+        && requested != framework
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Report uses {}; format '{}' does not apply",
+            framework, requested
+        )));
+    }
+
+    if query.project.is_some() && framework != Framework::Playwright {
+        return Err(AppError::InvalidInput(
+            "project only applies to Playwright reports".to_string(),
+        ));
+    }
+
+    let candidates = pool.get_rerun_candidates(report_id, query.job_id).await?;
+
+    // The same test may fail in several jobs; rerun it once
+    let mut seen = HashSet::new();
+    let tests: Vec<RerunTest> = candidates
+        .into_iter()
+        .filter(|c| query.include_flaky || c.status != "flaky")
+        .map(|c| {
+            let project = match framework {
+                Framework::Playwright => split_project(&c.full_title).0.map(str::to_string),
+                _ => None,
+            };
+            RerunTest {
+                file_path: c.file_path,
+                title: c.title,
+                full_title: c.full_title,
+                project,
+                status: c.status,
+            }
+        })
+        .filter(|t| {
+            query
+                .project
+                .as_deref()
+                .is_none_or(|p| t.project.as_deref() == Some(p))
+        })
+        .filter(|t| seen.insert((t.file_path.clone(), t.full_title.clone())))
+        .collect();
+
+    let args = rerun_args(framework, &tests);
+
+    if format == RerunFormat::Json {
+        return Ok(HttpResponse::Ok().json(RerunManifest {
+            report_id,
+            framework,
+            files: rerun_files(&tests),
+            tests,
+            args,
+        }));
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .body(args.join("\n")))
+}
+
+/// Configure rerun routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/reports/{report_id}/rerun").route(web::get().to(get_rerun_manifest)),
+    );
+}
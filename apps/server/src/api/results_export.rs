@@ -0,0 +1,189 @@
+//! Results export API handlers.
+//!
+//! Serves a report's or job's test results as JUnit XML or CSV for tools
+//! that don't read the JSON API.
+
+use std::collections::HashMap;
+
+use actix_web::http::header;
+use actix_web::{HttpResponse, web};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::services::results_export::{self, ExportJob, ResultsFormat, results_filename};
+
+/// Stream the results of a report, or of one of its jobs.
+async fn results_response(
+    pool: &DbPool,
+    report_id: Uuid,
+    job_id: Option<Uuid>,
+    format: ResultsFormat,
+) -> AppResult<HttpResponse> {
+    let report = pool
+        .get_report_by_id(report_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report {}", report_id)))?;
+
+    let jobs = match job_id {
+        Some(job_id) => {
+            let job = pool
+                .get_job_by_id(job_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Job {}", job_id)))?;
+            if job.test_report_id != report_id {
+                return Err(AppError::NotFound(format!(
+                    "Job {} in report {}",
+                    job_id, report_id
+                )));
+            }
+            vec![job]
+        }
+        None => pool.get_jobs_by_report_id(report_id).await?,
+    };
+
+    // Suites are few next to test cases, so they're loaded up front for the
+    // totals; test cases are paged while streaming
+    let mut suites_by_job: HashMap<Uuid, Vec<_>> = HashMap::new();
+    for suite in pool.get_test_suites_by_report_id(report_id, None).await? {
+        suites_by_job
+            .entry(suite.test_job_id)
+            .or_default()
+            .push(suite);
+    }
+    let jobs: Vec<ExportJob> = jobs
+        .into_iter()
+        .map(|job| ExportJob {
+            suites: suites_by_job.remove(&job.id).unwrap_or_default(),
+            job,
+        })
+        .collect();
+
+    let stream = results_export::export(pool.clone(), report, jobs, format);
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!(
+                "inline; filename=\"{}\"",
+                results_filename(report_id, job_id, format)
+            ),
+        ))
+        .streaming(stream))
+}
+
+/// Get a report's results as JUnit XML.
+///
+/// One `testsuite` per test suite of every job, with the job's name and
+/// environment as properties. A test retried within a run is a single
+/// `testcase`: earlier failed attempts become `rerunFailure` elements when it
+/// still failed, or `flakyFailure` when it passed on a retry. Quarantined
+/// failures are reported as skipped. Attachments are linked in `system-out`
+/// as `[[ATTACHMENT|/files/...]]`, relative to the server.
+#[utoipa::path(
+    get,
+    path = "/reports/{report_id}/results.xml",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID")
+    ),
+    responses(
+        (status = 200, description = "JUnit XML results", content_type = "application/xml"),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_report_junit(
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    results_response(&pool, path.into_inner(), None, ResultsFormat::JUnit).await
+}
+
+/// Get a report's results as CSV.
+///
+/// One row per test with its job, environment, suite, final status, total
+/// duration across attempts, retries and error message. Attachment links
+/// are space-separated in the last column.
+#[utoipa::path(
+    get,
+    path = "/reports/{report_id}/results.csv",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID")
+    ),
+    responses(
+        (status = 200, description = "CSV results", content_type = "text/csv"),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_report_csv(
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    results_response(&pool, path.into_inner(), None, ResultsFormat::Csv).await
+}
+
+/// Get a job's results as JUnit XML.
+///
+/// Same format as `GET /reports/{report_id}/results.xml`, limited to one job.
+#[utoipa::path(
+    get,
+    path = "/reports/{report_id}/jobs/{job_id}/results.xml",
+    tag = "Jobs",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID"),
+        ("job_id" = Uuid, Path, description = "Job UUID")
+    ),
+    responses(
+        (status = 200, description = "JUnit XML results", content_type = "application/xml"),
+        (status = 404, description = "Report or job not found", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_job_junit(
+    pool: web::Data<DbPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> AppResult<HttpResponse> {
+    let (report_id, job_id) = path.into_inner();
+    results_response(&pool, report_id, Some(job_id), ResultsFormat::JUnit).await
+}
+
+/// Get a job's results as CSV.
+///
+/// Same format as `GET /reports/{report_id}/results.csv`, limited to one job.
+#[utoipa::path(
+    get,
+    path = "/reports/{report_id}/jobs/{job_id}/results.csv",
+    tag = "Jobs",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID"),
+        ("job_id" = Uuid, Path, description = "Job UUID")
+    ),
+    responses(
+        (status = 200, description = "CSV results", content_type = "text/csv"),
+        (status = 404, description = "Report or job not found", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_job_csv(
+    pool: web::Data<DbPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> AppResult<HttpResponse> {
+    let (report_id, job_id) = path.into_inner();
+    results_response(&pool, report_id, Some(job_id), ResultsFormat::Csv).await
+}
+
+/// Configure results export routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/reports/{report_id}/results.xml").route(web::get().to(get_report_junit)),
+    )
+    .service(web::resource("/reports/{report_id}/results.csv").route(web::get().to(get_report_csv)))
+    .service(
+        web::resource("/reports/{report_id}/jobs/{job_id}/results.xml")
+            .route(web::get().to(get_job_junit)),
+    )
+    .service(
+        web::resource("/reports/{report_id}/jobs/{job_id}/results.csv")
+            .route(web::get().to(get_job_csv)),
+    );
+}
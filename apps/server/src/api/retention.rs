@@ -0,0 +1,52 @@
+//! Data retention API handler.
+
+use actix_web::{HttpResponse, web};
+use chrono::Utc;
+
+use crate::auth::ApiKeyAuth;
+use crate::config::Config;
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{OIDC_ADMIN_DENIED_MSG, RetentionPreview};
+use crate::services::retention;
+
+/// Preview what the next retention run would remove.
+///
+/// Counts expired reports by kind (default branch, other branch, pull
+/// request), expired HTML reports and screenshots, and soft-deleted reports
+/// due for purging. Nothing is removed. Requires the admin role.
+#[utoipa::path(
+    get,
+    path = "/retention/preview",
+    tag = "Retention",
+    responses(
+        (status = 200, description = "Retention dry run", body = RetentionPreview),
+        (status = 401, description = "Admin role required", body = crate::error::ErrorResponse),
+    ),
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn preview_retention(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.is_oidc() {
+        return Err(AppError::Unauthorized(OIDC_ADMIN_DENIED_MSG.to_string()));
+    }
+    if !auth.caller.is_admin() {
+        return Err(AppError::Unauthorized(
+            "Admin role required to preview retention".to_string(),
+        ));
+    }
+
+    let preview = retention::preview(pool.get_ref(), &config.retention, Utc::now()).await?;
+
+    Ok(HttpResponse::Ok().json(preview))
+}
+
+/// Configure retention routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/retention/preview").route(web::get().to(preview_retention)));
+}
@@ -0,0 +1,150 @@
+//! Run lineage API handlers.
+//!
+//! Re-running a GitHub Actions workflow registers a new report for each
+//! attempt. Reports sharing a repository, run ID and workflow form a run
+//! lineage whose final per-test results come from the latest attempt.
+
+use std::collections::HashMap;
+
+use actix_web::{HttpResponse, web};
+
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    Framework, GitHubMetadata, ReportStatus, RunAttempt, RunLineageQuery, RunLineageResponse,
+};
+use crate::services::run_lineage::{
+    AttemptTest, merge_attempts, needs_attention, parse_run_attempt, summarize,
+};
+
+/// Path parameters identifying a workflow run.
+#[derive(serde::Deserialize)]
+pub struct RunPath {
+    pub org: String,
+    pub repo: String,
+    pub run_id: String,
+}
+
+/// Load the reports of every attempt of a run, oldest attempt first.
+///
+/// Test statistics are only loaded when `with_stats` is set.
+pub(crate) async fn load_run_attempts(
+    pool: &DbPool,
+    repository: &str,
+    run_id: &str,
+    workflow: Option<&str>,
+    with_stats: bool,
+) -> AppResult<Vec<RunAttempt>> {
+    let reports = pool
+        .get_reports_by_run(repository, run_id, workflow)
+        .await?;
+
+    let mut stats = if with_stats {
+        let ids: Vec<_> = reports.iter().map(|r| r.id).collect();
+        pool.get_test_stats_by_report_ids(&ids).await?
+    } else {
+        HashMap::new()
+    };
+
+    let mut attempts: Vec<RunAttempt> = reports
+        .into_iter()
+        .map(|r| {
+            let metadata = GitHubMetadata::from_json(r.github_metadata.as_ref());
+            RunAttempt {
+                run_attempt: parse_run_attempt(metadata.run_attempt.as_deref()),
+                report_id: r.id,
+                framework: Framework::parse(&r.framework).unwrap_or(Framework::Playwright),
+                workflow: metadata.workflow,
+                status: ReportStatus::parse(&r.status).unwrap_or(ReportStatus::Initializing),
+                test_stats: stats.remove(&r.id),
+                created_at: r.created_at,
+            }
+        })
+        .collect();
+
+    // Reports are loaded by creation time, which breaks ties within an attempt
+    attempts.sort_by_key(|a| a.run_attempt);
+
+    Ok(attempts)
+}
+
+/// Get every attempt of a workflow run and the merged result per test.
+///
+/// A test's final status comes from the last attempt it ran in, so tests not
+/// selected by "re-run failed jobs" keep their earlier result. Tests that
+/// failed in an earlier attempt and passed later are flagged
+/// `passed_on_rerun`.
+#[utoipa::path(
+    get,
+    path = "/runs/{org}/{repo}/{run_id}",
+    tag = "Runs",
+    params(
+        ("org" = String, Path, description = "Repository owner (organization or user)"),
+        ("repo" = String, Path, description = "Repository name"),
+        ("run_id" = String, Path, description = "GitHub Actions run ID"),
+        ("workflow" = Option<String>, Query, description = "Only reports from this workflow"),
+        ("include_passing" = Option<bool>, Query, description = "Also list tests that passed in every attempt (default false)")
+    ),
+    responses(
+        (status = 200, description = "Run lineage", body = RunLineageResponse),
+        (status = 404, description = "No reports for run", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_run(
+    pool: web::Data<DbPool>,
+    path: web::Path<RunPath>,
+    query: web::Query<RunLineageQuery>,
+) -> AppResult<HttpResponse> {
+    let RunPath { org, repo, run_id } = path.into_inner();
+    let repository = format!("{}/{}", org, repo);
+
+    let attempts =
+        load_run_attempts(&pool, &repository, &run_id, query.workflow.as_deref(), true).await?;
+
+    if attempts.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "Reports for run {} in {}",
+            run_id, repository
+        )));
+    }
+
+    let by_report: HashMap<_, _> = attempts
+        .iter()
+        .map(|a| (a.report_id, (a.run_attempt, a.framework)))
+        .collect();
+    let report_ids: Vec<_> = by_report.keys().copied().collect();
+
+    let statuses = pool.get_run_test_statuses(&report_ids).await?;
+    let attempt_tests: Vec<AttemptTest<'_>> = statuses
+        .iter()
+        .filter_map(|s| {
+            let &(run_attempt, framework) = by_report.get(&s.report_id)?;
+            Some(AttemptTest {
+                run_attempt,
+                framework,
+                full_title: &s.full_title,
+                status: &s.status,
+                quarantined: s.quarantined,
+            })
+        })
+        .collect();
+
+    let mut tests = merge_attempts(&attempt_tests);
+    let summary = summarize(&tests);
+    if !query.include_passing {
+        tests.retain(needs_attention);
+    }
+
+    Ok(HttpResponse::Ok().json(RunLineageResponse {
+        repository,
+        run_id,
+        attempts,
+        summary,
+        tests,
+    }))
+}
+
+/// Configure run lineage routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/runs/{org}/{repo}/{run_id}").route(web::get().to(get_run)));
+}
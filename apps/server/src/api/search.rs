@@ -0,0 +1,123 @@
+//! Global search API handler.
+//!
+//! Searches test titles, suite file paths and error messages across all
+//! reports, unlike the per-report search under `/reports/{id}/search`.
+
+use actix_web::{HttpResponse, web};
+
+use crate::config::Config;
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{GlobalSearchQuery, GlobalSearchResponse, SearchHit};
+use crate::services::search::{
+    decode_cursor, encode_cursor, escape_html, highlight_terms, render_highlight,
+};
+
+/// Maximum search query length in characters.
+const MAX_QUERY_LENGTH: usize = 200;
+
+/// Search test cases across reports.
+///
+/// Terms use web search syntax (`"exact phrase"`, `or`, `-exclude`) and are
+/// matched against titles and error messages; titles and file paths also
+/// match by trigram similarity. Results are ranked by relevance and paginated
+/// with `cursor`.
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = "Test Results",
+    params(
+        ("q" = String, Query, description = "Search terms"),
+        ("repository" = Option<String>, Query, description = "Filter by GitHub repository"),
+        ("ref" = Option<String>, Query, description = "Filter by git ref"),
+        ("status" = Option<String>, Query, description = "Filter by test status"),
+        ("from" = Option<String>, Query, description = "Start of time range (RFC 3339)"),
+        ("to" = Option<String>, Query, description = "End of time range (RFC 3339)"),
+        ("limit" = Option<i32>, Query, description = "Results per page (default 20, max 100)"),
+        ("cursor" = Option<String>, Query, description = "Cursor from the previous page")
+    ),
+    responses(
+        (status = 200, description = "Search results", body = GlobalSearchResponse),
+        (status = 400, description = "Invalid query or cursor", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn search(
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    query: web::Query<GlobalSearchQuery>,
+) -> AppResult<HttpResponse> {
+    let query = query.into_inner();
+    let search_min_length = config.features.search_min_length;
+
+    let q = query.q.trim();
+    if q.is_empty() || q.chars().count() < search_min_length {
+        return Ok(HttpResponse::Ok().json(GlobalSearchResponse {
+            query: query.q,
+            search_min_length,
+            hits: vec![],
+            next_cursor: None,
+        }));
+    }
+
+    if q.chars().count() > MAX_QUERY_LENGTH {
+        return Err(AppError::InvalidInput(format!(
+            "q exceeds {} characters",
+            MAX_QUERY_LENGTH
+        )));
+    }
+
+    let after = match query.cursor.as_deref() {
+        Some(cursor) => Some(
+            decode_cursor(cursor)
+                .ok_or_else(|| AppError::InvalidInput("Invalid cursor".to_string()))?,
+        ),
+        None => None,
+    };
+
+    // Fetch one extra row to know whether another page exists
+    let limit = query.limit.clamp(1, 100) as usize;
+    let mut rows = pool
+        .search_test_cases(&query, after, limit as i64 + 1)
+        .await?;
+
+    let next_cursor = if rows.len() > limit {
+        rows.truncate(limit);
+        rows.last().map(|r| encode_cursor(r.rank, r.id))
+    } else {
+        None
+    };
+
+    let hits = rows
+        .into_iter()
+        .map(|row| SearchHit {
+            title_highlight: render_highlight(&row.title_headline)
+                .unwrap_or_else(|| escape_html(&row.full_title)),
+            file_path_highlight: row.file_path.as_deref().and_then(|p| highlight_terms(p, q)),
+            error_highlight: row.error_headline.as_deref().and_then(render_highlight),
+            test_case_id: row.id,
+            report_id: row.report_id,
+            job_id: row.job_id,
+            suite_id: row.suite_id,
+            title: row.title,
+            full_title: row.full_title,
+            status: row.status,
+            file_path: row.file_path,
+            repository: row.repository,
+            git_ref: row.git_ref,
+            rank: row.rank,
+            created_at: row.created_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(GlobalSearchResponse {
+        query: query.q,
+        search_min_length,
+        hits,
+        next_cursor,
+    }))
+}
+
+/// Configure global search routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/search").route(web::get().to(search)));
+}
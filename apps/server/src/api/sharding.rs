@@ -0,0 +1,141 @@
+//! Shard planning API handler.
+
+use std::collections::{HashMap, HashSet};
+
+use actix_web::{HttpResponse, web};
+use chrono::{Duration, Utc};
+
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{Shard, ShardFile, ShardPlanRequest, ShardPlanResponse};
+use crate::services::sharding::{plan_shards, unseen_file_estimate};
+
+/// Maximum number of spec files per plan.
+const MAX_FILES: usize = 5000;
+
+/// Maximum number of shards per plan.
+const MAX_SHARDS: usize = 256;
+
+/// Default days of history to consider.
+const DEFAULT_LOOKBACK_DAYS: i64 = 30;
+
+/// Plan balanced shards from historical spec file durations.
+///
+/// Each file is weighted by its median duration over past runs and files are
+/// bin-packed longest first. Files without history are weighted with the
+/// median of the known files. Read-only; intended to be called by CI before
+/// fanning out jobs.
+#[utoipa::path(
+    post,
+    path = "/sharding/plan",
+    tag = "Sharding",
+    request_body = ShardPlanRequest,
+    responses(
+        (status = 200, description = "Balanced shard assignment", body = ShardPlanResponse),
+        (status = 400, description = "Invalid input", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn plan(
+    pool: web::Data<DbPool>,
+    body: web::Json<ShardPlanRequest>,
+) -> AppResult<HttpResponse> {
+    let body = body.into_inner();
+
+    if body.shards == 0 || body.shards > MAX_SHARDS {
+        return Err(AppError::InvalidInput(format!(
+            "shards must be between 1 and {}",
+            MAX_SHARDS
+        )));
+    }
+
+    let lookback_days = body.lookback_days.unwrap_or(DEFAULT_LOOKBACK_DAYS);
+    if !(1..=180).contains(&lookback_days) {
+        return Err(AppError::InvalidInput(
+            "lookback_days must be between 1 and 180".to_string(),
+        ));
+    }
+
+    // Normalize and dedupe while keeping the caller's order
+    let mut seen = HashSet::new();
+    let files: Vec<String> = body
+        .files
+        .iter()
+        .map(|f| f.trim().trim_start_matches("./").to_string())
+        .filter(|f| !f.is_empty() && seen.insert(f.clone()))
+        .collect();
+
+    if files.is_empty() {
+        return Err(AppError::InvalidInput(
+            "files must not be empty".to_string(),
+        ));
+    }
+    if files.len() > MAX_FILES {
+        return Err(AppError::InvalidInput(format!(
+            "At most {} files are allowed",
+            MAX_FILES
+        )));
+    }
+
+    let history: HashMap<String, (i64, i64)> = pool
+        .get_file_duration_medians(
+            &files,
+            body.repository.as_deref(),
+            body.framework,
+            Utc::now() - Duration::days(lookback_days),
+        )
+        .await?
+        .into_iter()
+        .map(|row| (row.file_path, (row.median_ms.max(0), row.samples)))
+        .collect();
+
+    let known: Vec<i64> = history.values().map(|(median, _)| *median).collect();
+    let unseen_estimate = unseen_file_estimate(&known);
+
+    let weighted: Vec<ShardFile> = files
+        .into_iter()
+        .map(|file_path| {
+            let (estimated_duration_ms, samples) = history
+                .get(&file_path)
+                .copied()
+                .unwrap_or((unseen_estimate, 0));
+            ShardFile {
+                file_path,
+                estimated_duration_ms,
+                samples,
+            }
+        })
+        .collect();
+    let unseen_files = weighted.iter().filter(|f| f.samples == 0).count();
+
+    let durations: Vec<i64> = weighted.iter().map(|f| f.estimated_duration_ms).collect();
+    let shards: Vec<Shard> = plan_shards(&durations, body.shards)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (items, load))| Shard {
+            index: i + 1,
+            files: items
+                .into_iter()
+                .map(|item| weighted[item].clone())
+                .collect(),
+            estimated_duration_ms: load,
+        })
+        .collect();
+
+    let estimated_makespan_ms = shards
+        .iter()
+        .map(|s| s.estimated_duration_ms)
+        .max()
+        .unwrap_or(0);
+
+    Ok(HttpResponse::Ok().json(ShardPlanResponse {
+        shards,
+        estimated_makespan_ms,
+        unseen_file_estimate_ms: unseen_estimate,
+        unseen_files,
+    }))
+}
+
+/// Configure sharding routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/sharding/plan").route(web::post().to(plan)));
+}
@@ -16,10 +16,12 @@ use crate::models::{
     HtmlUploadProgress, HtmlUploadResponse, InitHtmlRequest, InitHtmlResponse, InitJobRequest,
     InitJobResponse, InitJsonRequest, InitJsonResponse, InitScreenshotsRequest,
     InitScreenshotsResponse, JobDetailResponse, JobGitHubMetadata, JobListResponse, JobStatus,
-    JsonUploadProgress, JsonUploadResponse, QueryJobsParams, RejectedFile, ReportStatus,
-    ScreenshotUploadResponse, WsEvent, WsEventMessage,
+    JsonUploadProgress, JsonUploadResponse, MAX_EXPECTED_JOBS, QueryJobsParams, RejectedFile,
+    ReportStatus, ScreenshotUploadResponse, UploadStatus, WsEvent, WsEventMessage,
 };
 use crate::services::extraction;
+use crate::services::lifecycle::Lifecycle;
+use crate::services::report_completion;
 use crate::services::{EventBroadcaster, Storage};
 
 /// Allowed file extensions for HTML report uploads.
@@ -111,6 +113,54 @@ fn validate_file(path: &str, size: Option<i64>) -> Option<String> {
     None
 }
 
+/// Get a job that still accepts uploads.
+///
+/// Completed and failed jobs are immutable, as are all jobs of a closed
+/// report until an admin reopens it.
+async fn get_writable_job(
+    pool: &DbPool,
+    report_id: Uuid,
+    job_id: Uuid,
+) -> AppResult<crate::entity::test_job::Model> {
+    let job = pool
+        .get_job_by_id(job_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Job {}", job_id)))?;
+
+    if job.test_report_id != report_id {
+        return Err(AppError::NotFound(format!(
+            "Job {} in report {}",
+            job_id, report_id
+        )));
+    }
+
+    if JobStatus::parse(&job.status).is_some_and(|s| s.is_final()) {
+        return Err(AppError::Conflict(format!(
+            "Job {} is already {}",
+            job_id, job.status
+        )));
+    }
+
+    let report = pool
+        .get_report_by_id(report_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report {}", report_id)))?;
+    if report_completion::is_closed(&report) {
+        return Err(report_completion::already_closed(&report));
+    }
+
+    Ok(job)
+}
+
+/// Whether this request completed an upload, rather than a concurrent one.
+fn completed_upload(result: AppResult<crate::entity::test_job::Model>) -> AppResult<bool> {
+    match result {
+        Ok(_) => Ok(true),
+        Err(AppError::Conflict(_)) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
 /// Infer content type from file extension.
 fn infer_content_type(path: &str) -> &'static str {
     let ext = path.rsplit('.').next().unwrap_or("").to_lowercase();
@@ -154,6 +204,7 @@ fn infer_content_type(path: &str) -> &'static str {
         (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
         (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
         (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+        (status = 409, description = "Report closed", body = crate::error::ErrorResponse),
     ),
     security(
         ("api_key" = [])
@@ -199,16 +250,36 @@ pub async fn init_job(
         return Ok(HttpResponse::Ok().json(response));
     }
 
+    // Closed reports take no new jobs until an admin reopens them
+    if report_completion::is_closed(&report) {
+        return Err(report_completion::already_closed(&report));
+    }
+
     // Generate UUIDv7 for time-ordered job ID
     let job_id = Uuid::now_v7();
 
-    // Insert job with Pending status
+    // Insert job with Pending status; open reports expect one more job for
+    // each job registered
     let github_job_name = req
         .github_metadata
         .as_ref()
         .and_then(|m| m.job_name.clone());
-    let _job = pool
-        .insert_job(
+    if report.is_open {
+        pool.insert_open_report_job(
+            job_id,
+            report_id,
+            req.github_metadata.clone(),
+            req.environment.clone(),
+        )
+        .await?
+        .ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "Report {} is closed or already has {} jobs",
+                report_id, MAX_EXPECTED_JOBS
+            ))
+        })?;
+    } else {
+        pool.insert_job(
             job_id,
             report_id,
             req.github_metadata.clone(),
@@ -216,13 +287,16 @@ pub async fn init_job(
             JobStatus::Pending,
         )
         .await?;
+    }
 
     // Update report status to "uploading" if this is the first job
     let current_status = ReportStatus::parse(&report.status);
     if current_status == Some(ReportStatus::Initializing) {
         pool.update_report_status(report_id, ReportStatus::Uploading)
             .await?;
-        // Broadcast report_updated event for status change
+    }
+    // Broadcast report_updated event for status or expected job count change
+    if current_status == Some(ReportStatus::Initializing) || report.is_open {
         let event = WsEventMessage::new(WsEvent::report_updated(report_id));
         broadcaster.send(event);
     }
@@ -261,6 +335,7 @@ pub async fn init_job(
         (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
         (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
         (status = 404, description = "Job not found", body = crate::error::ErrorResponse),
+        (status = 409, description = "Job finished or report closed", body = crate::error::ErrorResponse),
     ),
     security(
         ("api_key" = [])
@@ -288,18 +363,8 @@ pub async fn init_html(
         ));
     }
 
-    // Verify job exists and belongs to report
-    let job = pool
-        .get_job_by_id(job_id)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Job {}", job_id)))?;
-
-    if job.test_report_id != report_id {
-        return Err(AppError::NotFound(format!(
-            "Job {} in report {}",
-            job_id, report_id
-        )));
-    }
+    // Verify job belongs to report and still accepts uploads
+    get_writable_job(&pool, report_id, job_id).await?;
 
     // Validate files and separate accepted/rejected
     let mut accepted_files: Vec<AcceptedHtmlFile> = Vec::new();
@@ -344,7 +409,8 @@ pub async fn init_html(
     pool.insert_html_files(job_id, file_entries).await?;
 
     // Set HTML upload status to "started"
-    pool.set_html_upload_status(job_id, Some("started")).await?;
+    pool.set_html_upload_status(job_id, UploadStatus::Started)
+        .await?;
 
     info!(
         "HTML files initialized: report_id={}, job_id={}, files_accepted={}, files_rejected={}",
@@ -424,6 +490,10 @@ pub async fn get_html_progress(
         ("report_id" = Option<Uuid>, Query, description = "Filter by report ID"),
         ("status" = Option<String>, Query, description = "Filter by status"),
         ("github_job_name" = Option<String>, Query, description = "Filter by GitHub job name (partial match)"),
+        ("os" = Option<String>, Query, description = "Filter by environment OS"),
+        ("browser" = Option<String>, Query, description = "Filter by environment browser"),
+        ("device" = Option<String>, Query, description = "Filter by environment device"),
+        ("tag" = Option<String>, Query, description = "Filter by environment tag"),
         ("from_date" = Option<String>, Query, description = "Filter from date (ISO 8601)"),
         ("to_date" = Option<String>, Query, description = "Filter to date (ISO 8601)"),
         ("limit" = Option<i32>, Query, description = "Results per page (default 20, max 100)"),
@@ -534,6 +604,78 @@ pub async fn get_job(
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Delete a job with its results and files.
+///
+/// The job is hidden from every query right away and the report's stats are
+/// recomputed without it; its stored files are deleted now. Requires the
+/// admin role.
+#[utoipa::path(
+    delete,
+    path = "/reports/{report_id}/jobs/{job_id}",
+    tag = "Jobs",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID"),
+        ("job_id" = Uuid, Path, description = "Job UUID")
+    ),
+    responses(
+        (status = 200, description = "Job deleted"),
+        (status = 401, description = "Admin role required", body = crate::error::ErrorResponse),
+        (status = 404, description = "Job not found", body = crate::error::ErrorResponse),
+    ),
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn delete_job(
+    auth: crate::auth::ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    storage: web::Data<Storage>,
+    broadcaster: web::Data<EventBroadcaster>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.is_oidc() {
+        return Err(AppError::Unauthorized(
+            crate::models::OIDC_ADMIN_DENIED_MSG.to_string(),
+        ));
+    }
+    if !auth.caller.is_admin() {
+        return Err(AppError::Unauthorized(
+            "Admin role required to delete jobs".to_string(),
+        ));
+    }
+
+    let (report_id, job_id) = path.into_inner();
+
+    if !pool.soft_delete_job(report_id, job_id).await? {
+        return Err(AppError::NotFound(format!(
+            "Job {} in report {}",
+            job_id, report_id
+        )));
+    }
+
+    pool.refresh_report_rollup(report_id).await?;
+    broadcaster.send(WsEventMessage::new(WsEvent::report_updated(report_id)));
+
+    // A failure here leaves files behind until retention purges the report
+    let files_deleted = storage
+        .delete_prefix(&Storage::job_key_prefix(
+            &report_id.to_string(),
+            &job_id.to_string(),
+        ))
+        .await?;
+
+    info!(
+        "Deleted job {} of report {} ({} files removed from storage)",
+        job_id, report_id, files_deleted
+    );
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message": "Job deleted",
+        "id": job_id,
+        "files_deleted": files_deleted,
+    })))
+}
+
 /// Upload HTML files for a job.
 ///
 /// Accepts multipart form data with files. Each file must have been registered
@@ -552,6 +694,7 @@ pub async fn get_job(
         (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
         (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
         (status = 404, description = "Job not found", body = crate::error::ErrorResponse),
+        (status = 409, description = "Job finished or report closed", body = crate::error::ErrorResponse),
     ),
     security(
         ("api_key" = [])
@@ -572,18 +715,8 @@ pub async fn upload_html(
 
     let (report_id, job_id) = path.into_inner();
 
-    // Verify job exists and belongs to report
-    let job = pool
-        .get_job_by_id(job_id)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Job {}", job_id)))?;
-
-    if job.test_report_id != report_id {
-        return Err(AppError::NotFound(format!(
-            "Job {} in report {}",
-            job_id, report_id
-        )));
-    }
+    // Verify job belongs to report and still accepts uploads
+    get_writable_job(&pool, report_id, job_id).await?;
 
     // Get pending HTML files for this job
     let pending_files = pool.get_pending_html_files(job_id).await?;
@@ -652,13 +785,15 @@ pub async fn upload_html(
     if all_uploaded {
         let html_path = Storage::job_key_prefix(&report_id.to_string(), &job_id.to_string());
         pool.update_job_html_path(job_id, html_path).await?;
-        pool.set_html_upload_status(job_id, Some("completed"))
-            .await?;
-
-        info!(
-            "All HTML files uploaded: report_id={}, job_id={}",
-            report_id, job_id
-        );
+        if completed_upload(
+            pool.set_html_upload_status(job_id, UploadStatus::Completed)
+                .await,
+        )? {
+            info!(
+                "All HTML files uploaded: report_id={}, job_id={}",
+                report_id, job_id
+            );
+        }
     }
 
     info!(
@@ -749,6 +884,7 @@ fn extract_test_name(path: &str) -> String {
         (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
         (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
         (status = 404, description = "Job not found", body = crate::error::ErrorResponse),
+        (status = 409, description = "Job finished or report closed", body = crate::error::ErrorResponse),
     ),
     security(
         ("api_key" = [])
@@ -777,18 +913,8 @@ pub async fn init_screenshots(
         ));
     }
 
-    // Verify job exists and belongs to report
-    let job = pool
-        .get_job_by_id(job_id)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Job {}", job_id)))?;
-
-    if job.test_report_id != report_id {
-        return Err(AppError::NotFound(format!(
-            "Job {} in report {}",
-            job_id, report_id
-        )));
-    }
+    // Verify job belongs to report and still accepts uploads
+    get_writable_job(&pool, report_id, job_id).await?;
 
     // Validate files and separate accepted/rejected
     let mut accepted_files: Vec<AcceptedScreenshot> = Vec::new();
@@ -844,7 +970,7 @@ pub async fn init_screenshots(
     pool.insert_screenshots(job_id, screenshot_entries).await?;
 
     // Set screenshots upload status to "started"
-    pool.set_screenshots_upload_status(job_id, Some("started"))
+    pool.set_screenshots_upload_status(job_id, UploadStatus::Started)
         .await?;
 
     info!(
@@ -882,6 +1008,7 @@ pub async fn init_screenshots(
         (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
         (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
         (status = 404, description = "Job not found", body = crate::error::ErrorResponse),
+        (status = 409, description = "Job finished or report closed", body = crate::error::ErrorResponse),
     ),
     security(
         ("api_key" = [])
@@ -902,18 +1029,8 @@ pub async fn upload_screenshots(
 
     let (report_id, job_id) = path.into_inner();
 
-    // Verify job exists and belongs to report
-    let job = pool
-        .get_job_by_id(job_id)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Job {}", job_id)))?;
-
-    if job.test_report_id != report_id {
-        return Err(AppError::NotFound(format!(
-            "Job {} in report {}",
-            job_id, report_id
-        )));
-    }
+    // Verify job belongs to report and still accepts uploads
+    get_writable_job(&pool, report_id, job_id).await?;
 
     // Get pending screenshots for this job
     let pending_screenshots = pool.get_pending_screenshots(job_id).await?;
@@ -988,10 +1105,12 @@ pub async fn upload_screenshots(
     let all_uploaded = pool.all_screenshots_uploaded(job_id).await?;
 
     // Set screenshots_upload_status to "completed" when all screenshots are uploaded
-    if all_uploaded {
-        pool.set_screenshots_upload_status(job_id, Some("completed"))
-            .await?;
-
+    if all_uploaded
+        && completed_upload(
+            pool.set_screenshots_upload_status(job_id, UploadStatus::Completed)
+                .await,
+        )?
+    {
         info!(
             "All screenshots uploaded: report_id={}, job_id={}",
             report_id, job_id
@@ -1080,6 +1199,7 @@ fn validate_json_file(path: &str, size: Option<i64>) -> Option<String> {
         (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
         (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
         (status = 404, description = "Job not found", body = crate::error::ErrorResponse),
+        (status = 409, description = "Job finished or report closed", body = crate::error::ErrorResponse),
     ),
     security(
         ("api_key" = [])
@@ -1107,18 +1227,8 @@ pub async fn init_json(
         ));
     }
 
-    // Verify job exists and belongs to report
-    let job = pool
-        .get_job_by_id(job_id)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Job {}", job_id)))?;
-
-    if job.test_report_id != report_id {
-        return Err(AppError::NotFound(format!(
-            "Job {} in report {}",
-            job_id, report_id
-        )));
-    }
+    // Verify job belongs to report and still accepts uploads
+    get_writable_job(&pool, report_id, job_id).await?;
 
     // Validate files and separate accepted/rejected
     let mut accepted_files: Vec<AcceptedJsonFile> = Vec::new();
@@ -1167,7 +1277,8 @@ pub async fn init_json(
     pool.insert_json_files(job_id, file_entries).await?;
 
     // Set JSON upload status to "started"
-    pool.set_json_upload_status(job_id, Some("started")).await?;
+    pool.set_json_upload_status(job_id, UploadStatus::Started)
+        .await?;
 
     info!(
         "JSON files initialized: report_id={}, job_id={}, files_accepted={}, files_rejected={}",
@@ -1257,6 +1368,7 @@ pub async fn get_json_progress(
         (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
         (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
         (status = 404, description = "Job not found", body = crate::error::ErrorResponse),
+        (status = 409, description = "Job finished or report closed", body = crate::error::ErrorResponse),
     ),
     security(
         ("api_key" = [])
@@ -1267,6 +1379,7 @@ pub async fn upload_json(
     pool: web::Data<DbPool>,
     storage: web::Data<Storage>,
     broadcaster: web::Data<EventBroadcaster>,
+    config: web::Data<crate::config::Config>,
     path: web::Path<(Uuid, Uuid)>,
     mut payload: Multipart,
 ) -> AppResult<HttpResponse> {
@@ -1278,18 +1391,8 @@ pub async fn upload_json(
 
     let (report_id, job_id) = path.into_inner();
 
-    // Verify job exists and belongs to report
-    let job = pool
-        .get_job_by_id(job_id)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Job {}", job_id)))?;
-
-    if job.test_report_id != report_id {
-        return Err(AppError::NotFound(format!(
-            "Job {} in report {}",
-            job_id, report_id
-        )));
-    }
+    // Verify job belongs to report and still accepts uploads
+    get_writable_job(&pool, report_id, job_id).await?;
 
     // Get pending JSON files for this job
     let pending_files = pool.get_pending_json_files(job_id).await?;
@@ -1356,11 +1459,15 @@ pub async fn upload_json(
 
     let mut extraction_triggered = false;
 
-    // When all JSON files are uploaded, trigger extraction
-    if all_uploaded {
-        pool.set_json_upload_status(job_id, Some("completed"))
-            .await?;
-
+    // When all JSON files are uploaded, trigger extraction. Concurrent
+    // requests may all see every file uploaded; only the one completing the
+    // upload extracts.
+    if all_uploaded
+        && completed_upload(
+            pool.set_json_upload_status(job_id, UploadStatus::Completed)
+                .await,
+        )?
+    {
         // Update job status to processing
         pool.update_job_status(job_id, JobStatus::Processing, None)
             .await?;
@@ -1380,12 +1487,14 @@ pub async fn upload_json(
         let pool_clone = pool.get_ref().clone();
         let storage_clone = storage.get_ref().clone();
         let broadcaster_clone = broadcaster.get_ref().clone();
+        let features = config.features.clone();
         let framework = report.framework.clone();
         tokio::spawn(async move {
             extraction::extract_job(
                 &pool_clone,
                 &storage_clone,
                 &broadcaster_clone,
+                &features,
                 job_id,
                 &framework,
             )
@@ -1421,7 +1530,11 @@ pub async fn upload_json(
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("/jobs").route(web::get().to(query_jobs)))
         .service(web::resource("/reports/{report_id}/jobs/init").route(web::post().to(init_job)))
-        .service(web::resource("/reports/{report_id}/jobs/{job_id}").route(web::get().to(get_job)))
+        .service(
+            web::resource("/reports/{report_id}/jobs/{job_id}")
+                .route(web::get().to(get_job))
+                .route(web::delete().to(delete_job)),
+        )
         // HTML upload routes (request-then-transfer pattern)
         .service(
             web::resource("/reports/{report_id}/jobs/{job_id}/html/init")
@@ -1,20 +1,31 @@
 //! Report API handlers.
 
-use actix_web::{HttpResponse, web};
+use actix_web::{HttpRequest, HttpResponse, web};
 use serde::Serialize;
 use tracing::info;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::auth::ApiKeyAuth;
+use crate::config::{Config, IDEMPOTENCY_KEY_HEADER};
 use crate::db::DbPool;
 use crate::error::{AppError, AppResult};
 use crate::models::{
-    Framework, GitHubMetadata, JobGitHubMetadata, JobStatus, JobSummary, ListReportsQuery,
-    RegisterReportRequest, RegisterReportResponse, ReportDetailResponse, ReportListResponse,
-    ReportStatus, ReportSummary, WsEvent, WsEventMessage,
+    CloseReportResponse, Framework, GitHubMetadata, JobGitHubMetadata, JobStatus, JobSummary,
+    ListReportsQuery, MAX_EXPECTED_JOBS, OIDC_ADMIN_DENIED_MSG, OwnerFilterQuery,
+    RegisterReportRequest, RegisterReportResponse, ReportDetailResponse, ReportLineage,
+    ReportListResponse, ReportMetadataResponse, ReportStatus, ReportSummary, TriageState,
+    UpdateReportMetadataRequest, UpdateReportRequest, UpdateReportResponse, WsEvent,
+    WsEventMessage, labels_from_json,
 };
-use crate::services::EventBroadcaster;
+use crate::services::idempotency;
+use crate::services::labels::{validate_custom_metadata, validate_labels};
+use crate::services::pipeline;
+use crate::services::report_completion;
+use crate::services::run_lineage::parse_run_attempt;
+use crate::services::text::non_empty;
+use crate::services::triage::TriageResolver;
+use crate::services::{EventBroadcaster, Storage};
 
 /// Response for test suite (simplified for report-level aggregation).
 #[derive(Debug, Serialize, ToSchema)]
@@ -35,6 +46,10 @@ pub struct TestSuiteResponse {
     pub failed_count: i32,
     pub skipped_count: i32,
     pub flaky_count: i32,
+    /// Failures of quarantined tests (not included in failed_count).
+    pub quarantined_count: i32,
+    /// Failures matching a known issue (included in failed_count).
+    pub known_issue_count: i32,
     pub duration_ms: i32,
     /// Actual test execution start time from framework JSON.
     pub start_time: Option<chrono::DateTime<chrono::Utc>>,
@@ -72,6 +87,9 @@ pub struct TestResultResponse {
     pub errors_json: Option<String>,
     /// Attachments (screenshots, videos) for this test result.
     pub attachments: Option<serde_json::Value>,
+    /// Key of the known issue the failure matched at extraction time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub known_issue_key: Option<String>,
 }
 
 /// Response for a test spec (a logical test with potentially multiple results/retries).
@@ -86,6 +104,10 @@ pub struct TestSpecResponse {
     pub column: i32,
     pub results: Vec<TestResultResponse>,
     pub screenshots: Vec<ScreenshotInfo>,
+    /// Triage state, made for one of the spec's results or carried forward
+    /// from an earlier failure with the same signature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub triage: Option<TriageState>,
 }
 
 /// Screenshot info for a test spec.
@@ -153,15 +175,30 @@ pub struct SearchResponse {
 /// Register a new test report.
 ///
 /// Creates a report with expected job count and optional GitHub metadata.
+/// Without `expected_jobs` the report is open: each job registered raises
+/// its expected job count until the report is finalized or goes idle.
+///
+/// Registration is idempotent: a repeated registration by the same caller
+/// with the same `Idempotency-Key` header, or without one for the same
+/// workflow run attempt, framework and labels, returns the existing report.
+/// Reusing a key for another repository or framework is a conflict.
+///
+/// The report joins the pipeline run of its `group_key`, or of its
+/// repository, SHA and run ID.
 #[utoipa::path(
     post,
     path = "/reports",
     tag = "Reports",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Key identifying retries of the same registration (max 255 characters)")
+    ),
     request_body = RegisterReportRequest,
     responses(
+        (status = 200, description = "Existing report with the same idempotency key", body = RegisterReportResponse),
         (status = 201, description = "Report registered successfully", body = RegisterReportResponse),
         (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
         (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+        (status = 409, description = "Idempotency key used for another repository or framework", body = crate::error::ErrorResponse),
     ),
     security(
         ("api_key" = [])
@@ -169,6 +206,7 @@ pub struct SearchResponse {
 )]
 pub async fn register_report(
     auth: ApiKeyAuth,
+    http_req: HttpRequest,
     pool: web::Data<DbPool>,
     broadcaster: web::Data<EventBroadcaster>,
     body: web::Json<RegisterReportRequest>,
@@ -182,24 +220,73 @@ pub async fn register_report(
 
     let req = body.into_inner();
 
-    // Validate expected_jobs range
-    if req.expected_jobs < 1 || req.expected_jobs > 100 {
-        return Err(AppError::InvalidInput(
-            "expected_jobs must be between 1 and 100".to_string(),
-        ));
+    if let Some(expected_jobs) = req.expected_jobs {
+        validate_expected_jobs(expected_jobs)?;
     }
 
+    validate_labels(&req.labels).map_err(AppError::InvalidInput)?;
+    if let Some(ref custom_metadata) = req.custom_metadata {
+        validate_custom_metadata(custom_metadata).map_err(AppError::InvalidInput)?;
+    }
+
+    let header_key = match http_req.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        Some(value) => Some(value.to_str().map_err(|_| {
+            AppError::InvalidInput("Idempotency-Key must be visible ASCII".to_string())
+        })?),
+        None => None,
+    };
+    let idempotency_key = idempotency::report_key(
+        header_key,
+        &idempotency::caller_scope(&auth.caller),
+        req.github_metadata.as_ref(),
+        req.framework,
+        &req.labels,
+    )
+    .map_err(AppError::InvalidInput)?;
+    let repository = req
+        .github_metadata
+        .as_ref()
+        .and_then(|m| non_empty(m.repository.as_deref()));
+    let pipeline_identity =
+        pipeline::identity(req.group_key.as_deref(), req.github_metadata.as_ref())
+            .map_err(AppError::InvalidInput)?;
+
     // Keep github_metadata from request body only — OIDC claims stored separately
     let github_metadata = req.github_metadata;
 
     // Generate report ID (UUIDv7 for time-ordered sorting)
     let report_id = Uuid::now_v7();
 
-    // Insert report with JSONB github_metadata (caller-supplied only)
-    let report = pool
-        .insert_report(report_id, req.expected_jobs, req.framework, github_metadata)
+    // Insert report with JSONB github_metadata (caller-supplied only), joining
+    // the pipeline run of the CI run (retried registrations keep theirs)
+    let (report, created) = pool
+        .insert_report(
+            report_id,
+            req.expected_jobs,
+            req.framework,
+            github_metadata,
+            &req.labels,
+            req.custom_metadata,
+            idempotency_key,
+            pipeline_identity.as_ref(),
+        )
         .await?;
 
+    if !created {
+        // A key reused for another repository or framework must not hand out
+        // the existing report
+        let existing_repository =
+            non_empty(GitHubMetadata::from_json(report.github_metadata.as_ref()).repository);
+        if existing_repository != repository || report.framework != req.framework.as_str() {
+            return Err(AppError::Conflict(format!(
+                "Idempotency key already used by report {} for another repository or framework",
+                report.id
+            )));
+        }
+        info!("Returning existing report: id={}", report.id);
+        return Ok(HttpResponse::Ok().json(register_response(&report, true)));
+    }
+
     // If authenticated via OIDC, store safe claims in separate table
     if let Some(ref claims) = auth.caller.oidc_claims {
         let safe = claims.to_safe_claims(auth.caller.role.as_str(), "/api/v1/reports", "POST");
@@ -233,21 +320,34 @@ pub async fn register_report(
         report_id,
         req.framework.as_str(),
         req.expected_jobs
+            .map_or_else(|| "open".to_string(), |n| n.to_string())
     );
 
     // Broadcast report_created event
     let event = WsEventMessage::new(WsEvent::report_created(report_id));
     broadcaster.send(event);
 
-    let response = RegisterReportResponse {
+    if let Some(pipeline_id) = report.pipeline_id {
+        pipeline::broadcast_progress(&pool, &broadcaster, pipeline_id).await;
+    }
+
+    Ok(HttpResponse::Created().json(register_response(&report, false)))
+}
+
+fn register_response(
+    report: &crate::entity::test_report::Model,
+    is_existing: bool,
+) -> RegisterReportResponse {
+    RegisterReportResponse {
         report_id: report.id,
         status: ReportStatus::parse(&report.status).unwrap_or(ReportStatus::Initializing),
         expected_jobs: report.expected_jobs,
+        open: report.is_open,
         framework: Framework::parse(&report.framework).unwrap_or(Framework::Playwright),
+        pipeline_id: report.pipeline_id,
         created_at: report.created_at,
-    };
-
-    Ok(HttpResponse::Created().json(response))
+        is_existing,
+    }
 }
 
 /// List reports with pagination and filtering.
@@ -259,10 +359,24 @@ pub async fn register_report(
         ("limit" = Option<i32>, Query, description = "Results per page (default 20, max 100)"),
         ("offset" = Option<i32>, Query, description = "Pagination offset"),
         ("framework" = Option<String>, Query, description = "Filter by framework"),
-        ("status" = Option<String>, Query, description = "Filter by status")
+        ("status" = Option<String>, Query, description = "Filter by status"),
+        ("repository" = Option<String>, Query, description = "Filter by GitHub repository"),
+        ("ref" = Option<String>, Query, description = "Filter by git ref"),
+        ("sha" = Option<String>, Query, description = "Filter by commit SHA (full or at least 7 characters)"),
+        ("pr_number" = Option<i32>, Query, description = "Filter by pull request number"),
+        ("actor" = Option<String>, Query, description = "Filter by triggering GitHub user"),
+        ("workflow" = Option<String>, Query, description = "Filter by workflow name"),
+        ("event_name" = Option<String>, Query, description = "Filter by triggering event"),
+        ("from" = Option<String>, Query, description = "Created at or after (RFC 3339)"),
+        ("to" = Option<String>, Query, description = "Created at or before (RFC 3339)"),
+        ("has_failures" = Option<bool>, Query, description = "Only reports with (true) or without (false) failures"),
+        ("label" = Option<String>, Query, description = "Filter by labels (key:value[,key:value...])"),
+        ("sort" = Option<String>, Query, description = "created_at (default), failures or duration"),
+        ("order" = Option<String>, Query, description = "asc or desc (default)")
     ),
     responses(
         (status = 200, description = "List of reports", body = ReportListResponse),
+        (status = 400, description = "Invalid filter", body = crate::error::ErrorResponse),
     )
 )]
 pub async fn list_reports(
@@ -270,6 +384,14 @@ pub async fn list_reports(
     query: web::Query<ListReportsQuery>,
 ) -> AppResult<HttpResponse> {
     let query = query.into_inner();
+
+    if let Some(ref sha) = query.sha
+        && (!(7..=40).contains(&sha.len()) || !sha.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        return Err(AppError::InvalidInput(
+            "sha must be 7 to 40 hexadecimal characters".to_string(),
+        ));
+    }
     let (reports, total) = pool.list_reports(&query).await?;
 
     // Batch fetch completed job counts, test stats, and OIDC claims for all reports
@@ -308,10 +430,13 @@ pub async fn list_reports(
                 framework: Framework::parse(&r.framework).unwrap_or(Framework::Playwright),
                 status: ReportStatus::parse(&r.status).unwrap_or(ReportStatus::Initializing),
                 expected_jobs: r.expected_jobs,
+                open: r.is_open,
                 jobs_complete: *jobs_complete_map.get(&r.id).unwrap_or(&0),
+                missing_jobs: r.missing_jobs,
                 test_stats,
                 github_metadata,
                 oidc_claims,
+                labels: labels_from_json(&r.labels),
                 created_at: r.created_at,
             }
         })
@@ -333,7 +458,8 @@ pub async fn list_reports(
     path = "/reports/{report_id}/suites",
     tag = "Reports",
     params(
-        ("report_id" = Uuid, Path, description = "Report UUID")
+        ("report_id" = Uuid, Path, description = "Report UUID"),
+        ("owner" = Option<String>, Query, description = "Only suites containing tests with this owner")
     ),
     responses(
         (status = 200, description = "List of test suites for the report", body = ReportSuitesResponse),
@@ -343,6 +469,7 @@ pub async fn list_reports(
 pub async fn get_report_suites(
     pool: web::Data<DbPool>,
     path: web::Path<Uuid>,
+    query: web::Query<OwnerFilterQuery>,
 ) -> AppResult<HttpResponse> {
     let report_id = path.into_inner();
 
@@ -421,7 +548,9 @@ pub async fn get_report_suites(
     };
 
     // Get all test suites for this report (through jobs)
-    let suites = pool.get_test_suites_by_report_id(report_id).await?;
+    let suites = pool
+        .get_test_suites_by_report_id(report_id, query.owner.as_deref())
+        .await?;
 
     let suite_responses: Vec<TestSuiteResponse> = suites
         .into_iter()
@@ -445,6 +574,8 @@ pub async fn get_report_suites(
                 failed_count: s.failed_count,
                 skipped_count: s.skipped_count,
                 flaky_count: s.flaky_count,
+                quarantined_count: s.quarantined_count,
+                known_issue_count: s.known_issue_count,
                 duration_ms: s.duration_ms,
                 start_time: s.start_time,
                 created_at: s.created_at,
@@ -533,21 +664,371 @@ pub async fn get_report(pool: web::Data<DbPool>, path: web::Path<Uuid>) -> AppRe
     let oidc_claims =
         crate::db::report_oidc_claims::find_by_report_id(pool.connection(), report_id).await?;
 
+    // Per-owner results for routing failures
+    let owners = pool.get_owner_summaries_by_report(report_id).await?;
+
+    // Reports from other attempts of the same workflow run
+    let lineage = match github_metadata
+        .as_ref()
+        .and_then(|m| Some((m.repository.as_deref()?, m.run_id.as_deref()?, m)))
+    {
+        Some((repository, run_id, metadata)) => {
+            let attempts = super::runs::load_run_attempts(
+                &pool,
+                repository,
+                run_id,
+                metadata.workflow.as_deref(),
+                false,
+            )
+            .await?;
+            let run_attempt = parse_run_attempt(metadata.run_attempt.as_deref());
+            Some(ReportLineage {
+                run_id: run_id.to_string(),
+                run_attempt,
+                is_latest: attempts.iter().all(|a| a.run_attempt <= run_attempt),
+                attempts,
+            })
+        }
+        None => None,
+    };
+
     let response = ReportDetailResponse {
         id: report.id,
         framework: Framework::parse(&report.framework).unwrap_or(Framework::Playwright),
         status: ReportStatus::parse(&report.status).unwrap_or(ReportStatus::Initializing),
         expected_jobs: report.expected_jobs,
+        open: report.is_open,
+        missing_jobs: report.missing_jobs,
         github_metadata,
         oidc_claims,
+        labels: labels_from_json(&report.labels),
+        custom_metadata: report.custom_metadata,
+        pipeline_id: report.pipeline_id,
         created_at: report.created_at,
         updated_at: report.updated_at,
         jobs: job_summaries,
+        owners,
+        lineage,
+    };
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Update a report's labels and custom metadata.
+///
+/// Provided fields replace the stored value; omitted fields are unchanged.
+/// Requires contributor or admin role.
+#[utoipa::path(
+    patch,
+    path = "/reports/{report_id}/metadata",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID")
+    ),
+    request_body = UpdateReportMetadataRequest,
+    responses(
+        (status = 200, description = "Updated metadata", body = ReportMetadataResponse),
+        (status = 400, description = "Invalid labels or metadata", body = crate::error::ErrorResponse),
+        (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn update_report_metadata(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateReportMetadataRequest>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.role == crate::models::ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Viewer role cannot change report metadata".to_string(),
+        ));
+    }
+
+    let report_id = path.into_inner();
+    let body = body.into_inner();
+
+    if let Some(ref labels) = body.labels {
+        validate_labels(labels).map_err(AppError::InvalidInput)?;
+    }
+    if let Some(ref custom_metadata) = body.custom_metadata {
+        validate_custom_metadata(custom_metadata).map_err(AppError::InvalidInput)?;
+    }
+
+    let report = pool
+        .update_report_metadata(report_id, body.labels.as_ref(), body.custom_metadata)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(ReportMetadataResponse {
+        id: report.id,
+        labels: labels_from_json(&report.labels),
+        custom_metadata: report.custom_metadata,
+    }))
+}
+
+/// Change a report's expected job count.
+///
+/// Sets how many jobs a report waits for before it closes, ending open mode
+/// for open reports. The report closes right away if that many jobs already
+/// finished. Requires contributor or admin role.
+#[utoipa::path(
+    patch,
+    path = "/reports/{report_id}",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID")
+    ),
+    request_body = UpdateReportRequest,
+    responses(
+        (status = 200, description = "Updated report", body = UpdateReportResponse),
+        (status = 400, description = "Invalid job count", body = crate::error::ErrorResponse),
+        (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+        (status = 409, description = "Report already closed", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn update_report(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    broadcaster: web::Data<EventBroadcaster>,
+    config: web::Data<Config>,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateReportRequest>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.role == crate::models::ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Viewer role cannot update reports".to_string(),
+        ));
+    }
+
+    let report_id = path.into_inner();
+    let expected_jobs = body.into_inner().expected_jobs;
+    validate_expected_jobs(expected_jobs)?;
+
+    let report = match pool.set_expected_jobs(report_id, expected_jobs).await? {
+        Some(report) => report,
+        None => {
+            let report = pool
+                .get_report_by_id(report_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Report {}", report_id)))?;
+            return Err(report_completion::already_closed(&report));
+        }
     };
 
+    info!("Report {} expects {} jobs", report_id, report.expected_jobs);
+
+    // May close the report if enough jobs already finished
+    report_completion::check(&pool, &broadcaster, &config.features, report_id).await?;
+
+    let report = pool
+        .get_report_by_id(report_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report {}", report_id)))?;
+
+    Ok(HttpResponse::Ok().json(UpdateReportResponse {
+        id: report.id,
+        status: ReportStatus::parse(&report.status).unwrap_or(ReportStatus::Initializing),
+        expected_jobs: report.expected_jobs,
+        open: report.is_open,
+    }))
+}
+
+fn validate_expected_jobs(expected_jobs: i32) -> AppResult<()> {
+    if !(1..=MAX_EXPECTED_JOBS).contains(&expected_jobs) {
+        return Err(AppError::InvalidInput(format!(
+            "expected_jobs must be between 1 and {}",
+            MAX_EXPECTED_JOBS
+        )));
+    }
+    Ok(())
+}
+
+/// Finalize a report with the jobs that finished so far.
+///
+/// Closes a report that is still waiting for jobs, e.g. when a matrix job was
+/// cancelled or a shard skipped. The report becomes `complete` if any job
+/// completed and `failed` otherwise, and records how many expected jobs never
+/// finished. Requires contributor or admin role.
+#[utoipa::path(
+    post,
+    path = "/reports/{report_id}/finalize",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID")
+    ),
+    responses(
+        (status = 200, description = "Report finalized", body = CloseReportResponse),
+        (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+        (status = 409, description = "Report already closed", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn finalize_report(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    broadcaster: web::Data<EventBroadcaster>,
+    config: web::Data<Config>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.role == crate::models::ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Viewer role cannot finalize reports".to_string(),
+        ));
+    }
+
+    let response =
+        report_completion::finalize(&pool, &broadcaster, &config.features, path.into_inner())
+            .await?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Cancel a report.
+///
+/// Closes a report that is still waiting for jobs as `cancelled` and fails
+/// its pending jobs. Requires contributor or admin role.
+#[utoipa::path(
+    post,
+    path = "/reports/{report_id}/cancel",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID")
+    ),
+    responses(
+        (status = 200, description = "Report cancelled", body = CloseReportResponse),
+        (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+        (status = 409, description = "Report already closed", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn cancel_report(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    broadcaster: web::Data<EventBroadcaster>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.role == crate::models::ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Viewer role cannot cancel reports".to_string(),
+        ));
+    }
+
+    let response = report_completion::cancel(&pool, &broadcaster, path.into_inner()).await?;
+
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Reopen a closed report.
+///
+/// Closed reports reject new jobs and uploads. Reopening moves the report
+/// back to `uploading` so jobs can be added, e.g. to re-run a shard, and it
+/// closes again like any other report once a new job finishes. Requires the
+/// admin role.
+#[utoipa::path(
+    post,
+    path = "/reports/{report_id}/reopen",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID")
+    ),
+    responses(
+        (status = 200, description = "Report reopened", body = UpdateReportResponse),
+        (status = 401, description = "Admin role required", body = crate::error::ErrorResponse),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+        (status = 409, description = "Report isn't closed", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn reopen_report(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    broadcaster: web::Data<EventBroadcaster>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.is_oidc() {
+        return Err(AppError::Unauthorized(OIDC_ADMIN_DENIED_MSG.to_string()));
+    }
+    if !auth.caller.is_admin() {
+        return Err(AppError::Unauthorized(
+            "Admin role required to reopen reports".to_string(),
+        ));
+    }
+
+    let report = report_completion::reopen(&pool, &broadcaster, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(UpdateReportResponse {
+        id: report.id,
+        status: ReportStatus::parse(&report.status).unwrap_or(ReportStatus::Uploading),
+        expected_jobs: report.expected_jobs,
+        open: report.is_open,
+    }))
+}
+
+/// Delete a report with all its jobs, results and files.
+///
+/// The report is hidden from every query right away and purged from the
+/// database after the retention grace period; its stored files are deleted
+/// now. Requires the admin role.
+#[utoipa::path(
+    delete,
+    path = "/reports/{report_id}",
+    tag = "Reports",
+    params(
+        ("report_id" = Uuid, Path, description = "Report UUID")
+    ),
+    responses(
+        (status = 200, description = "Report deleted"),
+        (status = 401, description = "Admin role required", body = crate::error::ErrorResponse),
+        (status = 404, description = "Report not found", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn delete_report(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    storage: web::Data<Storage>,
+    broadcaster: web::Data<EventBroadcaster>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.is_oidc() {
+        return Err(AppError::Unauthorized(OIDC_ADMIN_DENIED_MSG.to_string()));
+    }
+    if !auth.caller.is_admin() {
+        return Err(AppError::Unauthorized(
+            "Admin role required to delete reports".to_string(),
+        ));
+    }
+
+    let report_id = path.into_inner();
+
+    if !pool.soft_delete_report(report_id).await? {
+        return Err(AppError::NotFound(format!("Report {}", report_id)));
+    }
+
+    broadcaster.send(WsEventMessage::new(WsEvent::report_deleted(report_id)));
+
+    // A failure here leaves files behind until retention purges the report
+    let files_deleted = storage
+        .delete_prefix(&Storage::report_key_prefix(&report_id.to_string()))
+        .await?;
+
+    info!(
+        "Deleted report {} ({} files removed from storage)",
+        report_id, files_deleted
+    );
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message": "Report deleted",
+        "id": report_id,
+        "files_deleted": files_deleted,
+    })))
+}
+
 /// Path parameters for suite specs endpoint.
 #[derive(serde::Deserialize)]
 pub struct SuiteSpecsPath {
@@ -579,7 +1060,7 @@ pub async fn get_suite_specs(
     } = path.into_inner();
 
     // Verify report exists
-    let _report = pool
+    let report = pool
         .get_report_by_id(report_id)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Report {}", report_id)))?;
@@ -593,6 +1074,21 @@ pub async fn get_suite_specs(
     // Get test cases for this suite
     let test_cases = pool.get_test_cases_by_suite_id(suite_id).await?;
 
+    // Load triage records made for these cases or their failure signatures
+    let test_case_ids: Vec<Uuid> = test_cases.iter().map(|c| c.id).collect();
+    let signatures: Vec<String> = test_cases
+        .iter()
+        .filter_map(|c| c.failure_signature.clone())
+        .collect();
+    let triage_records = crate::db::triage_records::list_for_test_cases(
+        pool.connection(),
+        &test_case_ids,
+        &signatures,
+    )
+    .await?;
+    let repository = GitHubMetadata::from_json(report.github_metadata.as_ref()).repository;
+    let triage = TriageResolver::new(triage_records, repository.as_deref());
+
     // Get screenshots for this job
     let screenshots = pool.get_screenshots_by_job_id(suite.test_job_id).await?;
 
@@ -636,6 +1132,7 @@ pub async fn get_suite_specs(
                         serde_json::to_string(&vec![msg]).unwrap_or_else(|_| "[]".to_string())
                     }),
                     attachments: c.attachments.clone(),
+                    known_issue_key: c.known_issue_key.clone(),
                 })
                 .collect();
 
@@ -684,6 +1181,20 @@ pub async fn get_suite_specs(
                 }
             });
 
+            // Prefer a record made for one of the attempts, latest attempt first
+            let spec_triage = cases
+                .iter()
+                .rev()
+                .filter_map(|c| {
+                    triage.resolve(
+                        c.id,
+                        &full_title,
+                        c.failure_signature.as_deref(),
+                        c.created_at,
+                    )
+                })
+                .min_by_key(|t| t.inherited);
+
             TestSpecResponse {
                 id: first.id,
                 title: first.title.clone(),
@@ -694,6 +1205,7 @@ pub async fn get_suite_specs(
                 column: 0,
                 results,
                 screenshots: spec_screenshots,
+                triage: spec_triage,
             }
         })
         .collect();
@@ -861,7 +1373,19 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(list_reports))
             .route(web::post().to(register_report)),
     )
-    .service(web::resource("/reports/{report_id}").route(web::get().to(get_report)))
+    .service(
+        web::resource("/reports/{report_id}")
+            .route(web::get().to(get_report))
+            .route(web::patch().to(update_report))
+            .route(web::delete().to(delete_report)),
+    )
+    .service(web::resource("/reports/{report_id}/finalize").route(web::post().to(finalize_report)))
+    .service(web::resource("/reports/{report_id}/cancel").route(web::post().to(cancel_report)))
+    .service(web::resource("/reports/{report_id}/reopen").route(web::post().to(reopen_report)))
+    .service(
+        web::resource("/reports/{report_id}/metadata")
+            .route(web::patch().to(update_report_metadata)),
+    )
     .service(web::resource("/reports/{report_id}/suites").route(web::get().to(get_report_suites)))
     .service(
         web::resource("/reports/{report_id}/suites/{suite_id}/specs")
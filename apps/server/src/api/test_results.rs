@@ -31,6 +31,10 @@ pub struct TestSuiteResponse {
     pub failed_count: i32,
     pub skipped_count: i32,
     pub flaky_count: i32,
+    /// Failures of quarantined tests (not included in failed_count).
+    pub quarantined_count: i32,
+    /// Failures matching a known issue (included in failed_count).
+    pub known_issue_count: i32,
     pub duration_ms: i32,
     pub created_at: DateTime<Utc>,
 }
@@ -49,6 +53,12 @@ pub struct TestCaseResponse {
     pub duration_ms: i32,
     pub retry_count: i32,
     pub error_message: Option<String>,
+    /// Whether the test matched the quarantine list at extraction time.
+    pub quarantined: bool,
+    /// Owner resolved from the repository's ownership mapping.
+    pub owner: Option<String>,
+    /// Key of the known issue the failure matched at extraction time.
+    pub known_issue_key: Option<String>,
     pub sequence: i32,
     pub created_at: DateTime<Utc>,
 }
@@ -91,6 +101,8 @@ pub struct QueryTestCasesQuery {
     pub suite_id: Option<Uuid>,
     /// Filter by status (passed, failed, skipped, flaky, timedOut).
     pub status: Option<String>,
+    /// Filter by resolved owner (e.g., "@org/team").
+    pub owner: Option<String>,
     /// Results per page (default 20, max 100).
     pub limit: Option<i64>,
     /// Pagination offset.
@@ -136,6 +148,8 @@ pub async fn query_test_suites(
             failed_count: s.failed_count,
             skipped_count: s.skipped_count,
             flaky_count: s.flaky_count,
+            quarantined_count: s.quarantined_count,
+            known_issue_count: s.known_issue_count,
             duration_ms: s.duration_ms,
             created_at: s.created_at,
         })
@@ -160,6 +174,7 @@ pub async fn query_test_suites(
         ("job_id" = Option<Uuid>, Query, description = "Filter by job ID"),
         ("suite_id" = Option<Uuid>, Query, description = "Filter by suite ID"),
         ("status" = Option<String>, Query, description = "Filter by status"),
+        ("owner" = Option<String>, Query, description = "Filter by resolved owner"),
         ("limit" = Option<i64>, Query, description = "Results per page (default 20, max 100)"),
         ("offset" = Option<i64>, Query, description = "Pagination offset")
     ),
@@ -175,6 +190,7 @@ pub async fn query_test_cases(
         job_id: query.job_id,
         suite_id: query.suite_id,
         status: query.status.clone(),
+        owner: query.owner.clone(),
         limit: query.limit.unwrap_or(20),
         offset: query.offset.unwrap_or(0),
     };
@@ -194,6 +210,9 @@ pub async fn query_test_cases(
             duration_ms: c.duration_ms,
             retry_count: c.retry_count,
             error_message: c.error_message,
+            quarantined: c.quarantined,
+            owner: c.owner,
+            known_issue_key: c.known_issue_key,
             sequence: c.sequence,
             created_at: c.created_at,
         })
@@ -251,6 +270,8 @@ pub async fn get_job_test_suites(
             failed_count: s.failed_count,
             skipped_count: s.skipped_count,
             flaky_count: s.flaky_count,
+            quarantined_count: s.quarantined_count,
+            known_issue_count: s.known_issue_count,
             duration_ms: s.duration_ms,
             created_at: s.created_at,
         })
@@ -274,6 +295,7 @@ pub async fn get_job_test_suites(
     params(
         ("job_id" = Uuid, Path, description = "Job UUID"),
         ("status" = Option<String>, Query, description = "Filter by status"),
+        ("owner" = Option<String>, Query, description = "Filter by resolved owner"),
         ("limit" = Option<i64>, Query, description = "Results per page (default 20, max 100)"),
         ("offset" = Option<i64>, Query, description = "Pagination offset")
     ),
@@ -292,6 +314,7 @@ pub async fn get_job_test_cases(
         job_id: Some(job_id),
         suite_id: query.suite_id,
         status: query.status.clone(),
+        owner: query.owner.clone(),
         limit: query.limit.unwrap_or(20),
         offset: query.offset.unwrap_or(0),
     };
@@ -311,6 +334,9 @@ pub async fn get_job_test_cases(
             duration_ms: c.duration_ms,
             retry_count: c.retry_count,
             error_message: c.error_message,
+            quarantined: c.quarantined,
+            owner: c.owner,
+            known_issue_key: c.known_issue_key,
             sequence: c.sequence,
             created_at: c.created_at,
         })
@@ -334,6 +360,7 @@ pub async fn get_job_test_cases(
     params(
         ("suite_id" = Uuid, Path, description = "Suite UUID"),
         ("status" = Option<String>, Query, description = "Filter by status"),
+        ("owner" = Option<String>, Query, description = "Filter by resolved owner"),
         ("limit" = Option<i64>, Query, description = "Results per page (default 20, max 100)"),
         ("offset" = Option<i64>, Query, description = "Pagination offset")
     ),
@@ -352,6 +379,7 @@ pub async fn get_suite_test_cases(
         job_id: None,
         suite_id: Some(suite_id),
         status: query.status.clone(),
+        owner: query.owner.clone(),
         limit: query.limit.unwrap_or(20),
         offset: query.offset.unwrap_or(0),
     };
@@ -371,6 +399,9 @@ pub async fn get_suite_test_cases(
             duration_ms: c.duration_ms,
             retry_count: c.retry_count,
             error_message: c.error_message,
+            quarantined: c.quarantined,
+            owner: c.owner,
+            known_issue_key: c.known_issue_key,
             sequence: c.sequence,
             created_at: c.created_at,
         })
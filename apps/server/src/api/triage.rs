@@ -0,0 +1,324 @@
+//! Failure triage API handlers.
+//!
+//! Triage records capture what was learned about a failure. They are attached
+//! to a test case or a failure signature and carry forward to later failures
+//! of the same test with the same signature.
+
+use actix_web::{HttpResponse, web};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::auth::ApiKeyAuth;
+use crate::db::DbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    ApiKeyRole, AuthenticatedCaller, CreateTriageRequest, GitHubMetadata, TriageListQuery,
+    TriageListResponse, TriageRecord, UpdateTriageRequest,
+};
+use crate::services::quarantine::is_valid_fingerprint;
+use crate::services::text::non_empty;
+
+/// Maximum length of triage notes.
+const MAX_NOTES_LENGTH: usize = 10_000;
+
+/// Maximum length of an issue link.
+const MAX_ISSUE_URL_LENGTH: usize = 2048;
+
+/// List triage records.
+#[utoipa::path(
+    get,
+    path = "/triage",
+    tag = "Triage",
+    params(
+        ("test_case_id" = Option<Uuid>, Query, description = "Records made for this test case"),
+        ("failure_signature" = Option<String>, Query, description = "Records for this failure signature"),
+        ("repository" = Option<String>, Query, description = "Records scoped to this repository"),
+        ("classification" = Option<String>, Query, description = "product_bug, test_bug, infra, flaky or known_issue")
+    ),
+    responses(
+        (status = 200, description = "Triage records, most recently updated first", body = TriageListResponse),
+    )
+)]
+pub async fn list_triage(
+    pool: web::Data<DbPool>,
+    query: web::Query<TriageListQuery>,
+) -> AppResult<HttpResponse> {
+    let records = crate::db::triage_records::list(pool.connection(), &query).await?;
+
+    Ok(HttpResponse::Ok().json(TriageListResponse { records }))
+}
+
+/// Get a triage record.
+#[utoipa::path(
+    get,
+    path = "/triage/{id}",
+    tag = "Triage",
+    params(("id" = Uuid, Path, description = "Triage record UUID")),
+    responses(
+        (status = 200, description = "Triage record", body = TriageRecord),
+        (status = 404, description = "Record not found", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn get_triage(pool: web::Data<DbPool>, path: web::Path<Uuid>) -> AppResult<HttpResponse> {
+    let id = path.into_inner();
+
+    let record = crate::db::triage_records::find_by_id(pool.connection(), id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Triage record {}", id)))?;
+
+    Ok(HttpResponse::Ok().json(record))
+}
+
+/// Triage a failure.
+///
+/// Triaging a test case records its title, failure signature and repository
+/// so that the record carries forward to later failures of the same test.
+/// Requires contributor or admin role.
+#[utoipa::path(
+    post,
+    path = "/triage",
+    tag = "Triage",
+    request_body = CreateTriageRequest,
+    responses(
+        (status = 201, description = "Triage record created", body = TriageRecord),
+        (status = 400, description = "Invalid input", body = crate::error::ErrorResponse),
+        (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+        (status = 404, description = "Test case not found", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn create_triage(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    body: web::Json<CreateTriageRequest>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.role == ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Viewer role cannot triage failures".to_string(),
+        ));
+    }
+
+    let body = body.into_inner();
+    let notes = validate_notes(body.notes)?;
+    let issue_url = validate_issue_url(body.issue_url)?;
+
+    let (test_case_id, full_title, failure_signature, repository) = match body.test_case_id {
+        Some(case_id) => {
+            let case = pool
+                .get_test_case_by_id(case_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Test case {}", case_id)))?;
+            let repository = match pool.get_job_by_id(case.test_job_id).await? {
+                Some(job) => pool
+                    .get_report_by_id(job.test_report_id)
+                    .await?
+                    .and_then(|r| GitHubMetadata::from_json(r.github_metadata.as_ref()).repository),
+                None => None,
+            };
+            (
+                Some(case.id),
+                Some(case.full_title),
+                case.failure_signature,
+                repository,
+            )
+        }
+        None => {
+            let signature = non_empty(body.failure_signature).ok_or_else(|| {
+                AppError::InvalidInput(
+                    "Either test_case_id or failure_signature is required".to_string(),
+                )
+            })?;
+            if !is_valid_fingerprint(&signature) {
+                return Err(AppError::InvalidInput(
+                    "failure_signature must be a lowercase SHA-256 hex digest".to_string(),
+                ));
+            }
+            let full_title = non_empty(body.full_title);
+            let repository = non_empty(body.repository);
+            if full_title.as_ref().is_some_and(|t| t.len() > 1000)
+                || repository.as_ref().is_some_and(|r| r.len() > 255)
+            {
+                return Err(AppError::InvalidInput(
+                    "Field exceeds maximum length".to_string(),
+                ));
+            }
+            (None, full_title, Some(signature), repository)
+        }
+    };
+
+    let now = Utc::now();
+    let record = TriageRecord {
+        id: Uuid::now_v7(),
+        test_case_id,
+        full_title,
+        failure_signature,
+        repository,
+        classification: body.classification,
+        notes,
+        issue_url,
+        author: author_name(&pool, &auth.caller).await?,
+        created_at: now,
+        updated_at: now,
+    };
+
+    crate::db::triage_records::insert(pool.connection(), &record, &auth.caller.key_id).await?;
+
+    Ok(HttpResponse::Created().json(record))
+}
+
+/// Update a triage record.
+///
+/// Omitted fields are left unchanged; an empty string clears notes or the
+/// issue link. Requires contributor or admin role.
+#[utoipa::path(
+    put,
+    path = "/triage/{id}",
+    tag = "Triage",
+    params(("id" = Uuid, Path, description = "Triage record UUID")),
+    request_body = UpdateTriageRequest,
+    responses(
+        (status = 200, description = "Triage record updated", body = TriageRecord),
+        (status = 400, description = "Invalid input", body = crate::error::ErrorResponse),
+        (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+        (status = 404, description = "Record not found", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn update_triage(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateTriageRequest>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.role == ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Viewer role cannot triage failures".to_string(),
+        ));
+    }
+
+    let id = path.into_inner();
+    let body = body.into_inner();
+
+    let existing = crate::db::triage_records::find_by_id(pool.connection(), id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Triage record {}", id)))?;
+
+    let notes = match body.notes {
+        Some(notes) => validate_notes(Some(notes))?,
+        None => existing.notes,
+    };
+    let issue_url = match body.issue_url {
+        Some(url) => validate_issue_url(Some(url))?,
+        None => existing.issue_url,
+    };
+
+    let record = crate::db::triage_records::update(
+        pool.connection(),
+        id,
+        body.classification.unwrap_or(existing.classification),
+        notes,
+        issue_url,
+    )
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Triage record {}", id)))?;
+
+    Ok(HttpResponse::Ok().json(record))
+}
+
+/// Delete a triage record.
+///
+/// Requires contributor or admin role.
+#[utoipa::path(
+    delete,
+    path = "/triage/{id}",
+    tag = "Triage",
+    params(("id" = Uuid, Path, description = "Triage record UUID")),
+    responses(
+        (status = 200, description = "Triage record deleted"),
+        (status = 401, description = "Unauthorized", body = crate::error::ErrorResponse),
+        (status = 404, description = "Record not found", body = crate::error::ErrorResponse),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn delete_triage(
+    auth: ApiKeyAuth,
+    pool: web::Data<DbPool>,
+    path: web::Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    if auth.caller.role == ApiKeyRole::Viewer {
+        return Err(AppError::Unauthorized(
+            "Viewer role cannot delete triage records".to_string(),
+        ));
+    }
+
+    let id = path.into_inner();
+    let deleted = crate::db::triage_records::delete(pool.connection(), id).await?;
+
+    if deleted {
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "message": "Triage record deleted",
+            "id": id,
+        })))
+    } else {
+        Err(AppError::NotFound(format!("Triage record {}", id)))
+    }
+}
+
+/// Resolve a display name for the caller.
+///
+/// Uses the GitHub username for OAuth sessions, the workflow actor for OIDC
+/// tokens and the key name for API keys.
+async fn author_name(pool: &DbPool, caller: &AuthenticatedCaller) -> AppResult<String> {
+    if let Some(ref claims) = caller.oidc_claims {
+        return Ok(claims.actor.clone());
+    }
+
+    if let Some(user_id) = caller.key_id.strip_prefix("session:") {
+        let user = crate::db::users::find_by_id(pool.connection(), user_id).await?;
+        return Ok(user
+            .map(|u| u.username)
+            .unwrap_or_else(|| caller.key_id.clone()));
+    }
+
+    let key = crate::db::api_keys::find_by_id(pool.connection(), &caller.key_id).await?;
+    Ok(key.map(|k| k.name).unwrap_or_else(|| caller.key_id.clone()))
+}
+
+fn validate_notes(notes: Option<String>) -> AppResult<Option<String>> {
+    let notes = non_empty(notes);
+    if notes.as_ref().is_some_and(|n| n.len() > MAX_NOTES_LENGTH) {
+        return Err(AppError::InvalidInput(format!(
+            "notes exceed {} characters",
+            MAX_NOTES_LENGTH
+        )));
+    }
+    Ok(notes)
+}
+
+fn validate_issue_url(url: Option<String>) -> AppResult<Option<String>> {
+    let url = non_empty(url);
+    if let Some(ref url) = url
+        && (url.len() > MAX_ISSUE_URL_LENGTH
+            || !(url.starts_with("https://") || url.starts_with("http://")))
+    {
+        return Err(AppError::InvalidInput(
+            "issue_url must be an http(s) URL of at most 2048 characters".to_string(),
+        ));
+    }
+    Ok(url)
+}
+
+/// Configure triage routes.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/triage")
+            .route(web::get().to(list_triage))
+            .route(web::post().to(create_triage)),
+    )
+    .service(
+        web::resource("/triage/{id}")
+            .route(web::get().to(get_triage))
+            .route(web::put().to(update_triage))
+            .route(web::delete().to(delete_triage)),
+    );
+}
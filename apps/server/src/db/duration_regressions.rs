@@ -0,0 +1,238 @@
+//! Database queries for test duration baselines and regressions.
+
+use sea_orm::{ConnectionTrait, FromQueryResult, Statement};
+use uuid::Uuid;
+
+use crate::config::FeatureSettings;
+use crate::error::{AppError, AppResult};
+use crate::models::SlowestTestsQuery;
+
+use super::DbPool;
+
+/// Minimum number of baseline runs before a test can be flagged.
+const MIN_BASELINE_SAMPLES: i32 = 3;
+
+/// A stored duration regression row.
+#[derive(Debug, FromQueryResult)]
+pub struct DurationRegressionRow {
+    pub test_case_id: Uuid,
+    pub full_title: String,
+    pub duration_ms: i32,
+    pub baseline_median_ms: i32,
+    pub baseline_p90_ms: i32,
+    pub sample_count: i32,
+}
+
+/// A row of the slowest tests ranking.
+#[derive(Debug, FromQueryResult)]
+pub struct SlowestTestRow {
+    pub repository: Option<String>,
+    pub framework: String,
+    pub full_title: String,
+    pub run_count: i64,
+    pub median_ms: i64,
+    pub p90_ms: i64,
+    pub max_ms: i64,
+}
+
+impl DbPool {
+    /// Compare a report's passing tests against their duration baselines and
+    /// store the ones that regressed. Replaces any previous result for the report.
+    ///
+    /// The baseline for a test is the median and p90 over its most recent
+    /// successful runs in earlier reports of the same repository and framework.
+    /// A test is flagged when it is slower than the median by the configured
+    /// percentage and minimum delta, and also slower than the p90, so tests
+    /// whose duration normally varies that much aren't reported.
+    /// Returns the number of regressions found.
+    pub async fn detect_duration_regressions(
+        &self,
+        report_id: Uuid,
+        settings: &FeatureSettings,
+    ) -> AppResult<u64> {
+        self.connection()
+            .execute_raw(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Postgres,
+                "DELETE FROM duration_regressions WHERE report_id = $1",
+                [sea_orm::Value::from(report_id)],
+            ))
+            .await
+            .map_err(|e| {
+                AppError::Database(format!("Failed to clear duration regressions: {}", e))
+            })?;
+
+        let sql = r#"
+            WITH report AS (
+                SELECT id, framework, created_at, github_metadata->>'repository' as repository
+                FROM test_reports
+                WHERE id = $1
+            ),
+            current_runs AS (
+                -- Last passing attempt per test in this report
+                SELECT DISTINCT ON (tc.full_title)
+                    tc.id, tc.full_title, tc.duration_ms
+                FROM test_cases tc
+                INNER JOIN test_jobs j ON j.id = tc.test_job_id
+                WHERE j.test_report_id = $1
+                    AND tc.status = 'passed'
+                    AND tc.deleted_at IS NULL
+                ORDER BY tc.full_title, tc.retry_count DESC
+            ),
+            history AS (
+                SELECT
+                    h.full_title,
+                    h.duration_ms,
+                    ROW_NUMBER() OVER (PARTITION BY h.full_title ORDER BY h.created_at DESC) as rn
+                FROM test_cases h
+                INNER JOIN test_jobs hj ON hj.id = h.test_job_id
+                INNER JOIN test_reports hr ON hr.id = hj.test_report_id
+                CROSS JOIN report
+                WHERE h.full_title IN (SELECT full_title FROM current_runs)
+                    AND h.status = 'passed'
+                    AND h.deleted_at IS NULL
+                    AND hr.deleted_at IS NULL
+                    AND hr.id <> report.id
+                    AND hr.created_at < report.created_at
+                    AND hr.framework = report.framework
+                    AND (hr.github_metadata->>'repository') IS NOT DISTINCT FROM report.repository
+            ),
+            baseline AS (
+                SELECT
+                    full_title,
+                    COUNT(*)::INTEGER as sample_count,
+                    percentile_cont(0.5) WITHIN GROUP (ORDER BY duration_ms) as median_ms,
+                    percentile_cont(0.9) WITHIN GROUP (ORDER BY duration_ms) as p90_ms
+                FROM history
+                WHERE rn <= $2
+                GROUP BY full_title
+            )
+            INSERT INTO duration_regressions (
+                report_id, test_case_id, full_title, duration_ms,
+                baseline_median_ms, baseline_p90_ms, sample_count
+            )
+            SELECT
+                $1, c.id, c.full_title, c.duration_ms,
+                b.median_ms::INTEGER, b.p90_ms::INTEGER, b.sample_count
+            FROM current_runs c
+            INNER JOIN baseline b ON b.full_title = c.full_title
+            WHERE b.sample_count >= $3
+                AND c.duration_ms > b.median_ms * $4
+                AND c.duration_ms - b.median_ms >= $5
+                AND c.duration_ms > b.p90_ms
+        "#;
+
+        let factor = 1.0 + settings.regression_threshold_pct as f64 / 100.0;
+        let values: Vec<sea_orm::Value> = vec![
+            report_id.into(),
+            (settings.regression_baseline_runs as i64).into(),
+            MIN_BASELINE_SAMPLES.into(),
+            factor.into(),
+            (settings.regression_min_delta_ms as f64).into(),
+        ];
+
+        let result = self
+            .connection()
+            .execute_raw(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Postgres,
+                sql,
+                values,
+            ))
+            .await
+            .map_err(|e| {
+                AppError::Database(format!("Failed to detect duration regressions: {}", e))
+            })?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Get stored duration regressions for a report.
+    pub async fn get_duration_regressions(
+        &self,
+        report_id: Uuid,
+    ) -> AppResult<Vec<DurationRegressionRow>> {
+        DurationRegressionRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            r#"
+            SELECT test_case_id, full_title, duration_ms,
+                   baseline_median_ms, baseline_p90_ms, sample_count
+            FROM duration_regressions
+            WHERE report_id = $1
+            "#,
+            [sea_orm::Value::from(report_id)],
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to get duration regressions: {}", e)))
+    }
+
+    /// Rank tests by median duration of successful runs.
+    pub async fn get_slowest_tests(
+        &self,
+        query: &SlowestTestsQuery,
+    ) -> AppResult<Vec<SlowestTestRow>> {
+        let mut conditions = vec![
+            "tc.status = 'passed'".to_string(),
+            "tc.deleted_at IS NULL".to_string(),
+            "r.deleted_at IS NULL".to_string(),
+        ];
+        let mut values: Vec<sea_orm::Value> = Vec::new();
+
+        if let Some(ref repository) = query.repository {
+            values.push(repository.clone().into());
+            conditions.push(format!(
+                "r.github_metadata->>'repository' = ${}",
+                values.len()
+            ));
+        }
+
+        if let Some(framework) = query.framework {
+            values.push(framework.as_str().into());
+            conditions.push(format!("r.framework = ${}", values.len()));
+        }
+
+        // Default window keeps the ranking representative of recent runs
+        let from = query
+            .from
+            .unwrap_or_else(|| chrono::Utc::now() - chrono::Duration::days(30));
+        values.push(from.into());
+        conditions.push(format!("tc.created_at >= ${}", values.len()));
+
+        if let Some(to) = query.to {
+            values.push(to.into());
+            conditions.push(format!("tc.created_at <= ${}", values.len()));
+        }
+
+        values.push((query.limit.clamp(1, 100) as i64).into());
+
+        let sql = format!(
+            r#"
+            SELECT
+                r.github_metadata->>'repository' as repository,
+                r.framework,
+                tc.full_title,
+                COUNT(*) as run_count,
+                percentile_cont(0.5) WITHIN GROUP (ORDER BY tc.duration_ms)::BIGINT as median_ms,
+                percentile_cont(0.9) WITHIN GROUP (ORDER BY tc.duration_ms)::BIGINT as p90_ms,
+                MAX(tc.duration_ms)::BIGINT as max_ms
+            FROM test_cases tc
+            INNER JOIN test_jobs j ON j.id = tc.test_job_id
+            INNER JOIN test_reports r ON r.id = j.test_report_id
+            WHERE {}
+            GROUP BY 1, 2, 3
+            ORDER BY median_ms DESC
+            LIMIT ${}
+            "#,
+            conditions.join(" AND "),
+            values.len()
+        );
+
+        SlowestTestRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            &sql,
+            values,
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to get slowest tests: {}", e)))
+    }
+}
@@ -0,0 +1,184 @@
+//! Database queries for failure signature clustering.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, EntityTrait, FromQueryResult, JoinType, QueryFilter, QueryOrder, QuerySelect,
+    RelationTrait, Statement,
+};
+use uuid::Uuid;
+
+use crate::entity::test_case::{self, Entity as TestCase};
+use crate::entity::test_job;
+use crate::error::{AppError, AppResult};
+use crate::models::FailureSignaturesQuery;
+
+use super::DbPool;
+
+/// Aggregated totals for one signature across reports.
+#[derive(Debug, FromQueryResult)]
+pub struct SignatureTotals {
+    pub failure_signature: String,
+    pub occurrences: i64,
+    pub report_count: i64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub representative_error: Option<String>,
+}
+
+/// Occurrences of one signature within one time bucket.
+#[derive(Debug, FromQueryResult)]
+pub struct SignatureBucketCount {
+    pub failure_signature: String,
+    pub bucket_start: DateTime<Utc>,
+    pub occurrences: i64,
+}
+
+/// Build the shared WHERE clause for cross-report signature queries.
+///
+/// Returns the SQL fragment and its bind values; placeholders start at `$1`.
+fn signature_filters(query: &FailureSignaturesQuery) -> (String, Vec<sea_orm::Value>) {
+    let mut conditions = vec![
+        "tc.failure_signature IS NOT NULL".to_string(),
+        "tc.deleted_at IS NULL".to_string(),
+        "r.deleted_at IS NULL".to_string(),
+    ];
+    let mut values: Vec<sea_orm::Value> = Vec::new();
+
+    if let Some(ref repository) = query.repository {
+        values.push(repository.clone().into());
+        conditions.push(format!(
+            "r.github_metadata->>'repository' = ${}",
+            values.len()
+        ));
+    }
+
+    if let Some(ref git_ref) = query.git_ref {
+        values.push(git_ref.clone().into());
+        conditions.push(format!("r.github_metadata->>'ref' = ${}", values.len()));
+    }
+
+    if let Some(framework) = query.framework {
+        values.push(framework.as_str().into());
+        conditions.push(format!("r.framework = ${}", values.len()));
+    }
+
+    if let Some(from) = query.from {
+        values.push(from.into());
+        conditions.push(format!("tc.created_at >= ${}", values.len()));
+    }
+
+    if let Some(to) = query.to {
+        values.push(to.into());
+        conditions.push(format!("tc.created_at <= ${}", values.len()));
+    }
+
+    (conditions.join(" AND "), values)
+}
+
+impl DbPool {
+    /// Get failed test cases that have a failure signature within a report.
+    pub async fn get_signed_failures_by_report(
+        &self,
+        report_id: Uuid,
+    ) -> AppResult<Vec<test_case::Model>> {
+        let result = TestCase::find()
+            .join(JoinType::InnerJoin, test_case::Relation::Job.def())
+            .filter(test_job::Column::TestReportId.eq(report_id))
+            .filter(test_case::Column::FailureSignature.is_not_null())
+            .filter(test_case::Column::DeletedAt.is_null())
+            .order_by_asc(test_case::Column::TestJobId)
+            .order_by_asc(test_case::Column::Sequence)
+            .all(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to get report failures: {}", e)))?;
+
+        Ok(result)
+    }
+
+    /// Get the most common failure signatures across reports.
+    pub async fn get_top_failure_signatures(
+        &self,
+        query: &FailureSignaturesQuery,
+    ) -> AppResult<Vec<SignatureTotals>> {
+        let (where_clause, mut values) = signature_filters(query);
+        let limit = query.limit.clamp(1, 50) as i64;
+        values.push(limit.into());
+
+        let sql = format!(
+            r#"
+            SELECT
+                tc.failure_signature,
+                COUNT(*) as occurrences,
+                COUNT(DISTINCT r.id) as report_count,
+                MIN(tc.created_at) as first_seen,
+                MAX(tc.created_at) as last_seen,
+                (ARRAY_AGG(tc.error_message ORDER BY tc.created_at DESC))[1] as representative_error
+            FROM test_cases tc
+            INNER JOIN test_jobs j ON j.id = tc.test_job_id
+            INNER JOIN test_reports r ON r.id = j.test_report_id
+            WHERE {}
+            GROUP BY tc.failure_signature
+            ORDER BY occurrences DESC, tc.failure_signature
+            LIMIT ${}
+            "#,
+            where_clause,
+            values.len()
+        );
+
+        SignatureTotals::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            &sql,
+            values,
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to get failure signatures: {}", e)))
+    }
+
+    /// Get per-bucket occurrence counts for the given signatures.
+    pub async fn get_failure_signature_buckets(
+        &self,
+        query: &FailureSignaturesQuery,
+        signatures: &[String],
+    ) -> AppResult<Vec<SignatureBucketCount>> {
+        if signatures.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (where_clause, mut values) = signature_filters(query);
+        let start = values.len() + 1;
+        let in_clause: String = (start..start + signatures.len())
+            .map(|i| format!("${}", i))
+            .collect::<Vec<_>>()
+            .join(", ");
+        values.extend(signatures.iter().map(|s| s.clone().into()));
+
+        // Bucket name comes from a closed enum, safe to inline
+        let sql = format!(
+            r#"
+            SELECT
+                tc.failure_signature,
+                date_trunc('{}', tc.created_at) as bucket_start,
+                COUNT(*) as occurrences
+            FROM test_cases tc
+            INNER JOIN test_jobs j ON j.id = tc.test_job_id
+            INNER JOIN test_reports r ON r.id = j.test_report_id
+            WHERE {} AND tc.failure_signature IN ({})
+            GROUP BY 1, 2
+            ORDER BY 1, 2
+            "#,
+            query.bucket.as_str(),
+            where_clause,
+            in_clause
+        );
+
+        SignatureBucketCount::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            &sql,
+            values,
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to get failure signature buckets: {}", e)))
+    }
+}
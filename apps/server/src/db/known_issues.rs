@@ -0,0 +1,128 @@
+//! Database operations for known issue rules.
+
+use chrono::Utc;
+use sea_orm::*;
+use uuid::Uuid;
+
+use crate::entity::known_issue::{ActiveModel, Column, Entity, Model};
+use crate::error::AppResult;
+use crate::models::KnownIssue;
+
+/// Insert a new known issue.
+pub async fn insert(db: &DatabaseConnection, issue: &KnownIssue) -> AppResult<()> {
+    let model = ActiveModel {
+        id: Set(issue.id),
+        issue_key: Set(issue.issue_key.clone()),
+        issue_url: Set(issue.issue_url.clone()),
+        description: Set(issue.description.clone()),
+        repository: Set(issue.repository.clone()),
+        failure_signature: Set(issue.failure_signature.clone()),
+        error_pattern: Set(issue.error_pattern.clone()),
+        title_pattern: Set(issue.title_pattern.clone()),
+        enabled: Set(issue.enabled),
+        created_by: Set(issue.created_by.clone()),
+        created_at: Set(issue.created_at),
+        updated_at: Set(issue.updated_at),
+        deleted_at: Set(None),
+    };
+
+    Entity::insert(model).exec(db).await?;
+
+    Ok(())
+}
+
+/// Find a known issue by ID (non-deleted).
+pub async fn find_by_id(db: &DatabaseConnection, id: Uuid) -> AppResult<Option<KnownIssue>> {
+    let result = Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
+
+    Ok(result.map(model_to_issue))
+}
+
+/// List known issues (non-deleted) in creation order.
+///
+/// A repository filter keeps unscoped rules, since those apply everywhere.
+pub async fn list(
+    db: &DatabaseConnection,
+    repository: Option<&str>,
+    enabled_only: bool,
+) -> AppResult<Vec<KnownIssue>> {
+    let mut query = Entity::find().filter(Column::DeletedAt.is_null());
+
+    if let Some(repository) = repository {
+        query = query.filter(
+            Condition::any()
+                .add(Column::Repository.is_null())
+                .add(Column::Repository.eq(repository)),
+        );
+    }
+
+    if enabled_only {
+        query = query.filter(Column::Enabled.eq(true));
+    }
+
+    let results = query.order_by_asc(Column::CreatedAt).all(db).await?;
+
+    Ok(results.into_iter().map(model_to_issue).collect())
+}
+
+/// Update a known issue's mutable fields.
+pub async fn update(db: &DatabaseConnection, issue: &KnownIssue) -> AppResult<Option<KnownIssue>> {
+    let model = Entity::find_by_id(issue.id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
+
+    let Some(m) = model else {
+        return Ok(None);
+    };
+
+    let mut active: ActiveModel = m.into();
+    active.issue_key = Set(issue.issue_key.clone());
+    active.issue_url = Set(issue.issue_url.clone());
+    active.description = Set(issue.description.clone());
+    active.failure_signature = Set(issue.failure_signature.clone());
+    active.error_pattern = Set(issue.error_pattern.clone());
+    active.title_pattern = Set(issue.title_pattern.clone());
+    active.enabled = Set(issue.enabled);
+    active.updated_at = Set(Utc::now());
+    let updated = active.update(db).await?;
+
+    Ok(Some(model_to_issue(updated)))
+}
+
+/// Soft-delete a known issue.
+pub async fn delete(db: &DatabaseConnection, id: Uuid) -> AppResult<bool> {
+    let model = Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
+
+    if let Some(m) = model {
+        let mut active: ActiveModel = m.into();
+        active.deleted_at = Set(Some(Utc::now()));
+        active.update(db).await?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+fn model_to_issue(m: Model) -> KnownIssue {
+    KnownIssue {
+        id: m.id,
+        issue_key: m.issue_key,
+        issue_url: m.issue_url,
+        description: m.description,
+        repository: m.repository,
+        failure_signature: m.failure_signature,
+        error_pattern: m.error_pattern,
+        title_pattern: m.title_pattern,
+        enabled: m.enabled,
+        created_by: m.created_by,
+        created_at: m.created_at,
+        updated_at: m.updated_at,
+    }
+}
@@ -1,15 +1,25 @@
 //! Database module providing connection management, migrations, and queries.
 
 pub mod api_keys;
+pub mod duration_regressions;
+pub mod failure_signatures;
 pub mod github_oidc_policies;
 pub mod html_files;
 pub mod json_files;
+pub mod known_issues;
+pub mod ownership_mappings;
+pub mod pipeline_runs;
+pub mod quarantined_tests;
 pub mod refresh_tokens;
+pub mod report_bundles;
 pub mod report_oidc_claims;
+pub mod report_rollups;
+pub mod retention;
 pub mod screenshots;
 pub mod test_jobs;
 pub mod test_reports;
 pub mod test_results;
+pub mod triage_records;
 pub mod users;
 
 use sea_orm::{ConnectOptions, Database, DatabaseConnection};
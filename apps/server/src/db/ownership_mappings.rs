@@ -0,0 +1,93 @@
+//! Database operations for test ownership mappings.
+
+use chrono::Utc;
+use sea_orm::*;
+use uuid::Uuid;
+
+use crate::entity::ownership_mapping::{ActiveModel, Column, Entity, Model};
+use crate::error::AppResult;
+use crate::models::{OwnershipMapping, TitleOwnerRule};
+
+/// Find the active mapping for a repository.
+pub async fn find_by_repository(
+    db: &DatabaseConnection,
+    repository: &str,
+) -> AppResult<Option<OwnershipMapping>> {
+    let result = Entity::find()
+        .filter(Column::Repository.eq(repository))
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
+
+    Ok(result.map(model_to_mapping))
+}
+
+/// Create or replace the mapping for a repository.
+pub async fn upsert(
+    db: &DatabaseConnection,
+    repository: &str,
+    codeowners: Option<String>,
+    title_rules: &[TitleOwnerRule],
+    updated_by: &str,
+) -> AppResult<OwnershipMapping> {
+    let rules_json = serde_json::to_value(title_rules)?;
+
+    let existing = Entity::find()
+        .filter(Column::Repository.eq(repository))
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
+
+    let model = if let Some(m) = existing {
+        let mut active: ActiveModel = m.into();
+        active.codeowners = Set(codeowners);
+        active.title_rules = Set(rules_json);
+        active.updated_by = Set(Some(updated_by.to_string()));
+        active.update(db).await?
+    } else {
+        let now = Utc::now();
+        ActiveModel {
+            id: Set(Uuid::now_v7()),
+            repository: Set(repository.to_string()),
+            codeowners: Set(codeowners),
+            title_rules: Set(rules_json),
+            updated_by: Set(Some(updated_by.to_string())),
+            created_at: Set(now),
+            updated_at: Set(now),
+            deleted_at: Set(None),
+        }
+        .insert(db)
+        .await?
+    };
+
+    Ok(model_to_mapping(model))
+}
+
+/// Soft-delete the mapping for a repository.
+pub async fn delete(db: &DatabaseConnection, repository: &str) -> AppResult<bool> {
+    let model = Entity::find()
+        .filter(Column::Repository.eq(repository))
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
+
+    if let Some(m) = model {
+        let mut active: ActiveModel = m.into();
+        active.deleted_at = Set(Some(Utc::now()));
+        active.update(db).await?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+fn model_to_mapping(m: Model) -> OwnershipMapping {
+    OwnershipMapping {
+        repository: m.repository,
+        codeowners: m.codeowners,
+        title_rules: serde_json::from_value(m.title_rules).unwrap_or_default(),
+        updated_by: m.updated_by,
+        created_at: m.created_at,
+        updated_at: m.updated_at,
+    }
+}
@@ -0,0 +1,129 @@
+//! Database queries for pipeline runs.
+
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Statement,
+};
+use uuid::Uuid;
+
+use crate::entity::pipeline_run::{self, Entity as PipelineRun};
+use crate::entity::test_report::{self as report, Entity as Report};
+use crate::error::{AppError, AppResult};
+use crate::models::ListPipelinesQuery;
+use crate::services::pipeline::PipelineIdentity;
+
+use super::DbPool;
+
+/// Pipeline runs with at least one live report.
+const HAS_LIVE_REPORTS_SQL: &str = "EXISTS (SELECT 1 FROM test_reports r \
+     WHERE r.pipeline_id = pipeline_runs.id AND r.deleted_at IS NULL)";
+
+/// Get the pipeline run with the identity's key, creating it if needed.
+///
+/// The run identity is kept from the first report that joined. Runs on `db`
+/// so the caller can insert the joining report in the same transaction.
+pub(super) async fn upsert_pipeline_run(
+    db: &impl ConnectionTrait,
+    identity: &PipelineIdentity,
+) -> AppResult<pipeline_run::Model> {
+    let sql = r#"
+        INSERT INTO pipeline_runs (id, pipeline_key, group_key, repository, git_ref, sha, run_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (pipeline_key) DO UPDATE SET updated_at = NOW()
+        RETURNING *
+    "#;
+
+    PipelineRun::find()
+        .from_raw_sql(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            sql,
+            [
+                Uuid::now_v7().into(),
+                identity.key.clone().into(),
+                identity.group_key.clone().into(),
+                identity.repository.clone().into(),
+                identity.git_ref.clone().into(),
+                identity.sha.clone().into(),
+                identity.run_id.clone().into(),
+            ],
+        ))
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to upsert pipeline run: {}", e)))?
+        .ok_or_else(|| AppError::Database("Failed to upsert pipeline run".to_string()))
+}
+
+impl DbPool {
+    /// Get a pipeline run by ID.
+    pub async fn get_pipeline_run(&self, id: Uuid) -> AppResult<Option<pipeline_run::Model>> {
+        PipelineRun::find_by_id(id)
+            .one(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to get pipeline run: {}", e)))
+    }
+
+    /// List pipeline runs with live reports, newest first.
+    pub async fn list_pipeline_runs(
+        &self,
+        query: &ListPipelinesQuery,
+    ) -> AppResult<(Vec<pipeline_run::Model>, u64)> {
+        use sea_orm::sea_query::Expr;
+
+        let mut select = PipelineRun::find().filter(Expr::cust(HAS_LIVE_REPORTS_SQL));
+
+        if let Some(ref repository) = query.repository {
+            select = select.filter(pipeline_run::Column::Repository.eq(repository.as_str()));
+        }
+
+        if let Some(ref git_ref) = query.git_ref {
+            select = select.filter(pipeline_run::Column::GitRef.eq(git_ref.as_str()));
+        }
+
+        // Abbreviated SHAs match as a prefix (validated as hex by the handler)
+        if let Some(ref sha) = query.sha {
+            select = select.filter(pipeline_run::Column::Sha.starts_with(sha.to_lowercase()));
+        }
+
+        if let Some(ref group_key) = query.group_key {
+            select = select.filter(pipeline_run::Column::GroupKey.eq(group_key.as_str()));
+        }
+
+        let total = select
+            .clone()
+            .count(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to count pipeline runs: {}", e)))?;
+
+        let limit = query.limit.clamp(1, 100) as u64;
+        let offset = query.offset.max(0) as u64;
+
+        let pipelines = select
+            .order_by_desc(pipeline_run::Column::CreatedAt)
+            .order_by_desc(pipeline_run::Column::Id)
+            .offset(offset)
+            .limit(limit)
+            .all(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list pipeline runs: {}", e)))?;
+
+        Ok((pipelines, total))
+    }
+
+    /// Get the live reports of pipeline runs, oldest first.
+    pub async fn get_reports_by_pipeline_ids(
+        &self,
+        pipeline_ids: &[Uuid],
+    ) -> AppResult<Vec<report::Model>> {
+        if pipeline_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Report::find()
+            .filter(report::Column::PipelineId.is_in(pipeline_ids.to_vec()))
+            .filter(report::Column::DeletedAt.is_null())
+            .order_by_asc(report::Column::CreatedAt)
+            .all(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to get pipeline reports: {}", e)))
+    }
+}
@@ -0,0 +1,108 @@
+//! Database operations for the test quarantine registry.
+
+use chrono::Utc;
+use sea_orm::*;
+use uuid::Uuid;
+
+use crate::entity::quarantined_test::{ActiveModel, Column, Entity, Model};
+use crate::error::AppResult;
+use crate::models::{Framework, QuarantineEntry};
+
+/// Insert a new quarantine entry.
+pub async fn insert(db: &DatabaseConnection, entry: &QuarantineEntry) -> AppResult<()> {
+    let now = Utc::now();
+
+    let model = ActiveModel {
+        id: Set(entry.id),
+        repository: Set(entry.repository.clone()),
+        framework: Set(entry.framework.map(|f| f.as_str().to_string())),
+        fingerprint: Set(entry.fingerprint.clone()),
+        full_title: Set(entry.full_title.clone()),
+        title_pattern: Set(entry.title_pattern.clone()),
+        reason: Set(entry.reason.clone()),
+        owner: Set(entry.owner.clone()),
+        expires_at: Set(entry.expires_at),
+        created_by: Set(entry.created_by.clone()),
+        created_at: Set(entry.created_at),
+        updated_at: Set(now),
+        deleted_at: Set(None),
+    };
+
+    Entity::insert(model).exec(db).await?;
+
+    Ok(())
+}
+
+/// List quarantine entries (non-deleted).
+///
+/// Repository and framework filters keep unscoped entries, since those apply
+/// everywhere. Expired entries are skipped unless `include_expired` is set.
+pub async fn list(
+    db: &DatabaseConnection,
+    repository: Option<&str>,
+    framework: Option<Framework>,
+    include_expired: bool,
+) -> AppResult<Vec<QuarantineEntry>> {
+    let mut query = Entity::find().filter(Column::DeletedAt.is_null());
+
+    if let Some(repository) = repository {
+        query = query.filter(
+            Condition::any()
+                .add(Column::Repository.is_null())
+                .add(Column::Repository.eq(repository)),
+        );
+    }
+
+    if let Some(framework) = framework {
+        query = query.filter(
+            Condition::any()
+                .add(Column::Framework.is_null())
+                .add(Column::Framework.eq(framework.as_str())),
+        );
+    }
+
+    if !include_expired {
+        query = query.filter(
+            Condition::any()
+                .add(Column::ExpiresAt.is_null())
+                .add(Column::ExpiresAt.gt(Utc::now())),
+        );
+    }
+
+    let results = query.order_by_asc(Column::CreatedAt).all(db).await?;
+
+    Ok(results.into_iter().map(model_to_entry).collect())
+}
+
+/// Soft-delete a quarantine entry.
+pub async fn delete(db: &DatabaseConnection, id: Uuid) -> AppResult<bool> {
+    let model = Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
+
+    if let Some(m) = model {
+        let mut active: ActiveModel = m.into();
+        active.deleted_at = Set(Some(Utc::now()));
+        active.update(db).await?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+fn model_to_entry(m: Model) -> QuarantineEntry {
+    QuarantineEntry {
+        id: m.id,
+        repository: m.repository,
+        framework: m.framework.as_deref().and_then(Framework::parse),
+        fingerprint: m.fingerprint,
+        full_title: m.full_title,
+        title_pattern: m.title_pattern,
+        reason: m.reason,
+        owner: m.owner,
+        expires_at: m.expires_at,
+        created_by: m.created_by,
+        created_at: m.created_at,
+    }
+}
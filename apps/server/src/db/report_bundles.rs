@@ -0,0 +1,192 @@
+//! Database queries for report bundle export and import.
+
+use sea_orm::sea_query::Query;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseTransaction, EntityTrait, InsertMany, IntoActiveModel,
+    QueryFilter, QueryOrder, TransactionTrait,
+};
+use uuid::Uuid;
+
+use crate::entity::{
+    duration_regression, html_file, json_file, report_oidc_claim, screenshot, test_case, test_job,
+    test_report, test_suite, triage_record,
+};
+use crate::error::{AppError, AppResult};
+
+use super::DbPool;
+
+/// Rows per multi-row insert, well under Postgres' bind parameter limit.
+const INSERT_BATCH_SIZE: usize = 1000;
+
+/// Rows attached to a report itself rather than one of its jobs.
+#[derive(Debug, Default)]
+pub struct ReportRows {
+    pub oidc_claims: Option<report_oidc_claim::Model>,
+    pub duration_regressions: Vec<duration_regression::Model>,
+}
+
+/// Live results and file records of a job.
+#[derive(Debug, Default)]
+pub struct JobRows {
+    pub suites: Vec<test_suite::Model>,
+    pub cases: Vec<test_case::Model>,
+    /// Live triage records of the job's test cases.
+    pub triage_records: Vec<triage_record::Model>,
+    pub html_files: Vec<html_file::Model>,
+    pub json_files: Vec<json_file::Model>,
+    pub screenshots: Vec<screenshot::Model>,
+}
+
+/// Insert models in batches.
+async fn insert_batches<A, M>(txn: &DatabaseTransaction, models: &[M], what: &str) -> AppResult<()>
+where
+    A: ActiveModelTrait,
+    M: IntoActiveModel<A> + Clone,
+{
+    for batch in models.chunks(INSERT_BATCH_SIZE) {
+        InsertMany::<A>::many(batch.iter().cloned())
+            .exec_without_returning(txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to import {}: {}", what, e)))?;
+    }
+    Ok(())
+}
+
+impl DbPool {
+    /// Get the OIDC claims and duration regressions of a report.
+    pub async fn get_report_rows(&self, report_id: Uuid) -> AppResult<ReportRows> {
+        let db = self.connection();
+        let map_err = |e| AppError::Database(format!("Failed to get report rows: {}", e));
+
+        Ok(ReportRows {
+            oidc_claims: report_oidc_claim::Entity::find()
+                .filter(report_oidc_claim::Column::ReportId.eq(report_id))
+                .one(db)
+                .await
+                .map_err(map_err)?,
+            duration_regressions: duration_regression::Entity::find()
+                .filter(duration_regression::Column::ReportId.eq(report_id))
+                .order_by_asc(duration_regression::Column::TestCaseId)
+                .all(db)
+                .await
+                .map_err(map_err)?,
+        })
+    }
+
+    /// Get the live suites, test cases and file records of a job.
+    pub async fn get_job_rows(&self, job_id: Uuid) -> AppResult<JobRows> {
+        let db = self.connection();
+        let map_err = |e| AppError::Database(format!("Failed to get job rows: {}", e));
+
+        Ok(JobRows {
+            suites: test_suite::Entity::find()
+                .filter(test_suite::Column::TestJobId.eq(job_id))
+                .filter(test_suite::Column::DeletedAt.is_null())
+                .order_by_asc(test_suite::Column::Id)
+                .all(db)
+                .await
+                .map_err(map_err)?,
+            cases: test_case::Entity::find()
+                .filter(test_case::Column::TestJobId.eq(job_id))
+                .filter(test_case::Column::DeletedAt.is_null())
+                .order_by_asc(test_case::Column::Id)
+                .all(db)
+                .await
+                .map_err(map_err)?,
+            triage_records: triage_record::Entity::find()
+                .filter(
+                    triage_record::Column::TestCaseId.in_subquery(
+                        Query::select()
+                            .column(test_case::Column::Id)
+                            .from(test_case::Entity)
+                            .and_where(test_case::Column::TestJobId.eq(job_id))
+                            .and_where(test_case::Column::DeletedAt.is_null())
+                            .to_owned(),
+                    ),
+                )
+                .filter(triage_record::Column::DeletedAt.is_null())
+                .order_by_asc(triage_record::Column::Id)
+                .all(db)
+                .await
+                .map_err(map_err)?,
+            html_files: html_file::Entity::find()
+                .filter(html_file::Column::TestJobId.eq(job_id))
+                .filter(html_file::Column::DeletedAt.is_null())
+                .order_by_asc(html_file::Column::Id)
+                .all(db)
+                .await
+                .map_err(map_err)?,
+            json_files: json_file::Entity::find()
+                .filter(json_file::Column::TestJobId.eq(job_id))
+                .filter(json_file::Column::DeletedAt.is_null())
+                .order_by_asc(json_file::Column::Id)
+                .all(db)
+                .await
+                .map_err(map_err)?,
+            screenshots: screenshot::Entity::find()
+                .filter(screenshot::Column::TestJobId.eq(job_id))
+                .filter(screenshot::Column::DeletedAt.is_null())
+                .order_by_asc(screenshot::Column::Id)
+                .all(db)
+                .await
+                .map_err(map_err)?,
+        })
+    }
+
+    /// Insert an imported report with its jobs, results and file records,
+    /// all or nothing.
+    pub async fn insert_report_rows(
+        &self,
+        report: &test_report::Model,
+        report_rows: &ReportRows,
+        jobs: &[test_job::Model],
+        rows: &[JobRows],
+    ) -> AppResult<()> {
+        let txn = self
+            .connection()
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to start report import: {}", e)))?;
+
+        test_report::ActiveModel::from(report.clone())
+            .insert(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to import report: {}", e)))?;
+
+        if let Some(claims) = &report_rows.oidc_claims {
+            report_oidc_claim::ActiveModel::from(claims.clone())
+                .insert(&txn)
+                .await
+                .map_err(|e| AppError::Database(format!("Failed to import OIDC claims: {}", e)))?;
+        }
+
+        insert_batches::<test_job::ActiveModel, _>(&txn, jobs, "jobs").await?;
+        for rows in rows {
+            insert_batches::<test_suite::ActiveModel, _>(&txn, &rows.suites, "test suites").await?;
+            insert_batches::<test_case::ActiveModel, _>(&txn, &rows.cases, "test cases").await?;
+            insert_batches::<triage_record::ActiveModel, _>(
+                &txn,
+                &rows.triage_records,
+                "triage records",
+            )
+            .await?;
+            insert_batches::<html_file::ActiveModel, _>(&txn, &rows.html_files, "HTML files")
+                .await?;
+            insert_batches::<json_file::ActiveModel, _>(&txn, &rows.json_files, "JSON files")
+                .await?;
+            insert_batches::<screenshot::ActiveModel, _>(&txn, &rows.screenshots, "screenshots")
+                .await?;
+        }
+        // Regressions reference test cases of any job
+        insert_batches::<duration_regression::ActiveModel, _>(
+            &txn,
+            &report_rows.duration_regressions,
+            "duration regressions",
+        )
+        .await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to commit report import: {}", e)))
+    }
+}
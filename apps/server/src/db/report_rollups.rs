@@ -0,0 +1,178 @@
+//! Database queries for per-report rollups used by trend analytics.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ConnectionTrait, FromQueryResult, Statement};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::models::TrendsQuery;
+use crate::services::labels::parse_label_filter;
+
+use super::DbPool;
+
+/// Aggregated rollup totals for one time bucket.
+#[derive(Debug, FromQueryResult)]
+pub struct TrendRow {
+    pub bucket_start: DateTime<Utc>,
+    pub report_count: i64,
+    pub total: i64,
+    pub passed: i64,
+    pub failed: i64,
+    pub skipped: i64,
+    pub flaky: i64,
+    pub duration_ms: i64,
+    pub wall_clock_ms: i64,
+}
+
+impl DbPool {
+    /// Recompute the rollup row for a report from its jobs and suites.
+    ///
+    /// Idempotent; called after each job extraction so the rollup always
+    /// reflects every job extracted so far.
+    pub async fn refresh_report_rollup(&self, report_id: Uuid) -> AppResult<()> {
+        let sql = r#"
+            INSERT INTO report_rollups (
+                report_id, report_created_at, framework, repository, workflow, git_ref,
+                total_count, passed_count, failed_count, skipped_count, flaky_count,
+                duration_ms, wall_clock_ms
+            )
+            SELECT
+                r.id,
+                r.created_at,
+                r.framework,
+                r.github_metadata->>'repository',
+                r.github_metadata->>'workflow',
+                r.github_metadata->>'ref',
+                COALESCE(s.total, 0),
+                COALESCE(s.passed, 0),
+                COALESCE(s.failed, 0),
+                COALESCE(s.skipped, 0),
+                COALESCE(s.flaky, 0),
+                COALESCE(jb.duration_ms, 0),
+                jb.wall_clock_ms
+            FROM test_reports r
+            LEFT JOIN LATERAL (
+                SELECT
+                    SUM(ts.total_count)::INTEGER as total,
+                    SUM(ts.passed_count)::INTEGER as passed,
+                    SUM(ts.failed_count)::INTEGER as failed,
+                    SUM(ts.skipped_count)::INTEGER as skipped,
+                    SUM(ts.flaky_count)::INTEGER as flaky
+                FROM test_jobs j
+                INNER JOIN test_suites ts ON ts.test_job_id = j.id
+                WHERE j.test_report_id = r.id
+                    AND j.deleted_at IS NULL
+                    AND ts.deleted_at IS NULL
+            ) s ON TRUE
+            LEFT JOIN LATERAL (
+                SELECT
+                    SUM(j.duration_ms)::BIGINT as duration_ms,
+                    (EXTRACT(EPOCH FROM (
+                        MAX(j.start_time + (j.duration_ms || ' milliseconds')::interval) - MIN(j.start_time)
+                    )) * 1000)::BIGINT as wall_clock_ms
+                FROM test_jobs j
+                WHERE j.test_report_id = r.id AND j.deleted_at IS NULL
+            ) jb ON TRUE
+            WHERE r.id = $1
+            ON CONFLICT (report_id) DO UPDATE SET
+                framework = EXCLUDED.framework,
+                repository = EXCLUDED.repository,
+                workflow = EXCLUDED.workflow,
+                git_ref = EXCLUDED.git_ref,
+                total_count = EXCLUDED.total_count,
+                passed_count = EXCLUDED.passed_count,
+                failed_count = EXCLUDED.failed_count,
+                skipped_count = EXCLUDED.skipped_count,
+                flaky_count = EXCLUDED.flaky_count,
+                duration_ms = EXCLUDED.duration_ms,
+                wall_clock_ms = EXCLUDED.wall_clock_ms
+        "#;
+
+        self.connection()
+            .execute_raw(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Postgres,
+                sql,
+                [sea_orm::Value::from(report_id)],
+            ))
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to refresh report rollup: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Aggregate report rollups into time buckets.
+    pub async fn query_trends(&self, query: &TrendsQuery) -> AppResult<Vec<TrendRow>> {
+        let mut conditions = vec!["r.deleted_at IS NULL".to_string()];
+        let mut values: Vec<sea_orm::Value> = Vec::new();
+
+        if let Some(ref repository) = query.repository {
+            values.push(repository.clone().into());
+            conditions.push(format!("ru.repository = ${}", values.len()));
+        }
+
+        if let Some(ref workflow) = query.workflow {
+            values.push(workflow.clone().into());
+            conditions.push(format!("ru.workflow = ${}", values.len()));
+        }
+
+        if let Some(ref git_ref) = query.git_ref {
+            values.push(git_ref.clone().into());
+            conditions.push(format!("ru.git_ref = ${}", values.len()));
+        }
+
+        if let Some(framework) = query.framework {
+            values.push(framework.as_str().into());
+            conditions.push(format!("ru.framework = ${}", values.len()));
+        }
+
+        if let Some(ref label) = query.label {
+            let labels = parse_label_filter(label).map_err(AppError::InvalidInput)?;
+            if !labels.is_empty() {
+                values.push(serde_json::to_value(labels)?.into());
+                conditions.push(format!("r.labels @> ${}", values.len()));
+            }
+        }
+
+        if let Some(from) = query.from {
+            values.push(from.into());
+            conditions.push(format!("ru.report_created_at >= ${}", values.len()));
+        }
+
+        if let Some(to) = query.to {
+            values.push(to.into());
+            conditions.push(format!("ru.report_created_at <= ${}", values.len()));
+        }
+
+        // Bucket name comes from a closed enum, safe to inline
+        let sql = format!(
+            r#"
+            SELECT
+                date_trunc('{}', ru.report_created_at) as bucket_start,
+                COUNT(*) as report_count,
+                COALESCE(SUM(ru.total_count), 0)::BIGINT as total,
+                COALESCE(SUM(ru.passed_count), 0)::BIGINT as passed,
+                COALESCE(SUM(ru.failed_count), 0)::BIGINT as failed,
+                COALESCE(SUM(ru.skipped_count), 0)::BIGINT as skipped,
+                COALESCE(SUM(ru.flaky_count), 0)::BIGINT as flaky,
+                COALESCE(SUM(ru.duration_ms), 0)::BIGINT as duration_ms,
+                COALESCE(SUM(ru.wall_clock_ms), 0)::BIGINT as wall_clock_ms
+            FROM report_rollups ru
+            INNER JOIN test_reports r ON r.id = ru.report_id
+            WHERE {}
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+            query.bucket.as_str(),
+            conditions.join(" AND ")
+        );
+
+        TrendRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            &sql,
+            values,
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to query trends: {}", e)))
+    }
+}
@@ -0,0 +1,471 @@
+//! Database queries for data retention.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ConnectionTrait, DatabaseBackend, FromQueryResult, Statement};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::services::retention::RetentionCutoffs;
+
+use super::DbPool;
+use super::test_jobs::SOFT_DELETE_JOB_ROWS_SQL;
+
+/// A report and one of its jobs (`None` for reports without jobs).
+#[derive(Debug, FromQueryResult)]
+pub struct JobKey {
+    pub report_id: Uuid,
+    pub job_id: Option<Uuid>,
+}
+
+/// Number of expired reports of one kind.
+#[derive(Debug, FromQueryResult)]
+pub struct ExpiredReportCount {
+    pub class: String,
+    pub reports: i64,
+}
+
+/// Expired HTML and screenshot files.
+#[derive(Debug, FromQueryResult)]
+pub struct ExpiredArtifacts {
+    pub jobs: i64,
+    pub files: i64,
+    pub bytes: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ArtifactKey {
+    job_id: Uuid,
+    s3_key: Option<String>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    count: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct IdRow {
+    id: Uuid,
+}
+
+/// SQL for the kind of report `r` (see [`crate::models::ReportClass`]).
+///
+/// Pushes the default branch refs onto `values`.
+fn report_class_sql(cutoffs: &RetentionCutoffs, values: &mut Vec<sea_orm::Value>) -> String {
+    let mut refs = Vec::with_capacity(cutoffs.default_refs.len());
+    for git_ref in &cutoffs.default_refs {
+        values.push(git_ref.clone().into());
+        refs.push(format!("${}", values.len()));
+    }
+    let default_branch = if refs.is_empty() {
+        "FALSE".to_string()
+    } else {
+        format!("r.github_metadata->>'ref' IN ({})", refs.join(", "))
+    };
+
+    format!(
+        r#"CASE
+            WHEN r.github_metadata ? 'pr_number'
+                OR r.github_metadata->>'event_name' IN ('pull_request', 'pull_request_target')
+                THEN 'pull_request'
+            WHEN {} THEN 'default_branch'
+            ELSE 'branch'
+        END"#,
+        default_branch
+    )
+}
+
+/// SQL condition matching live reports `r` past their kind's retention.
+///
+/// Pushes its parameters onto `values`.
+fn expired_report_sql(cutoffs: &RetentionCutoffs, values: &mut Vec<sea_orm::Value>) -> String {
+    let class = report_class_sql(cutoffs, values);
+    values.push(cutoffs.default_branch.into());
+    let default_branch = values.len();
+    values.push(cutoffs.branch.into());
+    let branch = values.len();
+    values.push(cutoffs.pull_request.into());
+    let pull_request = values.len();
+
+    // A NULL cutoff keeps that kind forever
+    format!(
+        r#"r.deleted_at IS NULL
+            AND r.created_at < (CASE {}
+                WHEN 'default_branch' THEN ${}::timestamptz
+                WHEN 'pull_request' THEN ${}::timestamptz
+                ELSE ${}::timestamptz
+            END)"#,
+        class, default_branch, pull_request, branch
+    )
+}
+
+/// SQL condition matching live jobs `j` of live reports `r` with HTML or
+/// screenshot files older than `$1`.
+const EXPIRED_ARTIFACTS_SQL: &str = r#"r.deleted_at IS NULL
+    AND j.deleted_at IS NULL
+    AND j.created_at < $1
+    AND (
+        EXISTS (
+            SELECT 1 FROM html_files f
+            WHERE f.test_job_id = j.id AND f.deleted_at IS NULL
+        )
+        OR EXISTS (
+            SELECT 1 FROM screenshots s
+            WHERE s.test_job_id = j.id AND s.deleted_at IS NULL
+        )
+    )"#;
+
+impl DbPool {
+    /// Count live reports past retention, by kind.
+    pub async fn count_expired_reports(
+        &self,
+        cutoffs: &RetentionCutoffs,
+    ) -> AppResult<Vec<ExpiredReportCount>> {
+        let mut values = Vec::new();
+        let class = report_class_sql(cutoffs, &mut values);
+        let expired = expired_report_sql(cutoffs, &mut values);
+
+        let sql = format!(
+            "SELECT {} AS class, COUNT(*) AS reports FROM test_reports r WHERE {} GROUP BY 1",
+            class, expired
+        );
+
+        ExpiredReportCount::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            &sql,
+            values,
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to count expired reports: {}", e)))
+    }
+
+    /// Get the oldest live reports past retention.
+    pub async fn find_expired_reports(
+        &self,
+        cutoffs: &RetentionCutoffs,
+        limit: u64,
+    ) -> AppResult<Vec<Uuid>> {
+        let mut values = Vec::new();
+        let expired = expired_report_sql(cutoffs, &mut values);
+        values.push((limit as i64).into());
+
+        let sql = format!(
+            "SELECT r.id FROM test_reports r WHERE {} ORDER BY r.created_at LIMIT ${}",
+            expired,
+            values.len()
+        );
+
+        let rows = IdRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            &sql,
+            values,
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to find expired reports: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| r.id).collect())
+    }
+
+    /// Soft-delete up to `limit` reports past retention, with their jobs,
+    /// results and files.
+    ///
+    /// Returns the deleted reports' jobs, ordered by report.
+    pub async fn soft_delete_expired_reports(
+        &self,
+        cutoffs: &RetentionCutoffs,
+        limit: u64,
+    ) -> AppResult<Vec<JobKey>> {
+        let mut values = Vec::new();
+        let expired = expired_report_sql(cutoffs, &mut values);
+        values.push((limit as i64).into());
+
+        // SKIP LOCKED lets concurrent runs claim different reports
+        let sql = format!(
+            r#"
+            WITH expired AS (
+                UPDATE test_reports
+                SET deleted_at = NOW(), updated_at = NOW()
+                WHERE id IN (
+                    SELECT r.id FROM test_reports r
+                    WHERE {}
+                    ORDER BY r.created_at
+                    LIMIT ${}
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING id
+            ),
+            deleted_jobs AS (
+                UPDATE test_jobs j
+                SET deleted_at = NOW(), updated_at = NOW()
+                FROM expired e
+                WHERE j.test_report_id = e.id AND j.deleted_at IS NULL
+                RETURNING j.test_report_id, j.id
+            ),
+            {}
+            SELECT e.id AS report_id, j.id AS job_id
+            FROM expired e
+            LEFT JOIN deleted_jobs j ON j.test_report_id = e.id
+            ORDER BY e.id
+            "#,
+            expired,
+            values.len(),
+            SOFT_DELETE_JOB_ROWS_SQL
+        );
+
+        JobKey::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            &sql,
+            values,
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to soft-delete expired reports: {}", e)))
+    }
+
+    /// Count HTML and screenshot files past artifact retention.
+    pub async fn count_expired_artifacts(
+        &self,
+        cutoffs: &RetentionCutoffs,
+    ) -> AppResult<ExpiredArtifacts> {
+        let Some(cutoff) = cutoffs.artifacts else {
+            return Ok(ExpiredArtifacts {
+                jobs: 0,
+                files: 0,
+                bytes: 0,
+            });
+        };
+
+        let sql = format!(
+            r#"
+            WITH expired AS (
+                SELECT j.id
+                FROM test_jobs j
+                INNER JOIN test_reports r ON r.id = j.test_report_id
+                WHERE {}
+            ),
+            files AS (
+                SELECT f.size_bytes FROM html_files f
+                INNER JOIN expired e ON e.id = f.test_job_id
+                WHERE f.deleted_at IS NULL
+                UNION ALL
+                SELECT s.size_bytes FROM screenshots s
+                INNER JOIN expired e ON e.id = s.test_job_id
+                WHERE s.deleted_at IS NULL
+            )
+            SELECT
+                (SELECT COUNT(*) FROM expired) AS jobs,
+                COUNT(*) AS files,
+                COALESCE(SUM(size_bytes), 0)::BIGINT AS bytes
+            FROM files
+            "#,
+            EXPIRED_ARTIFACTS_SQL
+        );
+
+        let row = ExpiredArtifacts::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            &sql,
+            [cutoff.into()],
+        ))
+        .one(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to count expired artifacts: {}", e)))?;
+
+        Ok(row.unwrap_or(ExpiredArtifacts {
+            jobs: 0,
+            files: 0,
+            bytes: 0,
+        }))
+    }
+
+    /// Mark the HTML and screenshot files of up to `limit` jobs older than
+    /// `cutoff` deleted and clear the jobs' HTML paths.
+    ///
+    /// Returns the number of jobs and the storage keys to delete.
+    pub async fn clear_expired_artifacts(
+        &self,
+        cutoff: DateTime<Utc>,
+        limit: u64,
+    ) -> AppResult<(u64, Vec<String>)> {
+        let sql = format!(
+            r#"
+            WITH expired AS (
+                SELECT j.id
+                FROM test_jobs j
+                INNER JOIN test_reports r ON r.id = j.test_report_id
+                WHERE {}
+                LIMIT $2
+                FOR UPDATE OF j SKIP LOCKED
+            ),
+            html AS (
+                UPDATE html_files f
+                SET deleted_at = NOW(), updated_at = NOW()
+                FROM expired e
+                WHERE f.test_job_id = e.id AND f.deleted_at IS NULL
+                RETURNING f.test_job_id, f.s3_key
+            ),
+            shots AS (
+                UPDATE screenshots s
+                SET deleted_at = NOW(), updated_at = NOW()
+                FROM expired e
+                WHERE s.test_job_id = e.id AND s.deleted_at IS NULL
+                RETURNING s.test_job_id, s.s3_key
+            ),
+            jobs AS (
+                UPDATE test_jobs j
+                SET html_path = NULL, updated_at = NOW()
+                FROM expired e
+                WHERE j.id = e.id
+                RETURNING j.id
+            )
+            SELECT e.id AS job_id, k.s3_key
+            FROM expired e
+            LEFT JOIN (
+                SELECT test_job_id, s3_key FROM html
+                UNION ALL
+                SELECT test_job_id, s3_key FROM shots
+            ) k ON k.test_job_id = e.id
+            "#,
+            EXPIRED_ARTIFACTS_SQL
+        );
+
+        let rows = ArtifactKey::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            &sql,
+            [cutoff.into(), (limit as i64).into()],
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to clear expired artifacts: {}", e)))?;
+
+        let mut jobs: Vec<Uuid> = rows.iter().map(|r| r.job_id).collect();
+        jobs.sort_unstable();
+        jobs.dedup();
+        let keys = rows.into_iter().filter_map(|r| r.s3_key).collect();
+
+        Ok((jobs.len() as u64, keys))
+    }
+
+    /// Count reports soft-deleted before the purge cutoff.
+    pub async fn count_purgeable_reports(&self, cutoffs: &RetentionCutoffs) -> AppResult<i64> {
+        let row = CountRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "SELECT COUNT(*) AS count FROM test_reports WHERE deleted_at < $1",
+            [cutoffs.purge.into()],
+        ))
+        .one(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to count purgeable reports: {}", e)))?;
+
+        Ok(row.map_or(0, |r| r.count))
+    }
+
+    /// Get up to `limit` reports soft-deleted before the purge cutoff, with
+    /// all their jobs, ordered by report.
+    pub async fn find_purgeable_reports(
+        &self,
+        cutoffs: &RetentionCutoffs,
+        limit: u64,
+    ) -> AppResult<Vec<JobKey>> {
+        let sql = r#"
+            SELECT r.id AS report_id, j.id AS job_id
+            FROM (
+                SELECT id FROM test_reports
+                WHERE deleted_at < $1
+                ORDER BY deleted_at
+                LIMIT $2
+            ) r
+            LEFT JOIN test_jobs j ON j.test_report_id = r.id
+            ORDER BY r.id
+        "#;
+
+        JobKey::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            sql,
+            [cutoffs.purge.into(), (limit as i64).into()],
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to find purgeable reports: {}", e)))
+    }
+
+    /// Permanently delete soft-deleted reports (cascading to all their rows).
+    ///
+    /// Only reports still soft-deleted before `cutoff` are removed. Pipeline
+    /// runs left without reports are removed too.
+    pub async fn purge_reports(
+        &self,
+        report_ids: &[Uuid],
+        cutoff: DateTime<Utc>,
+    ) -> AppResult<u64> {
+        if report_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut values: Vec<sea_orm::Value> = vec![cutoff.into()];
+        let mut placeholders = Vec::with_capacity(report_ids.len());
+        for id in report_ids {
+            values.push((*id).into());
+            placeholders.push(format!("${}", values.len()));
+        }
+
+        let sql = format!(
+            "DELETE FROM test_reports WHERE deleted_at < $1 AND id IN ({}) RETURNING pipeline_id",
+            placeholders.join(", ")
+        );
+
+        let rows = self
+            .connection()
+            .query_all_raw(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                &sql,
+                values,
+            ))
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to purge reports: {}", e)))?;
+
+        let mut pipeline_ids = Vec::new();
+        for row in &rows {
+            if let Some(id) = row
+                .try_get::<Option<Uuid>>("", "pipeline_id")
+                .map_err(|e| AppError::Database(format!("Failed to purge reports: {}", e)))?
+                && !pipeline_ids.contains(&id)
+            {
+                pipeline_ids.push(id);
+            }
+        }
+        self.delete_empty_pipeline_runs(&pipeline_ids).await?;
+
+        Ok(rows.len() as u64)
+    }
+
+    /// Delete pipeline runs no report belongs to anymore.
+    async fn delete_empty_pipeline_runs(&self, pipeline_ids: &[Uuid]) -> AppResult<()> {
+        if pipeline_ids.is_empty() {
+            return Ok(());
+        }
+
+        let values: Vec<sea_orm::Value> = pipeline_ids.iter().map(|id| (*id).into()).collect();
+        let placeholders: Vec<_> = (1..=values.len()).map(|i| format!("${}", i)).collect();
+        let sql = format!(
+            r#"
+            DELETE FROM pipeline_runs p
+            WHERE p.id IN ({})
+                AND NOT EXISTS (SELECT 1 FROM test_reports r WHERE r.pipeline_id = p.id)
+            "#,
+            placeholders.join(", ")
+        );
+
+        self.connection()
+            .execute_raw(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                &sql,
+                values,
+            ))
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete pipeline runs: {}", e)))?;
+
+        Ok(())
+    }
+}
@@ -88,6 +88,7 @@ impl DbPool {
     ) -> AppResult<Vec<screenshot::Model>> {
         let result = Screenshot::find()
             .filter(screenshot::Column::TestJobId.eq(job_id))
+            .filter(screenshot::Column::DeletedAt.is_null())
             .order_by_asc(screenshot::Column::TestName)
             .order_by_asc(screenshot::Column::Sequence)
             .all(self.connection())
@@ -102,6 +103,7 @@ impl DbPool {
         let result = Screenshot::find()
             .filter(screenshot::Column::TestJobId.eq(job_id))
             .filter(screenshot::Column::Status.eq(ScreenshotStatus::Pending.as_str()))
+            .filter(screenshot::Column::DeletedAt.is_null())
             .all(self.connection())
             .await
             .map_err(|e| AppError::Database(format!("Failed to get pending screenshots: {}", e)))?;
@@ -128,6 +130,7 @@ impl DbPool {
             )
             .filter(screenshot::Column::TestJobId.eq(job_id))
             .filter(screenshot::Column::Filename.is_in(filenames))
+            .filter(screenshot::Column::DeletedAt.is_null())
             .exec(self.connection())
             .await
             .map_err(|e| {
@@ -142,6 +145,7 @@ impl DbPool {
         let count = Screenshot::find()
             .filter(screenshot::Column::TestJobId.eq(job_id))
             .filter(screenshot::Column::Status.eq(ScreenshotStatus::Pending.as_str()))
+            .filter(screenshot::Column::DeletedAt.is_null())
             .count(self.connection())
             .await
             .map_err(|e| {
@@ -155,6 +159,7 @@ impl DbPool {
     pub async fn count_screenshots(&self, job_id: Uuid) -> AppResult<u64> {
         let count = Screenshot::find()
             .filter(screenshot::Column::TestJobId.eq(job_id))
+            .filter(screenshot::Column::DeletedAt.is_null())
             .count(self.connection())
             .await
             .map_err(|e| AppError::Database(format!("Failed to count screenshots: {}", e)))?;
@@ -186,6 +191,7 @@ impl DbPool {
         let result = Screenshot::find()
             .filter(screenshot::Column::TestJobId.eq(job_id))
             .filter(screenshot::Column::Filename.eq(filename))
+            .filter(screenshot::Column::DeletedAt.is_null())
             .one(self.connection())
             .await
             .map_err(|e| AppError::Database(format!("Failed to get screenshot: {}", e)))?;
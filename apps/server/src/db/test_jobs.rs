@@ -1,18 +1,123 @@
 //! Database queries for jobs.
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, IdenStatic, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
 };
 use uuid::Uuid;
 
 use crate::entity::test_job::{self as job, ActiveModel, Entity as Job};
 use crate::error::{AppError, AppResult};
-use crate::models::{EnvironmentMetadata, JobGitHubMetadata, JobStatus, QueryJobsParams};
+use crate::models::{
+    EnvironmentMetadata, JobGitHubMetadata, JobStatus, QueryJobsParams, ReportStatus, UploadStatus,
+};
+use crate::services::lifecycle::{Lifecycle, sql_list};
 
 use super::DbPool;
 
+/// Advisory lock key held while sweeping upload timeouts, so only one server
+/// instance sweeps at a time.
+const UPLOAD_SWEEP_LOCK_KEY: i64 = 0x7473_696f_0001;
+
+/// Latest upload activity of job `j`: the job row itself or any of its files.
+const JOB_ACTIVITY_SQL: &str = r#"GREATEST(
+    j.updated_at,
+    (SELECT MAX(f.uploaded_at) FROM html_files f WHERE f.test_job_id = j.id),
+    (SELECT MAX(f.uploaded_at) FROM screenshots f WHERE f.test_job_id = j.id),
+    (SELECT MAX(f.uploaded_at) FROM json_files f WHERE f.test_job_id = j.id)
+)"#;
+
+/// Data-modifying CTEs soft-deleting the suites, test cases and files of the
+/// jobs returned by a preceding `deleted_jobs (id)` CTE.
+pub(super) const SOFT_DELETE_JOB_ROWS_SQL: &str = r#"deleted_suites AS (
+        UPDATE test_suites s SET deleted_at = NOW(), updated_at = NOW()
+        FROM deleted_jobs d
+        WHERE s.test_job_id = d.id AND s.deleted_at IS NULL
+    ),
+    deleted_cases AS (
+        UPDATE test_cases c SET deleted_at = NOW(), updated_at = NOW()
+        FROM deleted_jobs d
+        WHERE c.test_job_id = d.id AND c.deleted_at IS NULL
+    ),
+    deleted_html AS (
+        UPDATE html_files f SET deleted_at = NOW(), updated_at = NOW()
+        FROM deleted_jobs d
+        WHERE f.test_job_id = d.id AND f.deleted_at IS NULL
+    ),
+    deleted_screenshots AS (
+        UPDATE screenshots f SET deleted_at = NOW(), updated_at = NOW()
+        FROM deleted_jobs d
+        WHERE f.test_job_id = d.id AND f.deleted_at IS NULL
+    ),
+    deleted_json AS (
+        UPDATE json_files f SET deleted_at = NOW(), updated_at = NOW()
+        FROM deleted_jobs d
+        WHERE f.test_job_id = d.id AND f.deleted_at IS NULL
+    )"#;
+
+/// A job whose started uploads timed out.
+#[derive(Debug, sea_orm::FromQueryResult)]
+pub struct TimedOutJob {
+    pub id: Uuid,
+    pub test_report_id: Uuid,
+    pub status: String,
+}
+
+/// A stalled report finalized by the upload timeout sweep.
+#[derive(Debug, sea_orm::FromQueryResult)]
+pub struct FinalizedReport {
+    pub id: Uuid,
+    pub status: String,
+    pub pipeline_id: Option<Uuid>,
+}
+
+/// Completed and failed jobs of a report.
+#[derive(Debug, Default, sea_orm::FromQueryResult)]
+pub struct FinishedJobs {
+    pub completed: i64,
+    pub failed: i64,
+}
+
+/// Result of one upload timeout sweep.
+#[derive(Debug, Default)]
+pub struct UploadSweep {
+    pub jobs: Vec<TimedOutJob>,
+    pub reports: Vec<FinalizedReport>,
+}
+
+/// Build a new job row.
+fn new_job(
+    id: Uuid,
+    report_id: Uuid,
+    github_metadata: Option<JobGitHubMetadata>,
+    environment: Option<EnvironmentMetadata>,
+    status: JobStatus,
+) -> ActiveModel {
+    let now = Utc::now();
+
+    let github_json = github_metadata.and_then(|m: JobGitHubMetadata| m.to_json());
+    let env_json = environment.and_then(|e: EnvironmentMetadata| e.to_json());
+
+    ActiveModel {
+        id: Set(id),
+        test_report_id: Set(report_id),
+        github_metadata: Set(github_json),
+        status: Set(status.as_str().to_string()),
+        html_upload_status: Set(None),
+        screenshots_upload_status: Set(None),
+        json_upload_status: Set(None),
+        html_path: Set(None),
+        environment: Set(env_json),
+        error_message: Set(None),
+        duration_ms: Set(None),
+        start_time: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        deleted_at: Set(None),
+    }
+}
+
 impl DbPool {
     /// Insert a new job.
     pub async fn insert_job(
@@ -23,30 +128,7 @@ impl DbPool {
         environment: Option<EnvironmentMetadata>,
         status: JobStatus,
     ) -> AppResult<job::Model> {
-        let now = Utc::now();
-
-        let github_json = github_metadata.and_then(|m: JobGitHubMetadata| m.to_json());
-        let env_json = environment.and_then(|e: EnvironmentMetadata| e.to_json());
-
-        let model = ActiveModel {
-            id: Set(id),
-            test_report_id: Set(report_id),
-            github_metadata: Set(github_json),
-            status: Set(status.as_str().to_string()),
-            html_upload_status: Set(None),
-            screenshots_upload_status: Set(None),
-            json_upload_status: Set(None),
-            html_path: Set(None),
-            environment: Set(env_json),
-            error_message: Set(None),
-            duration_ms: Set(None),
-            start_time: Set(None),
-            created_at: Set(now),
-            updated_at: Set(now),
-            deleted_at: Set(None),
-        };
-
-        let result = model
+        let result = new_job(id, report_id, github_metadata, environment, status)
             .insert(self.connection())
             .await
             .map_err(|e| AppError::Database(format!("Failed to insert job: {}", e)))?;
@@ -54,9 +136,52 @@ impl DbPool {
         Ok(result)
     }
 
-    /// Get a job by ID.
+    /// Insert a new pending job into an open report, counting it toward the
+    /// report's `expected_jobs` in the same transaction.
+    ///
+    /// Returns `None` without inserting if the report is closed, no longer
+    /// open or already has the maximum number of jobs.
+    pub async fn insert_open_report_job(
+        &self,
+        id: Uuid,
+        report_id: Uuid,
+        github_metadata: Option<JobGitHubMetadata>,
+        environment: Option<EnvironmentMetadata>,
+    ) -> AppResult<Option<job::Model>> {
+        use sea_orm::TransactionTrait;
+
+        let txn = self
+            .connection()
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to start job insert: {}", e)))?;
+
+        if !super::test_reports::add_open_report_job(&txn, report_id).await? {
+            return Ok(None);
+        }
+
+        let result = new_job(
+            id,
+            report_id,
+            github_metadata,
+            environment,
+            JobStatus::Pending,
+        )
+        .insert(&txn)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to insert job: {}", e)))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to commit job insert: {}", e)))?;
+
+        Ok(Some(result))
+    }
+
+    /// Get a live (not soft-deleted) job by ID.
     pub async fn get_job_by_id(&self, id: Uuid) -> AppResult<Option<job::Model>> {
         let result = Job::find_by_id(id)
+            .filter(job::Column::DeletedAt.is_null())
             .one(self.connection())
             .await
             .map_err(|e| AppError::Database(format!("Failed to get job: {}", e)))?;
@@ -68,6 +193,7 @@ impl DbPool {
     pub async fn get_jobs_by_report_id(&self, report_id: Uuid) -> AppResult<Vec<job::Model>> {
         let result = Job::find()
             .filter(job::Column::TestReportId.eq(report_id))
+            .filter(job::Column::DeletedAt.is_null())
             .order_by_asc(job::Column::Id) // UUIDv7 is time-ordered
             .all(self.connection())
             .await
@@ -76,29 +202,42 @@ impl DbPool {
         Ok(result)
     }
 
-    /// Update job status.
+    /// Move a live job to `status`.
+    ///
+    /// Fails with `Conflict` if the job's lifecycle doesn't allow the change,
+    /// e.g. completing a job that already failed.
     pub async fn update_job_status(
         &self,
         id: Uuid,
         status: JobStatus,
         error_message: Option<String>,
     ) -> AppResult<job::Model> {
-        let job = self
-            .get_job_by_id(id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Job {} not found", id)))?;
-
-        let mut active: ActiveModel = job.into();
-        active.status = Set(status.as_str().to_string());
-        active.error_message = Set(error_message);
-        active.updated_at = Set(Utc::now());
-
-        let result = active
-            .update(self.connection())
+        let jobs = Job::update_many()
+            .col_expr(
+                job::Column::Status,
+                sea_orm::sea_query::Expr::value(status.as_str()),
+            )
+            .col_expr(
+                job::Column::ErrorMessage,
+                sea_orm::sea_query::Expr::value(error_message),
+            )
+            .col_expr(
+                job::Column::UpdatedAt,
+                sea_orm::sea_query::Expr::value(Utc::now()),
+            )
+            .filter(job::Column::Id.eq(id))
+            .filter(job::Column::DeletedAt.is_null())
+            .filter(job::Column::Status.is_in(JobStatus::sources(status)))
+            .exec_with_returning(self.connection())
             .await
             .map_err(|e| AppError::Database(format!("Failed to update job status: {}", e)))?;
 
-        Ok(result)
+        match jobs.into_iter().next() {
+            Some(job) => Ok(job),
+            None => Err(self
+                .job_transition_error(id, "status", |j| Some(j.status.as_str()), status.as_str())
+                .await),
+        }
     }
 
     /// Update job HTML path.
@@ -120,70 +259,115 @@ impl DbPool {
         Ok(result)
     }
 
-    /// Update HTML upload status for a job.
+    /// Move a job's HTML upload to `status`.
     pub async fn set_html_upload_status(
         &self,
         id: Uuid,
-        status: Option<&str>,
+        status: UploadStatus,
     ) -> AppResult<job::Model> {
-        let job = self
-            .get_job_by_id(id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Job {} not found", id)))?;
-
-        let mut active: ActiveModel = job.into();
-        active.html_upload_status = Set(status.map(|s| s.to_string()));
-        active.updated_at = Set(Utc::now());
-
-        let result = active.update(self.connection()).await.map_err(|e| {
-            AppError::Database(format!("Failed to update html_upload_status: {}", e))
-        })?;
-
-        Ok(result)
+        self.set_upload_status(
+            id,
+            job::Column::HtmlUploadStatus,
+            |j| j.html_upload_status.as_deref(),
+            status,
+        )
+        .await
     }
 
-    /// Update screenshots upload status for a job.
+    /// Move a job's screenshots upload to `status`.
     pub async fn set_screenshots_upload_status(
         &self,
         id: Uuid,
-        status: Option<&str>,
+        status: UploadStatus,
     ) -> AppResult<job::Model> {
-        let job = self
-            .get_job_by_id(id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Job {} not found", id)))?;
-
-        let mut active: ActiveModel = job.into();
-        active.screenshots_upload_status = Set(status.map(|s| s.to_string()));
-        active.updated_at = Set(Utc::now());
-
-        let result = active.update(self.connection()).await.map_err(|e| {
-            AppError::Database(format!("Failed to update screenshots_upload_status: {}", e))
-        })?;
-
-        Ok(result)
+        self.set_upload_status(
+            id,
+            job::Column::ScreenshotsUploadStatus,
+            |j| j.screenshots_upload_status.as_deref(),
+            status,
+        )
+        .await
     }
 
-    /// Update JSON upload status for a job.
+    /// Move a job's JSON upload to `status`.
     pub async fn set_json_upload_status(
         &self,
         id: Uuid,
-        status: Option<&str>,
+        status: UploadStatus,
     ) -> AppResult<job::Model> {
-        let job = self
-            .get_job_by_id(id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Job {} not found", id)))?;
+        self.set_upload_status(
+            id,
+            job::Column::JsonUploadStatus,
+            |j| j.json_upload_status.as_deref(),
+            status,
+        )
+        .await
+    }
 
-        let mut active: ActiveModel = job.into();
-        active.json_upload_status = Set(status.map(|s| s.to_string()));
-        active.updated_at = Set(Utc::now());
+    /// Move an upload of a job that isn't finished yet to `status`.
+    ///
+    /// Fails with `Conflict` if the job is complete or failed, or if the
+    /// upload's lifecycle doesn't allow the change.
+    async fn set_upload_status(
+        &self,
+        id: Uuid,
+        column: job::Column,
+        current: fn(&job::Model) -> Option<&str>,
+        status: UploadStatus,
+    ) -> AppResult<job::Model> {
+        let mut allowed = Condition::any().add(column.is_in(UploadStatus::sources(status)));
+        if status.is_initial() {
+            allowed = allowed.add(column.is_null());
+        }
 
-        let result = active.update(self.connection()).await.map_err(|e| {
-            AppError::Database(format!("Failed to update json_upload_status: {}", e))
-        })?;
+        let jobs = Job::update_many()
+            .col_expr(column, sea_orm::sea_query::Expr::value(status.as_str()))
+            .col_expr(
+                job::Column::UpdatedAt,
+                sea_orm::sea_query::Expr::value(Utc::now()),
+            )
+            .filter(job::Column::Id.eq(id))
+            .filter(job::Column::DeletedAt.is_null())
+            .filter(job::Column::Status.is_in(JobStatus::active()))
+            .filter(allowed)
+            .exec_with_returning(self.connection())
+            .await
+            .map_err(|e| {
+                AppError::Database(format!("Failed to update {}: {}", column.as_str(), e))
+            })?;
+
+        match jobs.into_iter().next() {
+            Some(job) => Ok(job),
+            None => Err(self
+                .job_transition_error(id, column.as_str(), current, status.as_str())
+                .await),
+        }
+    }
 
-        Ok(result)
+    /// Explain why a conditional update of a job's `field` matched no row.
+    async fn job_transition_error(
+        &self,
+        id: Uuid,
+        field: &str,
+        current: fn(&job::Model) -> Option<&str>,
+        next: &str,
+    ) -> AppError {
+        let job = match self.get_job_by_id(id).await {
+            Ok(Some(job)) => job,
+            Ok(None) => return AppError::NotFound(format!("Job {}", id)),
+            Err(e) => return e,
+        };
+
+        if JobStatus::parse(&job.status).is_some_and(|s| s.is_final()) {
+            return AppError::Conflict(format!("Job {} is already {}", id, job.status));
+        }
+        AppError::Conflict(format!(
+            "Job {} {} can't change from {} to {}",
+            id,
+            field,
+            current(&job).unwrap_or("none"),
+            next
+        ))
     }
 
     /// Update job duration and start time from JUnit stats.
@@ -221,6 +405,7 @@ impl DbPool {
 
         let result = Job::find()
             .filter(job::Column::TestReportId.eq(report_id))
+            .filter(job::Column::DeletedAt.is_null())
             .filter(Expr::cust_with_values(
                 "github_metadata->>'job_id' = $1",
                 [github_job_id.to_string()],
@@ -232,16 +417,59 @@ impl DbPool {
         Ok(result)
     }
 
-    /// Count completed jobs for a report.
-    pub async fn count_completed_jobs(&self, report_id: Uuid) -> AppResult<u64> {
-        let count = Job::find()
+    /// Count a report's completed and failed jobs.
+    pub async fn count_finished_jobs(&self, report_id: Uuid) -> AppResult<FinishedJobs> {
+        use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+        let sql = r#"
+            SELECT
+                COUNT(*) FILTER (WHERE status = 'complete') AS completed,
+                COUNT(*) FILTER (WHERE status = 'failed') AS failed
+            FROM test_jobs
+            WHERE test_report_id = $1 AND deleted_at IS NULL
+        "#;
+
+        let counts = FinishedJobs::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            sql,
+            [report_id.into()],
+        ))
+        .one(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to count finished jobs: {}", e)))?;
+
+        Ok(counts.unwrap_or_default())
+    }
+
+    /// Fail a report's pending jobs.
+    ///
+    /// Returns the IDs of the failed jobs.
+    pub async fn fail_pending_jobs(
+        &self,
+        report_id: Uuid,
+        error_message: &str,
+    ) -> AppResult<Vec<Uuid>> {
+        let jobs = Job::update_many()
+            .col_expr(
+                job::Column::Status,
+                sea_orm::sea_query::Expr::value(JobStatus::Failed.as_str()),
+            )
+            .col_expr(
+                job::Column::ErrorMessage,
+                sea_orm::sea_query::Expr::value(error_message),
+            )
+            .col_expr(
+                job::Column::UpdatedAt,
+                sea_orm::sea_query::Expr::value(Utc::now()),
+            )
             .filter(job::Column::TestReportId.eq(report_id))
-            .filter(job::Column::Status.eq(JobStatus::Complete.as_str()))
-            .count(self.connection())
+            .filter(job::Column::Status.eq(JobStatus::Pending.as_str()))
+            .filter(job::Column::DeletedAt.is_null())
+            .exec_with_returning(self.connection())
             .await
-            .map_err(|e| AppError::Database(format!("Failed to count completed jobs: {}", e)))?;
+            .map_err(|e| AppError::Database(format!("Failed to fail pending jobs: {}", e)))?;
 
-        Ok(count)
+        Ok(jobs.into_iter().map(|j| j.id).collect())
     }
 
     /// Batch count completed jobs for multiple reports.
@@ -267,7 +495,7 @@ impl DbPool {
         let in_clause = placeholders.join(", ");
 
         let sql = format!(
-            "SELECT test_report_id as report_id, COUNT(*) as count FROM test_jobs WHERE test_report_id IN ({}) AND status = 'complete' GROUP BY test_report_id",
+            "SELECT test_report_id as report_id, COUNT(*) as count FROM test_jobs WHERE test_report_id IN ({}) AND status = 'complete' AND deleted_at IS NULL GROUP BY test_report_id",
             in_clause
         );
 
@@ -295,7 +523,7 @@ impl DbPool {
     pub async fn query_jobs(&self, query: &QueryJobsParams) -> AppResult<(Vec<job::Model>, u64)> {
         use sea_orm::sea_query::Expr;
 
-        let mut select = Job::find();
+        let mut select = Job::find().filter(job::Column::DeletedAt.is_null());
 
         // Apply filters
         if let Some(report_id) = query.report_id {
@@ -314,6 +542,27 @@ impl DbPool {
             ));
         }
 
+        // Environment filters as one containment check (served by the GIN index)
+        let mut environment = serde_json::Map::new();
+        for (key, value) in [
+            ("os", &query.os),
+            ("browser", &query.browser),
+            ("device", &query.device),
+        ] {
+            if let Some(value) = value {
+                environment.insert(key.to_string(), value.clone().into());
+            }
+        }
+        if let Some(ref tag) = query.tag {
+            environment.insert("tags".to_string(), serde_json::json!([tag]));
+        }
+        if !environment.is_empty() {
+            select = select.filter(Expr::cust_with_values(
+                "environment @> $1",
+                [serde_json::Value::Object(environment)],
+            ));
+        }
+
         if let Some(ref from_date) = query.from_date {
             select = select.filter(job::Column::CreatedAt.gte(*from_date));
         }
@@ -343,4 +592,240 @@ impl DbPool {
 
         Ok((jobs, total))
     }
+
+    /// Soft-delete a live job of a report, with its results and files.
+    ///
+    /// Returns `false` if the job doesn't exist or is already deleted.
+    pub async fn soft_delete_job(&self, report_id: Uuid, job_id: Uuid) -> AppResult<bool> {
+        use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+        let sql = format!(
+            r#"
+            WITH deleted_jobs AS (
+                UPDATE test_jobs
+                SET deleted_at = NOW(), updated_at = NOW()
+                WHERE id = $1 AND test_report_id = $2 AND deleted_at IS NULL
+                RETURNING id
+            ),
+            {}
+            SELECT id FROM deleted_jobs
+            "#,
+            SOFT_DELETE_JOB_ROWS_SQL
+        );
+
+        let row = self
+            .connection()
+            .query_one_raw(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                &sql,
+                [job_id.into(), report_id.into()],
+            ))
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete job: {}", e)))?;
+
+        Ok(row.is_some())
+    }
+
+    /// Time out uploads with no activity since `cutoff` and finalize stalled
+    /// reports.
+    ///
+    /// Uploads still `started` become `timedout`; a pending job whose JSON
+    /// upload timed out fails, since it can never be extracted. Reports still
+    /// initializing or uploading with no active job (processing, or pending
+    /// with activity since `cutoff`) are finalized when one of their jobs has
+    /// a timed-out upload, or when all of their jobs are complete or failed:
+    /// complete if any job completed, failed otherwise, recording expected
+    /// jobs that never finished. Open reports with jobs are finalized the same
+    /// way once they and their jobs have been idle since `idle_cutoff`
+    /// instead; open reports without jobs wait for their first job.
+    ///
+    /// A `None` cutoff disables that rule. Returns `None` without sweeping if
+    /// another instance holds the sweep lock.
+    pub async fn sweep_upload_timeouts(
+        &self,
+        cutoff: Option<DateTime<Utc>>,
+        idle_cutoff: Option<DateTime<Utc>>,
+    ) -> AppResult<Option<UploadSweep>> {
+        use sea_orm::{
+            ConnectionTrait, DatabaseBackend, FromQueryResult, Statement, TransactionTrait,
+        };
+
+        let txn = self
+            .connection()
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to start upload sweep: {}", e)))?;
+
+        // Transaction-scoped, so the lock is released on commit or rollback
+        let locked = txn
+            .query_one_raw(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "SELECT pg_try_advisory_xact_lock($1) AS locked",
+                [UPLOAD_SWEEP_LOCK_KEY.into()],
+            ))
+            .await
+            .and_then(|row| match row {
+                Some(row) => row.try_get::<bool>("", "locked"),
+                None => Ok(false),
+            })
+            .map_err(|e| AppError::Database(format!("Failed to acquire sweep lock: {}", e)))?;
+
+        if !locked {
+            return Ok(None);
+        }
+
+        // Statuses come from the lifecycles, so the sweep only makes
+        // transitions they allow
+        let closable: Vec<&str> = ReportStatus::STATES
+            .iter()
+            .filter(|s| {
+                s.can_transition_to(ReportStatus::Complete)
+                    && s.can_transition_to(ReportStatus::Failed)
+            })
+            .map(|s| s.name())
+            .collect();
+        let closable = sql_list(&closable);
+        let unfinished = sql_list(&JobStatus::active());
+        let timeout_sources = sql_list(&UploadStatus::sources(UploadStatus::Timedout));
+        let timedout = UploadStatus::Timedout.name();
+
+        // Finalize reports first: jobs timed out below count as inactive
+        // because their activity is already older than the cutoff
+        let reports_sql = format!(
+            r#"
+            UPDATE test_reports r
+            SET status = CASE
+                    WHEN EXISTS (
+                        SELECT 1 FROM test_jobs j
+                        WHERE j.test_report_id = r.id
+                            AND j.deleted_at IS NULL
+                            AND j.status = 'complete'
+                    ) THEN 'complete'
+                    ELSE 'failed'
+                END,
+                missing_jobs = GREATEST(r.expected_jobs - (
+                    SELECT COUNT(*) FROM test_jobs j
+                    WHERE j.test_report_id = r.id
+                        AND j.deleted_at IS NULL
+                        AND j.status IN ('complete', 'failed')
+                ), 0),
+                updated_at = NOW()
+            WHERE r.deleted_at IS NULL
+                AND r.status IN {closable}
+                AND (
+                    (
+                        NOT r.is_open
+                        AND r.updated_at < $1
+                        AND EXISTS (
+                            SELECT 1 FROM test_jobs j
+                            WHERE j.test_report_id = r.id
+                                AND j.deleted_at IS NULL
+                        )
+                        AND (
+                            -- An upload that times out below, or already did
+                            EXISTS (
+                                SELECT 1 FROM test_jobs j
+                                WHERE j.test_report_id = r.id
+                                    AND j.deleted_at IS NULL
+                                    AND (
+                                        '{timedout}' IN (
+                                            j.html_upload_status,
+                                            j.screenshots_upload_status,
+                                            j.json_upload_status
+                                        )
+                                        OR (
+                                            (
+                                                j.html_upload_status IN {timeout_sources}
+                                                OR j.screenshots_upload_status IN {timeout_sources}
+                                                OR j.json_upload_status IN {timeout_sources}
+                                            )
+                                            AND {activity} < $1
+                                        )
+                                    )
+                            )
+                            OR NOT EXISTS (
+                                SELECT 1 FROM test_jobs j
+                                WHERE j.test_report_id = r.id
+                                    AND j.deleted_at IS NULL
+                                    AND j.status IN {unfinished}
+                            )
+                        )
+                    )
+                    OR (
+                        r.is_open
+                        AND r.updated_at < $2
+                        AND EXISTS (
+                            SELECT 1 FROM test_jobs j
+                            WHERE j.test_report_id = r.id
+                                AND j.deleted_at IS NULL
+                        )
+                    )
+                )
+                AND NOT EXISTS (
+                    SELECT 1 FROM test_jobs j
+                    WHERE j.test_report_id = r.id
+                        AND j.deleted_at IS NULL
+                        AND (
+                            j.status = 'processing'
+                            OR (j.status = 'pending' AND {activity} >= $1)
+                            OR (r.is_open AND {activity} >= $2)
+                        )
+                )
+            RETURNING r.id, r.status, r.pipeline_id
+            "#,
+            activity = JOB_ACTIVITY_SQL
+        );
+
+        let reports = FinalizedReport::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            &reports_sql,
+            [cutoff.into(), idle_cutoff.into()],
+        ))
+        .all(&txn)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to finalize stalled reports: {}", e)))?;
+
+        let jobs_sql = format!(
+            r#"
+            UPDATE test_jobs j
+            SET html_upload_status = CASE WHEN j.html_upload_status IN {timeout_sources}
+                    THEN '{timedout}' ELSE j.html_upload_status END,
+                screenshots_upload_status = CASE WHEN j.screenshots_upload_status IN {timeout_sources}
+                    THEN '{timedout}' ELSE j.screenshots_upload_status END,
+                json_upload_status = CASE WHEN j.json_upload_status IN {timeout_sources}
+                    THEN '{timedout}' ELSE j.json_upload_status END,
+                status = CASE WHEN j.json_upload_status IN {timeout_sources} AND j.status = '{pending}'
+                    THEN '{failed}' ELSE j.status END,
+                error_message = CASE WHEN j.json_upload_status IN {timeout_sources} AND j.status = '{pending}'
+                    THEN 'JSON upload timed out' ELSE j.error_message END,
+                updated_at = NOW()
+            WHERE j.deleted_at IS NULL
+                AND (
+                    j.html_upload_status IN {timeout_sources}
+                    OR j.screenshots_upload_status IN {timeout_sources}
+                    OR j.json_upload_status IN {timeout_sources}
+                )
+                AND {activity} < $1
+            RETURNING j.id, j.test_report_id, j.status
+            "#,
+            activity = JOB_ACTIVITY_SQL,
+            pending = JobStatus::Pending.name(),
+            failed = JobStatus::Failed.name(),
+        );
+
+        let jobs = TimedOutJob::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            &jobs_sql,
+            [cutoff.into()],
+        ))
+        .all(&txn)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to time out uploads: {}", e)))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to commit upload sweep: {}", e)))?;
+
+        Ok(Some(UploadSweep { jobs, reports }))
+    }
 }
@@ -1,53 +1,181 @@
 //! Database queries for reports.
 
+use std::collections::BTreeMap;
+
 use chrono::Utc;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, SqlErr,
 };
+use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
 use crate::entity::test_report::{self as report, ActiveModel, Entity as Report};
 use crate::error::{AppError, AppResult};
-use crate::models::{Framework, GitHubMetadata, ListReportsQuery, ReportStatus};
+use crate::models::{
+    Framework, GitHubMetadata, ListReportsQuery, MAX_EXPECTED_JOBS, ReportSort, ReportStatus,
+    SortOrder,
+};
+
+use crate::services::labels::parse_label_filter;
+use crate::services::lifecycle::{Lifecycle, REOPENED_REPORT_STATUS, sql_list};
+use crate::services::pipeline::PipelineIdentity;
 
 use super::DbPool;
 
+/// Report statuses that can still change, as a SQL list.
+fn open_statuses_sql() -> String {
+    sql_list(&ReportStatus::active())
+}
+
+/// Count a new job toward an open report's `expected_jobs`.
+///
+/// Returns `false` if the report is closed, no longer open or already has
+/// the maximum number of jobs. Runs on `db` so the caller can insert the job
+/// in the same transaction.
+pub(super) async fn add_open_report_job(
+    db: &impl sea_orm::ConnectionTrait,
+    id: Uuid,
+) -> AppResult<bool> {
+    use sea_orm::{DatabaseBackend, Statement};
+
+    let sql = format!(
+        r#"
+        UPDATE test_reports
+        SET expected_jobs = expected_jobs + 1, updated_at = NOW()
+        WHERE id = $1
+            AND deleted_at IS NULL
+            AND is_open
+            AND expected_jobs < $2
+            AND status IN {}
+        RETURNING id
+        "#,
+        open_statuses_sql()
+    );
+
+    let row = db
+        .query_one_raw(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            &sql,
+            [id.into(), MAX_EXPECTED_JOBS.into()],
+        ))
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to add report job: {}", e)))?;
+
+    Ok(row.is_some())
+}
+
 impl DbPool {
-    /// Insert a new report.
+    /// Insert a new report, unless a live report holds the idempotency key.
+    ///
+    /// Open reports start with `expected_jobs` 0; each job raises it. A new
+    /// report joins `pipeline`'s run, which is created with it if needed.
+    /// Returns the report and whether it was created; an existing report keeps
+    /// its pipeline run.
+    #[allow(clippy::too_many_arguments)]
     pub async fn insert_report(
         &self,
         id: Uuid,
-        expected_jobs: i32,
+        expected_jobs: Option<i32>,
         framework: Framework,
         github_metadata: Option<GitHubMetadata>,
-    ) -> AppResult<report::Model> {
+        labels: &BTreeMap<String, String>,
+        custom_metadata: Option<JsonValue>,
+        idempotency_key: Option<String>,
+        pipeline: Option<&PipelineIdentity>,
+    ) -> AppResult<(report::Model, bool)> {
+        use sea_orm::TransactionTrait;
+
+        if let Some(ref key) = idempotency_key
+            && let Some(existing) = self.find_report_by_idempotency_key(key).await?
+        {
+            return Ok((existing, false));
+        }
+
         let now = Utc::now();
 
         let github_json = github_metadata.and_then(|m: GitHubMetadata| m.to_json());
+        let labels_json = serde_json::to_value(labels)?;
+
+        // A concurrent duplicate rolls back the pipeline run upsert too
+        let txn = self
+            .connection()
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to start report insert: {}", e)))?;
+
+        let pipeline_id = match pipeline {
+            Some(identity) => Some(
+                super::pipeline_runs::upsert_pipeline_run(&txn, identity)
+                    .await?
+                    .id,
+            ),
+            None => None,
+        };
 
         let model = ActiveModel {
             id: Set(id),
-            expected_jobs: Set(expected_jobs),
+            expected_jobs: Set(expected_jobs.unwrap_or(0)),
+            is_open: Set(expected_jobs.is_none()),
             framework: Set(framework.as_str().to_string()),
             status: Set(ReportStatus::Initializing.as_str().to_string()),
             github_metadata: Set(github_json),
+            labels: Set(labels_json),
+            custom_metadata: Set(custom_metadata),
+            missing_jobs: Set(None),
+            idempotency_key: Set(idempotency_key.clone()),
+            pipeline_id: Set(pipeline_id),
             created_at: Set(now),
             updated_at: Set(now),
             deleted_at: Set(None),
         };
 
-        let result = model
-            .insert(self.connection())
-            .await
-            .map_err(|e| AppError::Database(format!("Failed to insert report: {}", e)))?;
+        match model.insert(&txn).await {
+            Ok(report) => {
+                txn.commit().await.map_err(|e| {
+                    AppError::Database(format!("Failed to commit report insert: {}", e))
+                })?;
+                Ok((report, true))
+            }
+            // Registered concurrently with the same key
+            Err(e)
+                if idempotency_key.is_some()
+                    && matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+            {
+                txn.rollback().await.map_err(|e| {
+                    AppError::Database(format!("Failed to roll back report insert: {}", e))
+                })?;
+                let key = idempotency_key.unwrap_or_default();
+                let existing = self
+                    .find_report_by_idempotency_key(&key)
+                    .await?
+                    .ok_or_else(|| AppError::Database(format!("Failed to insert report: {}", e)))?;
+                Ok((existing, false))
+            }
+            Err(e) => Err(AppError::Database(format!(
+                "Failed to insert report: {}",
+                e
+            ))),
+        }
+    }
 
-        Ok(result)
+    /// Get the live report registered with an idempotency key.
+    pub async fn find_report_by_idempotency_key(
+        &self,
+        key: &str,
+    ) -> AppResult<Option<report::Model>> {
+        Report::find()
+            .filter(report::Column::IdempotencyKey.eq(key))
+            .filter(report::Column::DeletedAt.is_null())
+            .one(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to find report: {}", e)))
     }
 
-    /// Get a report by ID.
+    /// Get a live (not soft-deleted) report by ID.
     pub async fn get_report_by_id(&self, id: Uuid) -> AppResult<Option<report::Model>> {
         let result = Report::find_by_id(id)
+            .filter(report::Column::DeletedAt.is_null())
             .one(self.connection())
             .await
             .map_err(|e| AppError::Database(format!("Failed to get report: {}", e)))?;
@@ -55,25 +183,250 @@ impl DbPool {
         Ok(result)
     }
 
-    /// Update report status.
+    /// Soft-delete a live report with its jobs, results and files.
+    ///
+    /// Returns `false` if the report doesn't exist or is already deleted.
+    pub async fn soft_delete_report(&self, id: Uuid) -> AppResult<bool> {
+        use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+        let sql = format!(
+            r#"
+            WITH deleted_report AS (
+                UPDATE test_reports
+                SET deleted_at = NOW(), updated_at = NOW()
+                WHERE id = $1 AND deleted_at IS NULL
+                RETURNING id
+            ),
+            deleted_jobs AS (
+                UPDATE test_jobs j
+                SET deleted_at = NOW(), updated_at = NOW()
+                FROM deleted_report r
+                WHERE j.test_report_id = r.id AND j.deleted_at IS NULL
+                RETURNING j.id
+            ),
+            {}
+            SELECT id FROM deleted_report
+            "#,
+            super::test_jobs::SOFT_DELETE_JOB_ROWS_SQL
+        );
+
+        let row = self
+            .connection()
+            .query_one_raw(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                &sql,
+                [id.into()],
+            ))
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete report: {}", e)))?;
+
+        Ok(row.is_some())
+    }
+
+    /// Move a live report to `status`.
+    ///
+    /// Fails with `Conflict` if the report's lifecycle doesn't allow the
+    /// change, e.g. for reports that are already closed.
     pub async fn update_report_status(
         &self,
         id: Uuid,
         status: ReportStatus,
+    ) -> AppResult<report::Model> {
+        let reports = Report::update_many()
+            .col_expr(
+                report::Column::Status,
+                sea_orm::sea_query::Expr::value(status.as_str()),
+            )
+            .col_expr(
+                report::Column::UpdatedAt,
+                sea_orm::sea_query::Expr::value(Utc::now()),
+            )
+            .filter(report::Column::Id.eq(id))
+            .filter(report::Column::DeletedAt.is_null())
+            .filter(report::Column::Status.is_in(ReportStatus::sources(status)))
+            .exec_with_returning(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to update report status: {}", e)))?;
+
+        match reports.into_iter().next() {
+            Some(report) => Ok(report),
+            None => {
+                let report = self
+                    .get_report_by_id(id)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound(format!("Report {}", id)))?;
+                Err(AppError::Conflict(format!(
+                    "Report {} can't change from {} to {}",
+                    id, report.status, status
+                )))
+            }
+        }
+    }
+
+    /// Reopen a closed report so it accepts jobs and uploads again.
+    ///
+    /// Clears the missing job count recorded on close. Returns `None` if the
+    /// report doesn't exist or isn't closed.
+    pub async fn reopen_report(&self, id: Uuid) -> AppResult<Option<report::Model>> {
+        let closed: Vec<&str> = ReportStatus::STATES
+            .iter()
+            .filter(|s| s.is_final())
+            .map(|s| s.as_str())
+            .collect();
+
+        let reports = Report::update_many()
+            .col_expr(
+                report::Column::Status,
+                sea_orm::sea_query::Expr::value(REOPENED_REPORT_STATUS.as_str()),
+            )
+            .col_expr(
+                report::Column::MissingJobs,
+                sea_orm::sea_query::Expr::value(Option::<i32>::None),
+            )
+            .col_expr(
+                report::Column::UpdatedAt,
+                sea_orm::sea_query::Expr::value(Utc::now()),
+            )
+            .filter(report::Column::Id.eq(id))
+            .filter(report::Column::DeletedAt.is_null())
+            .filter(report::Column::Status.is_in(closed))
+            .exec_with_returning(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to reopen report: {}", e)))?;
+
+        Ok(reports.into_iter().next())
+    }
+
+    /// Close an open report: `complete` if any job completed, `failed`
+    /// otherwise.
+    ///
+    /// `missing_jobs` records expected jobs that never finished. Returns
+    /// `None` if the report doesn't exist or is already closed.
+    pub async fn close_report(
+        &self,
+        id: Uuid,
+        missing_jobs: Option<i32>,
+    ) -> AppResult<Option<report::Model>> {
+        use sea_orm::{DatabaseBackend, Statement};
+
+        let sql = format!(
+            r#"
+            UPDATE test_reports r
+            SET status = CASE
+                    WHEN EXISTS (
+                        SELECT 1 FROM test_jobs j
+                        WHERE j.test_report_id = r.id
+                            AND j.deleted_at IS NULL
+                            AND j.status = 'complete'
+                    ) THEN 'complete'
+                    ELSE 'failed'
+                END,
+                missing_jobs = $2,
+                updated_at = NOW()
+            WHERE r.id = $1 AND r.deleted_at IS NULL AND r.status IN {}
+            RETURNING r.*
+            "#,
+            open_statuses_sql()
+        );
+
+        Report::find()
+            .from_raw_sql(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                &sql,
+                [id.into(), missing_jobs.into()],
+            ))
+            .one(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to close report: {}", e)))
+    }
+
+    /// Set an unclosed report's expected job count, ending open mode.
+    ///
+    /// Returns `None` if the report doesn't exist or is already closed.
+    pub async fn set_expected_jobs(
+        &self,
+        id: Uuid,
+        expected_jobs: i32,
+    ) -> AppResult<Option<report::Model>> {
+        use sea_orm::{DatabaseBackend, Statement};
+
+        let sql = format!(
+            r#"
+            UPDATE test_reports
+            SET expected_jobs = $2, is_open = FALSE, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL AND status IN {}
+            RETURNING *
+            "#,
+            open_statuses_sql()
+        );
+
+        Report::find()
+            .from_raw_sql(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                &sql,
+                [id.into(), expected_jobs.into()],
+            ))
+            .one(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to set expected jobs: {}", e)))
+    }
+
+    /// Cancel an open report.
+    ///
+    /// Returns `None` if the report doesn't exist or is already closed.
+    pub async fn cancel_report(
+        &self,
+        id: Uuid,
+        missing_jobs: i32,
+    ) -> AppResult<Option<report::Model>> {
+        use sea_orm::{DatabaseBackend, Statement};
+
+        let sql = format!(
+            r#"
+            UPDATE test_reports
+            SET status = 'cancelled', missing_jobs = $2, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL AND status IN {}
+            RETURNING *
+            "#,
+            open_statuses_sql()
+        );
+
+        Report::find()
+            .from_raw_sql(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                &sql,
+                [id.into(), missing_jobs.into()],
+            ))
+            .one(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to cancel report: {}", e)))
+    }
+
+    /// Update a report's labels and/or custom metadata.
+    pub async fn update_report_metadata(
+        &self,
+        id: Uuid,
+        labels: Option<&BTreeMap<String, String>>,
+        custom_metadata: Option<JsonValue>,
     ) -> AppResult<report::Model> {
         let report = self
             .get_report_by_id(id)
             .await?
-            .ok_or_else(|| AppError::NotFound(format!("Report {} not found", id)))?;
+            .ok_or_else(|| AppError::NotFound(format!("Report {}", id)))?;
 
         let mut active: ActiveModel = report.into();
-        active.status = Set(status.as_str().to_string());
+        if let Some(labels) = labels {
+            active.labels = Set(serde_json::to_value(labels)?);
+        }
+        if let Some(custom_metadata) = custom_metadata {
+            active.custom_metadata = Set(Some(custom_metadata));
+        }
         active.updated_at = Set(Utc::now());
 
         let result = active
             .update(self.connection())
             .await
-            .map_err(|e| AppError::Database(format!("Failed to update report status: {}", e)))?;
+            .map_err(|e| AppError::Database(format!("Failed to update report metadata: {}", e)))?;
 
         Ok(result)
     }
@@ -85,7 +438,8 @@ impl DbPool {
     ) -> AppResult<(Vec<report::Model>, u64)> {
         use sea_orm::sea_query::Expr;
 
-        let mut select = Report::find();
+        // Soft-deleted reports are hidden until purged
+        let mut select = Report::find().filter(report::Column::DeletedAt.is_null());
 
         // Apply filters
         if let Some(ref framework) = query.framework {
@@ -112,6 +466,71 @@ impl DbPool {
             ));
         }
 
+        // Abbreviated SHAs match as a prefix (validated as hex by the handler)
+        if let Some(ref sha) = query.sha {
+            select = select.filter(Expr::cust_with_values(
+                "github_metadata->>'sha' LIKE $1",
+                [format!("{}%", sha.to_lowercase())],
+            ));
+        }
+
+        if let Some(pr_number) = query.pr_number {
+            select = select.filter(Expr::cust_with_values(
+                "github_metadata->>'pr_number' = $1",
+                [pr_number.to_string()],
+            ));
+        }
+
+        if let Some(ref actor) = query.actor {
+            select = select.filter(Expr::cust_with_values(
+                "github_metadata->>'actor' = $1",
+                [actor.clone()],
+            ));
+        }
+
+        if let Some(ref workflow) = query.workflow {
+            select = select.filter(Expr::cust_with_values(
+                "github_metadata->>'workflow' = $1",
+                [workflow.clone()],
+            ));
+        }
+
+        if let Some(ref event_name) = query.event_name {
+            select = select.filter(Expr::cust_with_values(
+                "github_metadata->>'event_name' = $1",
+                [event_name.clone()],
+            ));
+        }
+
+        if let Some(from) = query.from {
+            select = select.filter(report::Column::CreatedAt.gte(from));
+        }
+
+        if let Some(to) = query.to {
+            select = select.filter(report::Column::CreatedAt.lte(to));
+        }
+
+        if let Some(ref label) = query.label {
+            let labels = parse_label_filter(label).map_err(AppError::InvalidInput)?;
+            if !labels.is_empty() {
+                select = select.filter(Expr::cust_with_values(
+                    "labels @> $1",
+                    [serde_json::to_value(labels)?],
+                ));
+            }
+        }
+
+        // Failure counts come from the per-report rollup (quarantined excluded)
+        if let Some(has_failures) = query.has_failures {
+            let exists = "EXISTS (SELECT 1 FROM report_rollups rr \
+                 WHERE rr.report_id = test_reports.id AND rr.failed_count > 0)";
+            select = select.filter(if has_failures {
+                Expr::cust(exists)
+            } else {
+                Expr::cust(format!("NOT {}", exists))
+            });
+        }
+
         // Count total before pagination
         let total = select
             .clone()
@@ -123,8 +542,32 @@ impl DbPool {
         let limit = query.limit.clamp(1, 100) as u64;
         let offset = query.offset.max(0) as u64;
 
+        let order = match query.order {
+            SortOrder::Asc => Order::Asc,
+            SortOrder::Desc => Order::Desc,
+        };
+        select = match query.sort {
+            ReportSort::CreatedAt => select,
+            ReportSort::Failures => select.order_by(
+                Expr::cust(
+                    "COALESCE((SELECT rr.failed_count FROM report_rollups rr \
+                     WHERE rr.report_id = test_reports.id), 0)",
+                ),
+                order.clone(),
+            ),
+            ReportSort::Duration => select.order_by(
+                Expr::cust(
+                    "COALESCE((SELECT rr.duration_ms FROM report_rollups rr \
+                     WHERE rr.report_id = test_reports.id), 0)",
+                ),
+                order.clone(),
+            ),
+        };
+
+        // Creation time breaks ties so pages are stable
         let reports = select
-            .order_by_desc(report::Column::CreatedAt)
+            .order_by(report::Column::CreatedAt, order.clone())
+            .order_by(report::Column::Id, order)
             .offset(offset)
             .limit(limit)
             .all(self.connection())
@@ -133,4 +576,147 @@ impl DbPool {
 
         Ok((reports, total))
     }
+
+    /// Get the latest complete report of each repository, framework,
+    /// workflow and label combination on a git ref.
+    ///
+    /// `git_ref` matches stored refs as given or as a branch name under
+    /// `refs/heads/`.
+    pub async fn get_latest_complete_reports(
+        &self,
+        git_ref: &str,
+        repository: Option<&str>,
+    ) -> AppResult<Vec<report::Model>> {
+        use sea_orm::{DatabaseBackend, Statement};
+
+        let mut values: Vec<sea_orm::Value> = vec![
+            git_ref.to_string().into(),
+            format!("refs/heads/{}", git_ref).into(),
+        ];
+        let mut repository_filter = String::new();
+        if let Some(repository) = repository {
+            values.push(repository.to_string().into());
+            repository_filter = format!("AND github_metadata->>'repository' = ${}", values.len());
+        }
+
+        let sql = format!(
+            r#"
+            SELECT DISTINCT ON (
+                github_metadata->>'repository', framework, github_metadata->>'workflow', labels
+            ) *
+            FROM test_reports
+            WHERE deleted_at IS NULL
+                AND status = 'complete'
+                AND github_metadata->>'ref' IN ($1, $2)
+                {}
+            ORDER BY github_metadata->>'repository', framework, github_metadata->>'workflow',
+                labels, created_at DESC
+            "#,
+            repository_filter
+        );
+
+        Report::find()
+            .from_raw_sql(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                &sql,
+                values,
+            ))
+            .all(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to get latest reports: {}", e)))
+    }
+
+    /// Get the distinct git refs of complete reports starting with the
+    /// branch name `prefix`, as a bare name, branch or tag ref.
+    pub async fn get_complete_report_refs(
+        &self,
+        prefix: &str,
+        repository: Option<&str>,
+    ) -> AppResult<Vec<String>> {
+        use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+        let escaped = prefix
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        // Left-anchored so the ref index serves each pattern
+        let mut values: Vec<sea_orm::Value> = ["", "refs/heads/", "refs/tags/"]
+            .iter()
+            .map(|namespace| format!("{}{}%", namespace, escaped).into())
+            .collect();
+        let mut repository_filter = String::new();
+        if let Some(repository) = repository {
+            values.push(repository.to_string().into());
+            repository_filter = format!("AND github_metadata->>'repository' = ${}", values.len());
+        }
+
+        let sql = format!(
+            r#"
+            SELECT DISTINCT github_metadata->>'ref' AS git_ref
+            FROM test_reports
+            WHERE deleted_at IS NULL
+                AND status = 'complete'
+                AND (
+                    github_metadata->>'ref' LIKE $1
+                    OR github_metadata->>'ref' LIKE $2
+                    OR github_metadata->>'ref' LIKE $3
+                )
+                {}
+            "#,
+            repository_filter
+        );
+
+        let rows = self
+            .connection()
+            .query_all_raw(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                &sql,
+                values,
+            ))
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to get report refs: {}", e)))?;
+
+        rows.iter()
+            .map(|row| {
+                row.try_get::<String>("", "git_ref")
+                    .map_err(|e| AppError::Database(format!("Failed to get report refs: {}", e)))
+            })
+            .collect()
+    }
+
+    /// Get the reports registered by a GitHub workflow run, oldest first.
+    pub async fn get_reports_by_run(
+        &self,
+        repository: &str,
+        run_id: &str,
+        workflow: Option<&str>,
+    ) -> AppResult<Vec<report::Model>> {
+        use sea_orm::sea_query::Expr;
+
+        let mut select = Report::find()
+            .filter(report::Column::DeletedAt.is_null())
+            .filter(Expr::cust_with_values(
+                "github_metadata->>'repository' = $1",
+                [repository.to_string()],
+            ))
+            .filter(Expr::cust_with_values(
+                "github_metadata->>'run_id' = $1",
+                [run_id.to_string()],
+            ));
+
+        if let Some(workflow) = workflow {
+            select = select.filter(Expr::cust_with_values(
+                "github_metadata->>'workflow' = $1",
+                [workflow.to_string()],
+            ));
+        }
+
+        let reports = select
+            .order_by_asc(report::Column::CreatedAt)
+            .all(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to get run reports: {}", e)))?;
+
+        Ok(reports)
+    }
 }
@@ -11,6 +11,7 @@ use uuid::Uuid;
 use crate::entity::test_case::{self, ActiveModel as TestCaseActiveModel, Entity as TestCase};
 use crate::entity::test_suite::{self, ActiveModel as TestSuiteActiveModel, Entity as TestSuite};
 use crate::error::{AppError, AppResult};
+use crate::models::GlobalSearchQuery;
 
 use super::DbPool;
 
@@ -24,6 +25,8 @@ pub struct NewTestSuite {
     pub failed_count: i32,
     pub skipped_count: i32,
     pub flaky_count: i32,
+    pub quarantined_count: i32,
+    pub known_issue_count: i32,
     pub duration_ms: i32,
     /// Actual test execution start time from framework JSON.
     pub start_time: Option<chrono::DateTime<chrono::Utc>>,
@@ -39,10 +42,73 @@ pub struct NewTestCase {
     pub duration_ms: i32,
     pub retry_count: i32,
     pub error_message: Option<String>,
+    pub failure_signature: Option<String>,
+    pub quarantined: bool,
+    pub owner: Option<String>,
+    pub known_issue_key: Option<String>,
     pub sequence: i32,
     pub attachments: Option<JsonValue>,
 }
 
+/// Median historical duration of one spec file.
+#[derive(Debug, sea_orm::FromQueryResult)]
+pub struct FileDurationMedian {
+    pub file_path: String,
+    pub median_ms: i64,
+    pub samples: i64,
+}
+
+/// Last attempt of a failed or flaky test.
+#[derive(Debug, sea_orm::FromQueryResult)]
+pub struct RerunCandidate {
+    pub file_path: Option<String>,
+    pub title: String,
+    pub full_title: String,
+    pub status: String,
+}
+
+/// Final status of a test in one job, for merging run attempts.
+#[derive(Debug, sea_orm::FromQueryResult)]
+pub struct RunTestStatus {
+    pub report_id: Uuid,
+    pub full_title: String,
+    pub status: String,
+    pub quarantined: bool,
+    pub known_issue_key: Option<String>,
+}
+
+/// Final status of a test in one job, with the job's environment.
+#[derive(Debug, sea_orm::FromQueryResult)]
+pub struct MatrixRow {
+    pub full_title: String,
+    pub status: String,
+    pub environment: Option<JsonValue>,
+}
+
+/// A test case matching a global search, with raw `ts_headline` output.
+#[derive(Debug, sea_orm::FromQueryResult)]
+pub struct SearchRow {
+    pub id: Uuid,
+    pub suite_id: Uuid,
+    pub job_id: Uuid,
+    pub report_id: Uuid,
+    pub title: String,
+    pub full_title: String,
+    pub status: String,
+    pub file_path: Option<String>,
+    pub repository: Option<String>,
+    pub git_ref: Option<String>,
+    pub created_at: chrono::DateTime<Utc>,
+    pub rank: f64,
+    pub title_headline: String,
+    pub error_headline: Option<String>,
+}
+
+/// Weighted search document of a test case. Must match the expression of
+/// `idx_test_cases_search_vector` for the index to be used.
+const SEARCH_VECTOR_SQL: &str = "(setweight(to_tsvector('simple', tc.full_title), 'A') || \
+     setweight(to_tsvector('simple', COALESCE(tc.error_message, '')), 'B'))";
+
 /// Query parameters for test suites.
 #[derive(Debug, Default)]
 pub struct QueryTestSuitesParams {
@@ -57,6 +123,7 @@ pub struct QueryTestCasesParams {
     pub job_id: Option<Uuid>,
     pub suite_id: Option<Uuid>,
     pub status: Option<String>,
+    pub owner: Option<String>,
     pub limit: i64,
     pub offset: i64,
 }
@@ -77,6 +144,8 @@ impl DbPool {
             failed_count: Set(suite.failed_count),
             skipped_count: Set(suite.skipped_count),
             flaky_count: Set(suite.flaky_count),
+            quarantined_count: Set(suite.quarantined_count),
+            known_issue_count: Set(suite.known_issue_count),
             duration_ms: Set(suite.duration_ms),
             start_time: Set(suite.start_time),
             created_at: Set(now),
@@ -107,6 +176,10 @@ impl DbPool {
             duration_ms: Set(test_case.duration_ms),
             retry_count: Set(test_case.retry_count),
             error_message: Set(test_case.error_message),
+            failure_signature: Set(test_case.failure_signature),
+            quarantined: Set(test_case.quarantined),
+            owner: Set(test_case.owner),
+            known_issue_key: Set(test_case.known_issue_key),
             sequence: Set(test_case.sequence),
             attachments: Set(test_case.attachments),
             created_at: Set(now),
@@ -128,6 +201,7 @@ impl DbPool {
         suite_id: Uuid,
     ) -> AppResult<Option<test_suite::Model>> {
         let result = TestSuite::find_by_id(suite_id)
+            .filter(test_suite::Column::DeletedAt.is_null())
             .one(self.connection())
             .await
             .map_err(|e| AppError::Database(format!("Failed to get test suite: {}", e)))?;
@@ -135,17 +209,47 @@ impl DbPool {
         Ok(result)
     }
 
+    /// Get a single test case by ID.
+    pub async fn get_test_case_by_id(&self, case_id: Uuid) -> AppResult<Option<test_case::Model>> {
+        let result = TestCase::find_by_id(case_id)
+            .filter(test_case::Column::DeletedAt.is_null())
+            .one(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to get test case: {}", e)))?;
+
+        Ok(result)
+    }
+
     /// Get test suites by report ID (through jobs).
+    /// When `owner` is set, only suites containing tests with that owner are returned.
     pub async fn get_test_suites_by_report_id(
         &self,
         report_id: Uuid,
+        owner: Option<&str>,
     ) -> AppResult<Vec<test_suite::Model>> {
         use crate::entity::test_job as job;
+        use sea_orm::sea_query::Query;
         use sea_orm::{JoinType, RelationTrait};
 
-        let result = TestSuite::find()
+        let mut select = TestSuite::find()
             .join(JoinType::InnerJoin, test_suite::Relation::Job.def())
             .filter(job::Column::TestReportId.eq(report_id))
+            .filter(test_suite::Column::DeletedAt.is_null());
+
+        if let Some(owner) = owner {
+            select = select.filter(
+                test_suite::Column::Id.in_subquery(
+                    Query::select()
+                        .column(test_case::Column::TestSuiteId)
+                        .from(test_case::Entity)
+                        .and_where(test_case::Column::Owner.eq(owner))
+                        .and_where(test_case::Column::DeletedAt.is_null())
+                        .to_owned(),
+                ),
+            );
+        }
+
+        let result = select
             .order_by_asc(test_suite::Column::Id) // UUIDv7 is time-ordered
             .all(self.connection())
             .await
@@ -163,6 +267,7 @@ impl DbPool {
     ) -> AppResult<Vec<test_case::Model>> {
         let result = TestCase::find()
             .filter(test_case::Column::TestSuiteId.eq(suite_id))
+            .filter(test_case::Column::DeletedAt.is_null())
             .order_by_asc(test_case::Column::Sequence)
             .all(self.connection())
             .await
@@ -175,6 +280,7 @@ impl DbPool {
     pub async fn get_test_cases_by_job_id(&self, job_id: Uuid) -> AppResult<Vec<test_case::Model>> {
         let result = TestCase::find()
             .filter(test_case::Column::TestJobId.eq(job_id))
+            .filter(test_case::Column::DeletedAt.is_null())
             .order_by_asc(test_case::Column::Sequence)
             .all(self.connection())
             .await
@@ -183,12 +289,58 @@ impl DbPool {
         Ok(result)
     }
 
+    /// Get a page of a job's test cases, ordered by suite ID, sequence and ID,
+    /// starting after `after` (keyset pagination for large exports).
+    pub async fn get_test_case_page(
+        &self,
+        job_id: Uuid,
+        after: Option<&test_case::Model>,
+        limit: u64,
+    ) -> AppResult<Vec<test_case::Model>> {
+        use sea_orm::Condition;
+
+        let mut select = TestCase::find()
+            .filter(test_case::Column::TestJobId.eq(job_id))
+            .filter(test_case::Column::DeletedAt.is_null());
+
+        if let Some(after) = after {
+            select = select.filter(
+                Condition::any()
+                    .add(test_case::Column::TestSuiteId.gt(after.test_suite_id))
+                    .add(
+                        Condition::all()
+                            .add(test_case::Column::TestSuiteId.eq(after.test_suite_id))
+                            .add(
+                                Condition::any()
+                                    .add(test_case::Column::Sequence.gt(after.sequence))
+                                    .add(
+                                        Condition::all()
+                                            .add(test_case::Column::Sequence.eq(after.sequence))
+                                            .add(test_case::Column::Id.gt(after.id)),
+                                    ),
+                            ),
+                    ),
+            );
+        }
+
+        let result = select
+            .order_by_asc(test_case::Column::TestSuiteId)
+            .order_by_asc(test_case::Column::Sequence)
+            .order_by_asc(test_case::Column::Id)
+            .limit(limit)
+            .all(self.connection())
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to get test case page: {}", e)))?;
+
+        Ok(result)
+    }
+
     /// Query test suites with pagination.
     pub async fn query_test_suites(
         &self,
         query: &QueryTestSuitesParams,
     ) -> AppResult<(Vec<test_suite::Model>, u64)> {
-        let mut select = TestSuite::find();
+        let mut select = TestSuite::find().filter(test_suite::Column::DeletedAt.is_null());
 
         if let Some(job_id) = query.job_id {
             select = select.filter(test_suite::Column::TestJobId.eq(job_id));
@@ -221,7 +373,7 @@ impl DbPool {
         &self,
         query: &QueryTestCasesParams,
     ) -> AppResult<(Vec<test_case::Model>, u64)> {
-        let mut select = TestCase::find();
+        let mut select = TestCase::find().filter(test_case::Column::DeletedAt.is_null());
 
         if let Some(job_id) = query.job_id {
             select = select.filter(test_case::Column::TestJobId.eq(job_id));
@@ -235,6 +387,10 @@ impl DbPool {
             select = select.filter(test_case::Column::Status.eq(status));
         }
 
+        if let Some(ref owner) = query.owner {
+            select = select.filter(test_case::Column::Owner.eq(owner));
+        }
+
         // Count total before pagination
         let total = select
             .clone()
@@ -279,6 +435,7 @@ impl DbPool {
             .join(JoinType::InnerJoin, test_case::Relation::TestSuite.def())
             .join(JoinType::InnerJoin, test_suite::Relation::Job.def())
             .filter(test_job::Column::TestReportId.eq(report_id))
+            .filter(test_case::Column::DeletedAt.is_null())
             .filter(
                 sea_orm::Condition::any()
                     .add(
@@ -334,6 +491,361 @@ impl DbPool {
         Ok(result)
     }
 
+    /// Summarize test results per owner for a report.
+    ///
+    /// Each test counts once per job using its last attempt; tests without an
+    /// owner are grouped under a null owner.
+    pub async fn get_owner_summaries_by_report(
+        &self,
+        report_id: Uuid,
+    ) -> AppResult<Vec<crate::models::OwnerFailureSummary>> {
+        use sea_orm::{FromQueryResult, Statement};
+
+        #[derive(Debug, FromQueryResult)]
+        struct OwnerRow {
+            owner: Option<String>,
+            total: i64,
+            failed: i64,
+            flaky: i64,
+            quarantined: i64,
+        }
+
+        let rows = OwnerRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            r#"
+            WITH final_attempts AS (
+                SELECT DISTINCT ON (tc.test_job_id, tc.full_title)
+                    tc.owner, tc.status, tc.quarantined
+                FROM test_cases tc
+                INNER JOIN test_jobs j ON j.id = tc.test_job_id
+                WHERE j.test_report_id = $1
+                    AND tc.deleted_at IS NULL
+                ORDER BY tc.test_job_id, tc.full_title, tc.retry_count DESC
+            )
+            SELECT
+                owner,
+                COUNT(*) as total,
+                COUNT(*) FILTER (
+                    WHERE status IN ('failed', 'timedOut') AND NOT quarantined
+                ) as failed,
+                COUNT(*) FILTER (WHERE status = 'flaky') as flaky,
+                COUNT(*) FILTER (
+                    WHERE status IN ('failed', 'timedOut') AND quarantined
+                ) as quarantined
+            FROM final_attempts
+            GROUP BY owner
+            ORDER BY failed DESC, owner NULLS LAST
+            "#,
+            [sea_orm::Value::from(report_id)],
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to get owner summaries: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| crate::models::OwnerFailureSummary {
+                owner: r.owner,
+                total: r.total,
+                failed: r.failed,
+                flaky: r.flaky,
+                quarantined: r.quarantined,
+            })
+            .collect())
+    }
+
+    /// Get the last attempt of each failed or flaky test in a report.
+    ///
+    /// Tests are deduplicated per job and suite; `job_id` restricts the
+    /// result to one job.
+    pub async fn get_rerun_candidates(
+        &self,
+        report_id: Uuid,
+        job_id: Option<Uuid>,
+    ) -> AppResult<Vec<RerunCandidate>> {
+        use sea_orm::{FromQueryResult, Statement};
+
+        let mut values = vec![sea_orm::Value::from(report_id)];
+        let job_filter = match job_id {
+            Some(job_id) => {
+                values.push(job_id.into());
+                "AND tc.test_job_id = $2"
+            }
+            None => "",
+        };
+
+        let sql = format!(
+            r#"
+            SELECT file_path, title, full_title, status
+            FROM (
+                SELECT DISTINCT ON (tc.test_job_id, tc.test_suite_id, tc.full_title)
+                    ts.file_path, tc.title, tc.full_title, tc.status
+                FROM test_cases tc
+                INNER JOIN test_suites ts ON ts.id = tc.test_suite_id
+                INNER JOIN test_jobs j ON j.id = tc.test_job_id
+                WHERE j.test_report_id = $1
+                    AND tc.deleted_at IS NULL
+                    {}
+                ORDER BY tc.test_job_id, tc.test_suite_id, tc.full_title, tc.retry_count DESC
+            ) final_attempts
+            WHERE status IN ('failed', 'timedOut', 'flaky')
+            ORDER BY file_path, full_title
+            "#,
+            job_filter
+        );
+
+        RerunCandidate::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            &sql,
+            values,
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to get rerun candidates: {}", e)))
+    }
+
+    /// Get the final status of each test per job in a report, with the job's
+    /// environment.
+    pub async fn get_matrix_statuses(&self, report_id: Uuid) -> AppResult<Vec<MatrixRow>> {
+        use sea_orm::{FromQueryResult, Statement};
+
+        let sql = r#"
+            SELECT DISTINCT ON (tc.test_job_id, tc.full_title)
+                tc.full_title, tc.status, j.environment
+            FROM test_cases tc
+            INNER JOIN test_jobs j ON j.id = tc.test_job_id
+            WHERE j.test_report_id = $1
+                AND j.deleted_at IS NULL
+                AND tc.deleted_at IS NULL
+            ORDER BY tc.test_job_id, tc.full_title, tc.retry_count DESC
+        "#;
+
+        MatrixRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            sql,
+            [sea_orm::Value::from(report_id)],
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to get matrix statuses: {}", e)))
+    }
+
+    /// Get the final status of each test per job across several reports.
+    pub async fn get_run_test_statuses(
+        &self,
+        report_ids: &[Uuid],
+    ) -> AppResult<Vec<RunTestStatus>> {
+        use sea_orm::{FromQueryResult, Statement};
+
+        if report_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let in_clause: String = (1..=report_ids.len())
+            .map(|i| format!("${}", i))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let sql = format!(
+            r#"
+            SELECT DISTINCT ON (tc.test_job_id, tc.full_title)
+                j.test_report_id AS report_id, tc.full_title, tc.status, tc.quarantined,
+                tc.known_issue_key
+            FROM test_cases tc
+            INNER JOIN test_jobs j ON j.id = tc.test_job_id
+            WHERE j.test_report_id IN ({})
+                AND tc.deleted_at IS NULL
+            ORDER BY tc.test_job_id, tc.full_title, tc.retry_count DESC
+            "#,
+            in_clause
+        );
+
+        let values: Vec<sea_orm::Value> = report_ids
+            .iter()
+            .map(|id| sea_orm::Value::Uuid(Some(*id)))
+            .collect();
+
+        RunTestStatus::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            &sql,
+            values,
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to get run test statuses: {}", e)))
+    }
+
+    /// Search test titles, suite file paths and error messages across reports.
+    ///
+    /// Combines full-text matching with trigram word similarity so partial
+    /// words and paths also match. Candidates are collected with one UNION
+    /// branch per match kind, so each uses its index instead of a scan.
+    /// Results are ordered by rank, then ID, and `after` continues from a
+    /// previous page's last `(rank, id)`.
+    pub async fn search_test_cases(
+        &self,
+        query: &GlobalSearchQuery,
+        after: Option<(f64, Uuid)>,
+        limit: i64,
+    ) -> AppResult<Vec<SearchRow>> {
+        use crate::services::search::{HIGHLIGHT_START, HIGHLIGHT_STOP};
+        use sea_orm::{FromQueryResult, Statement};
+
+        let mut values: Vec<sea_orm::Value> = vec![query.q.trim().into()];
+        let mut conditions = vec![
+            "tc.deleted_at IS NULL".to_string(),
+            "ts.deleted_at IS NULL".to_string(),
+            "r.deleted_at IS NULL".to_string(),
+        ];
+
+        if let Some(ref repository) = query.repository {
+            values.push(repository.clone().into());
+            conditions.push(format!(
+                "r.github_metadata->>'repository' = ${}",
+                values.len()
+            ));
+        }
+
+        if let Some(ref git_ref) = query.git_ref {
+            values.push(git_ref.clone().into());
+            conditions.push(format!("r.github_metadata->>'ref' = ${}", values.len()));
+        }
+
+        if let Some(ref status) = query.status {
+            values.push(status.clone().into());
+            conditions.push(format!("tc.status = ${}", values.len()));
+        }
+
+        if let Some(from) = query.from {
+            values.push(from.into());
+            conditions.push(format!("r.created_at >= ${}", values.len()));
+        }
+
+        if let Some(to) = query.to {
+            values.push(to.into());
+            conditions.push(format!("r.created_at <= ${}", values.len()));
+        }
+
+        let cursor_filter = match after {
+            Some((rank, id)) => {
+                values.push(rank.into());
+                values.push(id.into());
+                format!(
+                    "WHERE (rank, id) < (${}, ${})",
+                    values.len() - 1,
+                    values.len()
+                )
+            }
+            None => String::new(),
+        };
+
+        values.push(limit.into());
+        let limit_param = values.len();
+
+        values.push(
+            format!(
+                "StartSel={}, StopSel={}, HighlightAll=true",
+                HIGHLIGHT_START, HIGHLIGHT_STOP
+            )
+            .into(),
+        );
+        let title_options = values.len();
+        values.push(
+            format!(
+                "StartSel={}, StopSel={}, MaxFragments=2, MaxWords=20, MinWords=5",
+                HIGHLIGHT_START, HIGHLIGHT_STOP
+            )
+            .into(),
+        );
+        let error_options = values.len();
+
+        let sql = format!(
+            r#"
+            WITH search AS (
+                SELECT websearch_to_tsquery('simple', $1) AS query
+            ),
+            -- One branch per index: the search vector, then the trigram
+            -- indexes on titles and suite file paths
+            candidates AS (
+                SELECT tc.id
+                FROM test_cases tc
+                CROSS JOIN search
+                WHERE tc.deleted_at IS NULL AND {vector} @@ search.query
+                UNION
+                SELECT tc.id
+                FROM test_cases tc
+                WHERE tc.deleted_at IS NULL AND $1 <% tc.full_title
+                UNION
+                SELECT tc.id
+                FROM test_suites ts
+                INNER JOIN test_cases tc ON tc.test_suite_id = ts.id
+                WHERE ts.deleted_at IS NULL
+                    AND ts.file_path IS NOT NULL
+                    AND $1 <% ts.file_path
+                    AND tc.deleted_at IS NULL
+            ),
+            matches AS (
+                SELECT
+                    tc.id,
+                    tc.test_suite_id AS suite_id,
+                    tc.test_job_id AS job_id,
+                    j.test_report_id AS report_id,
+                    tc.title,
+                    tc.full_title,
+                    tc.status,
+                    tc.error_message,
+                    ts.file_path,
+                    r.github_metadata->>'repository' AS repository,
+                    r.github_metadata->>'ref' AS git_ref,
+                    r.created_at,
+                    (
+                        ts_rank({vector}, search.query)
+                        + 0.5 * GREATEST(
+                            word_similarity($1, tc.full_title),
+                            word_similarity($1, COALESCE(ts.file_path, ''))
+                        )
+                    )::FLOAT8 AS rank
+                FROM candidates c
+                INNER JOIN test_cases tc ON tc.id = c.id
+                INNER JOIN test_suites ts ON ts.id = tc.test_suite_id
+                INNER JOIN test_jobs j ON j.id = tc.test_job_id
+                INNER JOIN test_reports r ON r.id = j.test_report_id
+                CROSS JOIN search
+                WHERE {conditions}
+            ),
+            page AS (
+                SELECT * FROM matches
+                {cursor_filter}
+                ORDER BY rank DESC, id DESC
+                LIMIT ${limit_param}
+            )
+            SELECT
+                page.id, page.suite_id, page.job_id, page.report_id, page.title,
+                page.full_title, page.status, page.file_path, page.repository,
+                page.git_ref, page.created_at, page.rank,
+                ts_headline('simple', page.full_title, search.query, ${title_options})
+                    AS title_headline,
+                CASE WHEN page.error_message IS NOT NULL THEN
+                    ts_headline('simple', page.error_message, search.query, ${error_options})
+                END AS error_headline
+            FROM page
+            CROSS JOIN search
+            ORDER BY page.rank DESC, page.id DESC
+            "#,
+            vector = SEARCH_VECTOR_SQL,
+            conditions = conditions.join(" AND "),
+        );
+
+        SearchRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            &sql,
+            values,
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to search test cases: {}", e)))
+    }
+
     /// Batch get test stats for multiple reports.
     /// Aggregates stats from test_suites through jobs.
     /// Returns a HashMap of report_id -> TestStats.
@@ -355,6 +867,8 @@ impl DbPool {
             failed: i64,
             skipped: i64,
             flaky: i64,
+            quarantined: i64,
+            known_issues: i64,
             duration_ms: Option<i64>,
             wall_clock_ms: Option<i64>,
         }
@@ -381,6 +895,8 @@ impl DbPool {
                 COALESCE(suite_stats.failed, 0) as failed,
                 COALESCE(suite_stats.skipped, 0) as skipped,
                 COALESCE(suite_stats.flaky, 0) as flaky,
+                COALESCE(suite_stats.quarantined, 0) as quarantined,
+                COALESCE(suite_stats.known_issues, 0) as known_issues,
                 job_stats.duration_ms,
                 job_stats.wall_clock_ms
             FROM (
@@ -398,6 +914,7 @@ impl DbPool {
                     END as wall_clock_ms
                 FROM test_jobs j
                 WHERE j.test_report_id IN ({})
+                    AND j.deleted_at IS NULL
                 GROUP BY j.test_report_id
             ) job_stats
             LEFT JOIN (
@@ -408,10 +925,13 @@ impl DbPool {
                     SUM(ts.passed_count) as passed,
                     SUM(ts.failed_count) as failed,
                     SUM(ts.skipped_count) as skipped,
-                    SUM(ts.flaky_count) as flaky
+                    SUM(ts.flaky_count) as flaky,
+                    SUM(ts.quarantined_count) as quarantined,
+                    SUM(ts.known_issue_count) as known_issues
                 FROM test_jobs j
                 INNER JOIN test_suites ts ON ts.test_job_id = j.id
                 WHERE j.test_report_id IN ({})
+                    AND ts.deleted_at IS NULL
                 GROUP BY j.test_report_id
             ) suite_stats ON suite_stats.report_id = job_stats.report_id
             "#,
@@ -442,6 +962,12 @@ impl DbPool {
                     failed: result.failed as i32,
                     skipped: result.skipped as i32,
                     flaky: result.flaky as i32,
+                    quarantined: result.quarantined as i32,
+                    known_issues: result.known_issues as i32,
+                    outcome: crate::models::ReportOutcome::from_counts(
+                        result.failed as i32,
+                        result.quarantined as i32,
+                    ),
                     duration_ms: result.duration_ms.map(|ms| ms.max(0)),
                     wall_clock_ms: result.wall_clock_ms.map(|ms| ms.max(0)),
                 },
@@ -450,4 +976,80 @@ impl DbPool {
 
         Ok(stats_map)
     }
+
+    /// Get the median historical duration of spec files.
+    ///
+    /// Suites from the same file within one job are summed first, so the
+    /// median is over per-run file durations. Files without history in the
+    /// window are omitted.
+    pub async fn get_file_duration_medians(
+        &self,
+        file_paths: &[String],
+        repository: Option<&str>,
+        framework: Option<crate::models::Framework>,
+        since: chrono::DateTime<Utc>,
+    ) -> AppResult<Vec<FileDurationMedian>> {
+        use sea_orm::{FromQueryResult, Statement};
+
+        if file_paths.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut values: Vec<sea_orm::Value> = file_paths.iter().map(|p| p.clone().into()).collect();
+        let in_clause: String = (1..=file_paths.len())
+            .map(|i| format!("${}", i))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut conditions = vec![
+            format!("ts.file_path IN ({})", in_clause),
+            "ts.deleted_at IS NULL".to_string(),
+            "r.deleted_at IS NULL".to_string(),
+        ];
+
+        values.push(since.into());
+        conditions.push(format!("ts.created_at >= ${}", values.len()));
+
+        if let Some(repository) = repository {
+            values.push(repository.into());
+            conditions.push(format!(
+                "r.github_metadata->>'repository' = ${}",
+                values.len()
+            ));
+        }
+
+        if let Some(framework) = framework {
+            values.push(framework.as_str().into());
+            conditions.push(format!("r.framework = ${}", values.len()));
+        }
+
+        let sql = format!(
+            r#"
+            WITH per_run AS (
+                SELECT ts.file_path, ts.test_job_id, SUM(ts.duration_ms)::BIGINT as duration_ms
+                FROM test_suites ts
+                INNER JOIN test_jobs j ON j.id = ts.test_job_id
+                INNER JOIN test_reports r ON r.id = j.test_report_id
+                WHERE {}
+                GROUP BY ts.file_path, ts.test_job_id
+            )
+            SELECT
+                file_path,
+                percentile_cont(0.5) WITHIN GROUP (ORDER BY duration_ms)::BIGINT as median_ms,
+                COUNT(*) as samples
+            FROM per_run
+            GROUP BY file_path
+            "#,
+            conditions.join(" AND ")
+        );
+
+        FileDurationMedian::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            &sql,
+            values,
+        ))
+        .all(self.connection())
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to get file durations: {}", e)))
+    }
 }
@@ -0,0 +1,156 @@
+//! Database operations for failure triage records.
+
+use chrono::Utc;
+use sea_orm::*;
+use uuid::Uuid;
+
+use crate::entity::triage_record::{ActiveModel, Column, Entity, Model};
+use crate::error::AppResult;
+use crate::models::{TriageClassification, TriageListQuery, TriageRecord};
+
+/// Insert a new triage record.
+pub async fn insert(
+    db: &DatabaseConnection,
+    record: &TriageRecord,
+    author_key_id: &str,
+) -> AppResult<()> {
+    let model = ActiveModel {
+        id: Set(record.id),
+        test_case_id: Set(record.test_case_id),
+        full_title: Set(record.full_title.clone()),
+        failure_signature: Set(record.failure_signature.clone()),
+        repository: Set(record.repository.clone()),
+        classification: Set(record.classification.as_str().to_string()),
+        notes: Set(record.notes.clone()),
+        issue_url: Set(record.issue_url.clone()),
+        author: Set(record.author.clone()),
+        author_key_id: Set(author_key_id.to_string()),
+        created_at: Set(record.created_at),
+        updated_at: Set(record.updated_at),
+        deleted_at: Set(None),
+    };
+
+    Entity::insert(model).exec(db).await?;
+
+    Ok(())
+}
+
+/// Find a triage record by ID (non-deleted).
+pub async fn find_by_id(db: &DatabaseConnection, id: Uuid) -> AppResult<Option<TriageRecord>> {
+    let result = Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
+
+    Ok(result.map(model_to_record))
+}
+
+/// List triage records (non-deleted), newest first.
+pub async fn list(
+    db: &DatabaseConnection,
+    query: &TriageListQuery,
+) -> AppResult<Vec<TriageRecord>> {
+    let mut select = Entity::find().filter(Column::DeletedAt.is_null());
+
+    if let Some(test_case_id) = query.test_case_id {
+        select = select.filter(Column::TestCaseId.eq(test_case_id));
+    }
+    if let Some(ref signature) = query.failure_signature {
+        select = select.filter(Column::FailureSignature.eq(signature.as_str()));
+    }
+    if let Some(ref repository) = query.repository {
+        select = select.filter(Column::Repository.eq(repository.as_str()));
+    }
+    if let Some(classification) = query.classification {
+        select = select.filter(Column::Classification.eq(classification.as_str()));
+    }
+
+    let results = select.order_by_desc(Column::UpdatedAt).all(db).await?;
+
+    Ok(results.into_iter().map(model_to_record).collect())
+}
+
+/// List records that may apply to the given test cases: those made for one of
+/// the cases directly, or for one of their failure signatures.
+pub async fn list_for_test_cases(
+    db: &DatabaseConnection,
+    test_case_ids: &[Uuid],
+    failure_signatures: &[String],
+) -> AppResult<Vec<TriageRecord>> {
+    if test_case_ids.is_empty() && failure_signatures.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let results = Entity::find()
+        .filter(Column::DeletedAt.is_null())
+        .filter(
+            Condition::any()
+                .add(Column::TestCaseId.is_in(test_case_ids.iter().copied()))
+                .add(Column::FailureSignature.is_in(failure_signatures.iter().cloned())),
+        )
+        .all(db)
+        .await?;
+
+    Ok(results.into_iter().map(model_to_record).collect())
+}
+
+/// Update a triage record's classification, notes and issue link.
+pub async fn update(
+    db: &DatabaseConnection,
+    id: Uuid,
+    classification: TriageClassification,
+    notes: Option<String>,
+    issue_url: Option<String>,
+) -> AppResult<Option<TriageRecord>> {
+    let model = Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
+
+    let Some(m) = model else {
+        return Ok(None);
+    };
+
+    let mut active: ActiveModel = m.into();
+    active.classification = Set(classification.as_str().to_string());
+    active.notes = Set(notes);
+    active.issue_url = Set(issue_url);
+    active.updated_at = Set(Utc::now());
+    let updated = active.update(db).await?;
+
+    Ok(Some(model_to_record(updated)))
+}
+
+/// Soft-delete a triage record.
+pub async fn delete(db: &DatabaseConnection, id: Uuid) -> AppResult<bool> {
+    let model = Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
+
+    if let Some(m) = model {
+        let mut active: ActiveModel = m.into();
+        active.deleted_at = Set(Some(Utc::now()));
+        active.update(db).await?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+fn model_to_record(m: Model) -> TriageRecord {
+    TriageRecord {
+        id: m.id,
+        test_case_id: m.test_case_id,
+        full_title: m.full_title,
+        failure_signature: m.failure_signature,
+        repository: m.repository,
+        classification: TriageClassification::parse(&m.classification)
+            .unwrap_or(TriageClassification::ProductBug),
+        notes: m.notes,
+        issue_url: m.issue_url,
+        author: m.author,
+        created_at: m.created_at,
+        updated_at: m.updated_at,
+    }
+}
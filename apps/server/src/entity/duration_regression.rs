@@ -0,0 +1,24 @@
+//! Duration regression entity: tests of a report slower than their baseline.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "duration_regressions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub report_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub test_case_id: Uuid,
+    pub full_title: String,
+    pub duration_ms: i32,
+    pub baseline_median_ms: i32,
+    pub baseline_p90_ms: i32,
+    pub sample_count: i32,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
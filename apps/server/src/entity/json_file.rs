@@ -4,8 +4,9 @@
 //! JSON files contain rich test data per framework (Cypress, Detox, Playwright).
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "json_files")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
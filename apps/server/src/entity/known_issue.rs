@@ -0,0 +1,27 @@
+//! Known issue entity for rules linking failures to tracked issues.
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "known_issues")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub issue_key: String,
+    pub issue_url: Option<String>,
+    pub description: Option<String>,
+    pub repository: Option<String>,
+    pub failure_signature: Option<String>,
+    pub error_pattern: Option<String>,
+    pub title_pattern: Option<String>,
+    pub enabled: bool,
+    pub created_by: Option<String>,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+    pub deleted_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,9 +1,14 @@
 //! SeaORM entity definitions for PostgreSQL database.
 
 pub mod api_key;
+pub mod duration_regression;
 pub mod github_oidc_policy;
 pub mod html_file;
 pub mod json_file;
+pub mod known_issue;
+pub mod ownership_mapping;
+pub mod pipeline_run;
+pub mod quarantined_test;
 pub mod refresh_token;
 pub mod report_oidc_claim;
 pub mod screenshot;
@@ -11,4 +16,5 @@ pub mod test_case;
 pub mod test_job;
 pub mod test_report;
 pub mod test_suite;
+pub mod triage_record;
 pub mod user;
@@ -0,0 +1,25 @@
+//! Ownership mapping entity for per-repository test owners.
+
+use sea_orm::entity::prelude::*;
+use serde_json::Value as JsonValue;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "ownership_mappings")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub repository: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub codeowners: Option<String>,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub title_rules: JsonValue,
+    pub updated_by: Option<String>,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+    pub deleted_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,27 @@
+//! Quarantined test entity for the flaky test registry.
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "quarantined_tests")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub repository: Option<String>,
+    pub framework: Option<String>,
+    pub fingerprint: Option<String>,
+    pub full_title: Option<String>,
+    pub title_pattern: Option<String>,
+    pub reason: String,
+    pub owner: String,
+    pub expires_at: Option<DateTimeUtc>,
+    pub created_by: Option<String>,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+    pub deleted_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,8 +1,9 @@
 //! Report OIDC claims entity — stores non-sensitive token claims 1:1 with reports.
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "report_oidc_claims")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
@@ -3,8 +3,9 @@
 //! Tracks screenshots with upload status for request-then-transfer pattern.
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "screenshots")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
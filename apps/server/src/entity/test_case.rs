@@ -1,9 +1,10 @@
 //! TestCase entity for SeaORM.
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "test_cases")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
@@ -16,6 +17,14 @@ pub struct Model {
     pub duration_ms: i32,
     pub retry_count: i32,
     pub error_message: Option<String>,
+    /// Hash of the normalized error message (failed cases only).
+    pub failure_signature: Option<String>,
+    /// Whether the test matched the quarantine list at extraction time.
+    pub quarantined: bool,
+    /// Owner resolved from the repository's ownership mapping.
+    pub owner: Option<String>,
+    /// Key of the known issue a failure matched at extraction time.
+    pub known_issue_key: Option<String>,
     pub sequence: i32,
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub attachments: Option<JsonValue>,
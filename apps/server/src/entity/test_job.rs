@@ -1,9 +1,10 @@
 //! Job entity for SeaORM.
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "test_jobs")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
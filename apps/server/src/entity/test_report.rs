@@ -1,18 +1,33 @@
 //! Report entity for SeaORM.
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "test_reports")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: Uuid,
     pub expected_jobs: i32,
+    /// Open-ended: jobs raise `expected_jobs` until finalized or idle.
+    pub is_open: bool,
     pub framework: String,
     pub status: String,
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub github_metadata: Option<JsonValue>,
+    /// Flat string key/value labels (e.g. `{"db": "mysql"}`).
+    #[sea_orm(column_type = "JsonBinary")]
+    pub labels: JsonValue,
+    /// Free-form caller-supplied metadata.
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub custom_metadata: Option<JsonValue>,
+    /// Expected jobs that never finished, set when the report closed early.
+    pub missing_jobs: Option<i32>,
+    /// Hashed registration idempotency key, unique among live reports.
+    pub idempotency_key: Option<String>,
+    /// Pipeline run grouping this report with others from the same CI run.
+    pub pipeline_id: Option<Uuid>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
     pub deleted_at: Option<DateTimeUtc>,
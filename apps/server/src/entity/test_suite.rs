@@ -1,8 +1,9 @@
 //! TestSuite entity for SeaORM.
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "test_suites")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
@@ -15,6 +16,10 @@ pub struct Model {
     pub failed_count: i32,
     pub skipped_count: i32,
     pub flaky_count: i32,
+    /// Failures of quarantined tests (not included in failed_count).
+    pub quarantined_count: i32,
+    /// Failures matching a known issue (a subset of failed_count).
+    pub known_issue_count: i32,
     pub duration_ms: i32,
     /// Actual test execution start time from framework JSON.
     pub start_time: Option<DateTimeUtc>,
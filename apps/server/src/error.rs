@@ -24,6 +24,10 @@ pub enum AppError {
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    /// Request conflicts with the resource's current state
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     /// Storage (S3) operation failed
     #[error("Storage error: {0}")]
     Storage(String),
@@ -55,6 +59,11 @@ impl ResponseError for AppError {
                 "UNAUTHORIZED",
                 self.to_string(),
             ),
+            AppError::Conflict(_) => (
+                actix_web::http::StatusCode::CONFLICT,
+                "CONFLICT",
+                self.to_string(),
+            ),
             AppError::Storage(err_str) => {
                 // Log the full S3 error server-side but return a generic message to
                 // the client to avoid leaking bucket names, endpoints, or S3 error codes.
@@ -121,6 +121,20 @@ async fn main() -> std::io::Result<()> {
     let event_broadcaster = EventBroadcaster::new();
     info!("Event broadcaster initialized for WebSocket connections");
 
+    // Time out stalled uploads in the background
+    tokio::spawn(services::upload_sweeper::run(
+        pool.clone(),
+        event_broadcaster.clone(),
+        config.features.clone(),
+    ));
+
+    // Expire old reports and artifacts in the background
+    tokio::spawn(services::retention::run(
+        pool.clone(),
+        storage.clone(),
+        config.retention.clone(),
+    ));
+
     // Initialize GitHub OIDC verifier (if enabled)
     let oidc_verifier = if config.github_oidc.enabled {
         let verifier = GitHubOidcVerifier::new(&config.github_oidc);
@@ -180,12 +194,13 @@ async fn main() -> std::io::Result<()> {
         //   actix-cors does NOT fall back to reflecting the Origin header when
         //   allowed_origin_fn / allowed_origin are used — unlisted origins receive
         //   no ACAO header, which browsers treat as a CORS denial.
-        let allowed_methods = vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"];
+        let allowed_methods = vec!["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
         let allowed_headers = vec![
             header::AUTHORIZATION,
             header::ACCEPT,
             header::CONTENT_TYPE,
             "X-API-Key".parse().unwrap(),
+            "Idempotency-Key".parse().unwrap(),
         ];
 
         let cors = if is_development {
@@ -237,9 +252,26 @@ async fn main() -> std::io::Result<()> {
             .service(
                 web::scope("/api/v1")
                     .configure(api::configure_health_routes)
+                    // Before report routes, which would take /reports/import for a report ID
+                    .configure(api::configure_report_bundle_routes)
                     .configure(api::configure_report_routes)
                     .configure(api::configure_job_routes)
                     .configure(api::configure_test_results_routes)
+                    .configure(api::configure_failure_group_routes)
+                    .configure(api::configure_analytics_routes)
+                    .configure(api::configure_quarantine_routes)
+                    .configure(api::configure_ownership_routes)
+                    .configure(api::configure_triage_routes)
+                    .configure(api::configure_known_issue_routes)
+                    .configure(api::configure_sharding_routes)
+                    .configure(api::configure_rerun_routes)
+                    .configure(api::configure_results_export_routes)
+                    .configure(api::configure_run_routes)
+                    .configure(api::configure_pipeline_routes)
+                    .configure(api::configure_release_routes)
+                    .configure(api::configure_search_routes)
+                    .configure(api::configure_matrix_routes)
+                    .configure(api::configure_retention_routes)
                     .configure(api::configure_websocket_routes)
                     .configure(services::configure_auth_routes)
                     .configure(services::configure_oauth_routes)
@@ -0,0 +1,45 @@
+//! Migration: Add failure_signature to test_cases.
+//!
+//! Stores a hash of the normalized error message so failures with the same
+//! root cause can be clustered within and across reports.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE test_cases
+                    ADD COLUMN failure_signature VARCHAR(64);
+
+                -- Grouping failures by signature (failed cases only)
+                CREATE INDEX idx_test_cases_failure_signature
+                    ON test_cases(failure_signature, created_at)
+                    WHERE deleted_at IS NULL AND failure_signature IS NOT NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_test_cases_failure_signature;
+                ALTER TABLE test_cases DROP COLUMN IF EXISTS failure_signature;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+//! Migration: Create report_rollups table.
+//!
+//! One row of precomputed test totals per report, refreshed at extraction
+//! time, so trend queries over long ranges avoid scanning test_suites.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE report_rollups (
+                    report_id UUID PRIMARY KEY REFERENCES test_reports(id) ON DELETE CASCADE,
+
+                    -- Denormalized report dimensions used for trend filtering
+                    report_created_at TIMESTAMPTZ NOT NULL,
+                    framework VARCHAR(20) NOT NULL,
+                    repository VARCHAR(255),
+                    workflow VARCHAR(255),
+                    git_ref VARCHAR(255),
+
+                    -- Test totals (unique specs, last attempt wins)
+                    total_count INTEGER NOT NULL DEFAULT 0,
+                    passed_count INTEGER NOT NULL DEFAULT 0,
+                    failed_count INTEGER NOT NULL DEFAULT 0,
+                    skipped_count INTEGER NOT NULL DEFAULT 0,
+                    flaky_count INTEGER NOT NULL DEFAULT 0,
+
+                    -- Summed job duration and parallel wall-clock time
+                    duration_ms BIGINT NOT NULL DEFAULT 0,
+                    wall_clock_ms BIGINT,
+
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                );
+
+                -- Trend queries scoped to a repository
+                CREATE INDEX idx_report_rollups_repository_created
+                    ON report_rollups(repository, report_created_at);
+
+                -- Trend queries across all repositories
+                CREATE INDEX idx_report_rollups_created
+                    ON report_rollups(report_created_at);
+
+                -- Trigger to update updated_at
+                CREATE TRIGGER update_report_rollups_updated_at
+                    BEFORE UPDATE ON report_rollups
+                    FOR EACH ROW
+                    EXECUTE FUNCTION update_updated_at_column();
+
+                -- Backfill existing reports
+                INSERT INTO report_rollups (
+                    report_id, report_created_at, framework, repository, workflow, git_ref,
+                    total_count, passed_count, failed_count, skipped_count, flaky_count,
+                    duration_ms, wall_clock_ms
+                )
+                SELECT
+                    r.id,
+                    r.created_at,
+                    r.framework,
+                    r.github_metadata->>'repository',
+                    r.github_metadata->>'workflow',
+                    r.github_metadata->>'ref',
+                    COALESCE(s.total, 0),
+                    COALESCE(s.passed, 0),
+                    COALESCE(s.failed, 0),
+                    COALESCE(s.skipped, 0),
+                    COALESCE(s.flaky, 0),
+                    COALESCE(jb.duration_ms, 0),
+                    jb.wall_clock_ms
+                FROM test_reports r
+                LEFT JOIN LATERAL (
+                    SELECT
+                        SUM(ts.total_count)::INTEGER as total,
+                        SUM(ts.passed_count)::INTEGER as passed,
+                        SUM(ts.failed_count)::INTEGER as failed,
+                        SUM(ts.skipped_count)::INTEGER as skipped,
+                        SUM(ts.flaky_count)::INTEGER as flaky
+                    FROM test_jobs j
+                    INNER JOIN test_suites ts ON ts.test_job_id = j.id
+                    WHERE j.test_report_id = r.id
+                ) s ON TRUE
+                LEFT JOIN LATERAL (
+                    SELECT
+                        SUM(j.duration_ms)::BIGINT as duration_ms,
+                        (EXTRACT(EPOCH FROM (
+                            MAX(j.start_time + (j.duration_ms || ' milliseconds')::interval) - MIN(j.start_time)
+                        )) * 1000)::BIGINT as wall_clock_ms
+                    FROM test_jobs j
+                    WHERE j.test_report_id = r.id
+                ) jb ON TRUE
+                WHERE r.deleted_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TABLE IF EXISTS report_rollups CASCADE;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+//! Migration: Create duration_regressions table.
+//!
+//! Stores tests flagged as slower than their historical baseline when a
+//! report completes, plus an index for per-test duration history lookups.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE duration_regressions (
+                    report_id UUID NOT NULL REFERENCES test_reports(id) ON DELETE CASCADE,
+                    test_case_id UUID NOT NULL REFERENCES test_cases(id) ON DELETE CASCADE,
+
+                    full_title VARCHAR(1000) NOT NULL,
+                    duration_ms INTEGER NOT NULL,
+
+                    -- Baseline over recent successful runs of the same test
+                    baseline_median_ms INTEGER NOT NULL,
+                    baseline_p90_ms INTEGER NOT NULL,
+                    sample_count INTEGER NOT NULL,
+
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+                    PRIMARY KEY (report_id, test_case_id)
+                );
+
+                -- Duration history per test (successful runs only)
+                CREATE INDEX idx_test_cases_passed_history
+                    ON test_cases(full_title, created_at DESC)
+                    WHERE deleted_at IS NULL AND status = 'passed';
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_test_cases_passed_history;
+                DROP TABLE IF EXISTS duration_regressions CASCADE;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+//! Migration: Create quarantined_tests table.
+//!
+//! Registry of known-flaky tests whose failures are counted separately from
+//! regular failures. Entries match a test by fingerprint (hash of its title
+//! without the Playwright `[project] ` prefix) or by a title pattern,
+//! optionally scoped to a repository/framework.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE quarantined_tests (
+                    id UUID PRIMARY KEY,
+
+                    -- Optional scope (NULL matches any)
+                    repository VARCHAR(255),
+                    framework VARCHAR(20),
+
+                    -- Test matcher: exact fingerprint or title pattern
+                    fingerprint VARCHAR(64),
+                    full_title VARCHAR(1000),
+                    title_pattern VARCHAR(1000),
+
+                    reason VARCHAR(1000) NOT NULL,
+                    owner VARCHAR(255) NOT NULL,
+                    expires_at TIMESTAMPTZ,
+                    created_by VARCHAR(255),
+
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    deleted_at TIMESTAMPTZ,
+
+                    CHECK (fingerprint IS NOT NULL OR title_pattern IS NOT NULL)
+                );
+
+                CREATE INDEX idx_quarantined_tests_scope
+                    ON quarantined_tests(repository, framework)
+                    WHERE deleted_at IS NULL;
+
+                CREATE TRIGGER update_quarantined_tests_updated_at
+                    BEFORE UPDATE ON quarantined_tests
+                    FOR EACH ROW
+                    EXECUTE FUNCTION update_updated_at_column();
+
+                -- Quarantined failures are tracked apart from failed_count
+                ALTER TABLE test_cases
+                    ADD COLUMN quarantined BOOLEAN NOT NULL DEFAULT FALSE;
+
+                ALTER TABLE test_suites
+                    ADD COLUMN quarantined_count INTEGER NOT NULL DEFAULT 0;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE test_suites DROP COLUMN IF EXISTS quarantined_count;
+                ALTER TABLE test_cases DROP COLUMN IF EXISTS quarantined;
+                DROP TRIGGER IF EXISTS update_quarantined_tests_updated_at ON quarantined_tests;
+                DROP TABLE IF EXISTS quarantined_tests CASCADE;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
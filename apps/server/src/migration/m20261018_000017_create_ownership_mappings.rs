@@ -0,0 +1,74 @@
+//! Migration: Create ownership_mappings table.
+//!
+//! Stores a per-repository test ownership mapping (CODEOWNERS text over suite
+//! file paths and explicit title rules) and the owner resolved for each test
+//! case at extraction time.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE ownership_mappings (
+                    id UUID PRIMARY KEY,
+                    repository VARCHAR(255) NOT NULL,
+
+                    -- CODEOWNERS syntax matched against test_suites.file_path
+                    codeowners TEXT,
+                    -- Ordered [{pattern, owner}] rules matched against full titles
+                    title_rules JSONB NOT NULL DEFAULT '[]',
+
+                    updated_by VARCHAR(255),
+
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    deleted_at TIMESTAMPTZ
+                );
+
+                -- One active mapping per repository
+                CREATE UNIQUE INDEX idx_ownership_mappings_repository_active
+                    ON ownership_mappings(repository)
+                    WHERE deleted_at IS NULL;
+
+                CREATE TRIGGER update_ownership_mappings_updated_at
+                    BEFORE UPDATE ON ownership_mappings
+                    FOR EACH ROW
+                    EXECUTE FUNCTION update_updated_at_column();
+
+                ALTER TABLE test_cases
+                    ADD COLUMN owner VARCHAR(255);
+
+                -- Owner filters within a job/report
+                CREATE INDEX idx_test_cases_owner
+                    ON test_cases(owner, test_job_id)
+                    WHERE deleted_at IS NULL AND owner IS NOT NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_test_cases_owner;
+                ALTER TABLE test_cases DROP COLUMN IF EXISTS owner;
+                DROP TRIGGER IF EXISTS update_ownership_mappings_updated_at ON ownership_mappings;
+                DROP TABLE IF EXISTS ownership_mappings CASCADE;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+//! Migration: Create triage_records table.
+//!
+//! Records what was learned about a failure: a classification, notes and an
+//! optional issue link. A record is attached to a test case, a failure
+//! signature, or both, and carries forward to later failures of the same test
+//! with the same signature.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE triage_records (
+                    id UUID PRIMARY KEY,
+
+                    -- Target: the test case triaged and/or its failure signature
+                    test_case_id UUID REFERENCES test_cases(id) ON DELETE SET NULL,
+                    full_title VARCHAR(1000),
+                    failure_signature VARCHAR(64),
+                    repository VARCHAR(255),
+
+                    classification VARCHAR(20) NOT NULL
+                        CHECK (classification IN ('product_bug', 'test_bug', 'infra', 'flaky', 'known_issue')),
+                    notes TEXT,
+                    issue_url VARCHAR(2048),
+
+                    author VARCHAR(255) NOT NULL,
+                    author_key_id VARCHAR(255) NOT NULL,
+
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    deleted_at TIMESTAMPTZ,
+
+                    CHECK (test_case_id IS NOT NULL OR failure_signature IS NOT NULL)
+                );
+
+                CREATE INDEX idx_triage_records_test_case
+                    ON triage_records(test_case_id)
+                    WHERE deleted_at IS NULL;
+
+                CREATE INDEX idx_triage_records_signature
+                    ON triage_records(failure_signature, full_title)
+                    WHERE deleted_at IS NULL;
+
+                CREATE TRIGGER update_triage_records_updated_at
+                    BEFORE UPDATE ON triage_records
+                    FOR EACH ROW
+                    EXECUTE FUNCTION update_updated_at_column();
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TRIGGER IF EXISTS update_triage_records_updated_at ON triage_records;
+                DROP TABLE IF EXISTS triage_records CASCADE;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+//! Migration: Create known_issues table.
+//!
+//! Rules linking failures to tracked issues (e.g., Jira keys). A rule matches
+//! on a failure signature, an error message regex and/or a test title
+//! pattern; every matcher that is set must match. Failed test cases are tagged
+//! with the issue key at extraction time.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE known_issues (
+                    id UUID PRIMARY KEY,
+
+                    issue_key VARCHAR(100) NOT NULL,
+                    issue_url VARCHAR(2048),
+                    description VARCHAR(1000),
+
+                    -- Optional scope (NULL matches any)
+                    repository VARCHAR(255),
+
+                    -- Matchers: all that are set must match
+                    failure_signature VARCHAR(64),
+                    error_pattern VARCHAR(1000),
+                    title_pattern VARCHAR(1000),
+
+                    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+                    created_by VARCHAR(255),
+
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    deleted_at TIMESTAMPTZ,
+
+                    CHECK (failure_signature IS NOT NULL OR error_pattern IS NOT NULL OR title_pattern IS NOT NULL)
+                );
+
+                CREATE INDEX idx_known_issues_repository
+                    ON known_issues(repository)
+                    WHERE deleted_at IS NULL;
+
+                CREATE TRIGGER update_known_issues_updated_at
+                    BEFORE UPDATE ON known_issues
+                    FOR EACH ROW
+                    EXECUTE FUNCTION update_updated_at_column();
+
+                -- Failed tests matching a known issue at extraction time
+                ALTER TABLE test_cases
+                    ADD COLUMN known_issue_key VARCHAR(100);
+
+                ALTER TABLE test_suites
+                    ADD COLUMN known_issue_count INTEGER NOT NULL DEFAULT 0;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE test_suites DROP COLUMN IF EXISTS known_issue_count;
+                ALTER TABLE test_cases DROP COLUMN IF EXISTS known_issue_key;
+                DROP TRIGGER IF EXISTS update_known_issues_updated_at ON known_issues;
+                DROP TABLE IF EXISTS known_issues CASCADE;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+//! Migration: Index reports by GitHub workflow run.
+//!
+//! Re-running a workflow registers a new report per attempt; this index
+//! groups them into a run lineage by repository and run ID.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE INDEX idx_test_reports_github_run
+                    ON test_reports(
+                        (github_metadata->>'repository'),
+                        (github_metadata->>'run_id')
+                    )
+                    WHERE deleted_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS idx_test_reports_github_run;")
+            .await?;
+
+        Ok(())
+    }
+}
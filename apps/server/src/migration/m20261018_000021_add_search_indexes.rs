@@ -0,0 +1,51 @@
+//! Migration: Indexes for global test search.
+//!
+//! Full-text search over test titles and error messages uses a weighted
+//! expression index; fuzzy matching on suite file paths uses trigrams. The
+//! expressions must match the ones used by `DbPool::search_test_cases`.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+                -- Titles rank above error messages
+                CREATE INDEX idx_test_cases_search_vector ON test_cases USING GIN ((
+                    setweight(to_tsvector('simple', full_title), 'A') ||
+                    setweight(to_tsvector('simple', COALESCE(error_message, '')), 'B')
+                ))
+                    WHERE deleted_at IS NULL;
+
+                CREATE INDEX idx_test_suites_file_path_trgm
+                    ON test_suites USING GIN (file_path gin_trgm_ops)
+                    WHERE deleted_at IS NULL AND file_path IS NOT NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_test_suites_file_path_trgm;
+                DROP INDEX IF EXISTS idx_test_cases_search_vector;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+//! Migration: Expression indexes for report list filters.
+//!
+//! The GIN index on `github_metadata` only serves containment queries, so the
+//! `->>` comparisons used by `DbPool::list_reports` and release readiness
+//! get B-tree expression indexes. The SHA and ref indexes use
+//! `text_pattern_ops` for prefix matching of abbreviated SHAs and release
+//! branches.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE INDEX idx_test_reports_github_sha
+                    ON test_reports((github_metadata->>'sha') text_pattern_ops)
+                    WHERE deleted_at IS NULL;
+
+                CREATE INDEX idx_test_reports_github_ref
+                    ON test_reports((github_metadata->>'ref') text_pattern_ops)
+                    WHERE deleted_at IS NULL;
+
+                CREATE INDEX idx_test_reports_github_pr_number
+                    ON test_reports((github_metadata->>'pr_number'), created_at DESC)
+                    WHERE deleted_at IS NULL;
+
+                CREATE INDEX idx_test_reports_github_actor
+                    ON test_reports((github_metadata->>'actor'), created_at DESC)
+                    WHERE deleted_at IS NULL;
+
+                CREATE INDEX idx_test_reports_github_workflow
+                    ON test_reports((github_metadata->>'workflow'), created_at DESC)
+                    WHERE deleted_at IS NULL;
+
+                CREATE INDEX idx_test_reports_github_event_name
+                    ON test_reports((github_metadata->>'event_name'), created_at DESC)
+                    WHERE deleted_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_test_reports_github_event_name;
+                DROP INDEX IF EXISTS idx_test_reports_github_workflow;
+                DROP INDEX IF EXISTS idx_test_reports_github_actor;
+                DROP INDEX IF EXISTS idx_test_reports_github_pr_number;
+                DROP INDEX IF EXISTS idx_test_reports_github_ref;
+                DROP INDEX IF EXISTS idx_test_reports_github_sha;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+//! Migration: Add labels and custom metadata to test_reports.
+//!
+//! Labels are flat string key/value pairs describing the run environment
+//! (server version, edition, database, feature flags) and are filtered by
+//! containment. Custom metadata is a free-form object that is stored but not
+//! indexed.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE test_reports
+                    ADD COLUMN labels JSONB NOT NULL DEFAULT '{}'::jsonb,
+                    ADD COLUMN custom_metadata JSONB;
+
+                -- Label filters (labels @> '{"db": "mysql"}')
+                CREATE INDEX idx_test_reports_labels
+                    ON test_reports USING GIN (labels jsonb_path_ops)
+                    WHERE deleted_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_test_reports_labels;
+                ALTER TABLE test_reports
+                    DROP COLUMN IF EXISTS custom_metadata,
+                    DROP COLUMN IF EXISTS labels;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+//! Migration: Index job environments for filtering.
+//!
+//! Job queries filter by OS, browser, device and tag with a single
+//! containment check (`environment @> '{"os": "linux"}'`).
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE INDEX idx_test_jobs_environment
+                    ON test_jobs USING GIN (environment jsonb_path_ops)
+                    WHERE deleted_at IS NULL AND environment IS NOT NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS idx_test_jobs_environment;")
+            .await?;
+
+        Ok(())
+    }
+}
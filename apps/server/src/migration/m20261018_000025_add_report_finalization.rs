@@ -0,0 +1,46 @@
+//! Migration: Allow finalizing and cancelling reports.
+//!
+//! Reports can be closed before every expected job ran. `missing_jobs`
+//! records how many expected jobs never finished, and `cancelled` is a new
+//! terminal status.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE test_reports DROP CONSTRAINT IF EXISTS test_reports_status_check;
+                ALTER TABLE test_reports ADD CONSTRAINT test_reports_status_check
+                    CHECK (status IN ('initializing', 'uploading', 'processing', 'complete', 'failed', 'cancelled'));
+                ALTER TABLE test_reports ADD COLUMN missing_jobs INTEGER;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE test_reports SET status = 'failed' WHERE status = 'cancelled';
+                ALTER TABLE test_reports DROP COLUMN IF EXISTS missing_jobs;
+                ALTER TABLE test_reports DROP CONSTRAINT IF EXISTS test_reports_status_check;
+                ALTER TABLE test_reports ADD CONSTRAINT test_reports_status_check
+                    CHECK (status IN ('initializing', 'uploading', 'processing', 'complete', 'failed'));
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
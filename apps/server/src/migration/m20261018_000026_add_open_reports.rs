@@ -0,0 +1,47 @@
+//! Migration: Open-ended reports.
+//!
+//! Reports registered without `expected_jobs` stay open: each new job raises
+//! `expected_jobs`, and the report closes on finalize or after an idle
+//! timeout instead of when the count is reached. An open report starts with
+//! no jobs, so `expected_jobs` may now be 0.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE test_reports ADD COLUMN is_open BOOLEAN NOT NULL DEFAULT FALSE;
+                ALTER TABLE test_reports DROP CONSTRAINT IF EXISTS test_reports_expected_jobs_check;
+                ALTER TABLE test_reports ADD CONSTRAINT test_reports_expected_jobs_check
+                    CHECK (expected_jobs >= 0 AND expected_jobs <= 100);
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE test_reports SET expected_jobs = 1 WHERE expected_jobs = 0;
+                ALTER TABLE test_reports DROP CONSTRAINT IF EXISTS test_reports_expected_jobs_check;
+                ALTER TABLE test_reports ADD CONSTRAINT test_reports_expected_jobs_check
+                    CHECK (expected_jobs >= 1 AND expected_jobs <= 100);
+                ALTER TABLE test_reports DROP COLUMN IF EXISTS is_open;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
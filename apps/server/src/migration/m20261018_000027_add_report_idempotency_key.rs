@@ -0,0 +1,43 @@
+//! Migration: Idempotent report registration.
+//!
+//! Registrations carry a key derived from the workflow run or sent as an
+//! `Idempotency-Key` header. Only one live report may hold a key, so retried
+//! registrations return the existing report instead of creating a duplicate.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE test_reports ADD COLUMN idempotency_key TEXT;
+                CREATE UNIQUE INDEX idx_test_reports_idempotency_key
+                    ON test_reports (idempotency_key)
+                    WHERE idempotency_key IS NOT NULL AND deleted_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_test_reports_idempotency_key;
+                ALTER TABLE test_reports DROP COLUMN IF EXISTS idempotency_key;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Migration: Create pipeline_runs table.
+//!
+//! A pipeline run groups the reports of one CI run across frameworks (e.g.
+//! Playwright, Cypress and Detox reports of the same workflow run). Reports
+//! join the pipeline matching their repository, SHA and run ID, or an explicit
+//! group key sent on registration. Reports registered before this migration
+//! don't belong to a pipeline.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE pipeline_runs (
+                    id UUID PRIMARY KEY,
+
+                    -- Derived from the run identity or the explicit group key
+                    pipeline_key TEXT NOT NULL UNIQUE,
+                    group_key VARCHAR(255),
+
+                    -- Run identity of the first member report
+                    repository VARCHAR(255),
+                    git_ref VARCHAR(255),
+                    sha VARCHAR(64),
+                    run_id VARCHAR(64),
+
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                );
+
+                CREATE INDEX idx_pipeline_runs_repository_created
+                    ON pipeline_runs(repository, created_at);
+
+                CREATE INDEX idx_pipeline_runs_created
+                    ON pipeline_runs(created_at);
+
+                CREATE TRIGGER update_pipeline_runs_updated_at
+                    BEFORE UPDATE ON pipeline_runs
+                    FOR EACH ROW
+                    EXECUTE FUNCTION update_updated_at_column();
+
+                ALTER TABLE test_reports
+                    ADD COLUMN pipeline_id UUID REFERENCES pipeline_runs(id) ON DELETE SET NULL;
+
+                CREATE INDEX idx_test_reports_pipeline_id
+                    ON test_reports(pipeline_id)
+                    WHERE pipeline_id IS NOT NULL AND deleted_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_test_reports_pipeline_id;
+                ALTER TABLE test_reports DROP COLUMN IF EXISTS pipeline_id;
+                DROP TRIGGER IF EXISTS update_pipeline_runs_updated_at ON pipeline_runs;
+                DROP TABLE IF EXISTS pipeline_runs CASCADE;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -20,6 +20,22 @@ mod m20260129_000009_create_github_oidc_policies;
 mod m20260129_000010_create_users;
 mod m20260129_000011_create_refresh_tokens;
 mod m20260226_000012_create_report_oidc_claims;
+mod m20261018_000013_add_test_case_failure_signature;
+mod m20261018_000014_create_report_rollups;
+mod m20261018_000015_create_duration_regressions;
+mod m20261018_000016_create_quarantined_tests;
+mod m20261018_000017_create_ownership_mappings;
+mod m20261018_000018_create_triage_records;
+mod m20261018_000019_create_known_issues;
+mod m20261018_000020_add_report_run_index;
+mod m20261018_000021_add_search_indexes;
+mod m20261018_000022_add_report_filter_indexes;
+mod m20261018_000023_add_report_labels;
+mod m20261018_000024_add_job_environment_index;
+mod m20261018_000025_add_report_finalization;
+mod m20261018_000026_add_open_reports;
+mod m20261018_000027_add_report_idempotency_key;
+mod m20261018_000028_create_pipeline_runs;
 
 pub struct Migrator;
 
@@ -39,6 +55,22 @@ impl MigratorTrait for Migrator {
             Box::new(m20260129_000010_create_users::Migration),
             Box::new(m20260129_000011_create_refresh_tokens::Migration),
             Box::new(m20260226_000012_create_report_oidc_claims::Migration),
+            Box::new(m20261018_000013_add_test_case_failure_signature::Migration),
+            Box::new(m20261018_000014_create_report_rollups::Migration),
+            Box::new(m20261018_000015_create_duration_regressions::Migration),
+            Box::new(m20261018_000016_create_quarantined_tests::Migration),
+            Box::new(m20261018_000017_create_ownership_mappings::Migration),
+            Box::new(m20261018_000018_create_triage_records::Migration),
+            Box::new(m20261018_000019_create_known_issues::Migration),
+            Box::new(m20261018_000020_add_report_run_index::Migration),
+            Box::new(m20261018_000021_add_search_indexes::Migration),
+            Box::new(m20261018_000022_add_report_filter_indexes::Migration),
+            Box::new(m20261018_000023_add_report_labels::Migration),
+            Box::new(m20261018_000024_add_job_environment_index::Migration),
+            Box::new(m20261018_000025_add_report_finalization::Migration),
+            Box::new(m20261018_000026_add_open_reports::Migration),
+            Box::new(m20261018_000027_add_report_idempotency_key::Migration),
+            Box::new(m20261018_000028_create_pipeline_runs::Migration),
         ]
     }
 }
@@ -0,0 +1,165 @@
+//! Analytics models and DTOs (trends and test durations).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::Framework;
+
+/// Time bucket granularity for time-series endpoints.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeBucket {
+    #[default]
+    Day,
+    Week,
+}
+
+impl TimeBucket {
+    /// Postgres `date_trunc` field name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+        }
+    }
+}
+
+impl std::fmt::Display for TimeBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Query parameters for the trends endpoint.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct TrendsQuery {
+    /// Filter by GitHub repository (e.g., "org/repo").
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Filter by workflow name.
+    #[serde(default)]
+    pub workflow: Option<String>,
+    /// Filter by git ref (e.g., "refs/heads/main").
+    #[serde(rename = "ref", default)]
+    pub git_ref: Option<String>,
+    /// Filter by framework.
+    #[serde(default)]
+    pub framework: Option<Framework>,
+    /// Only reports carrying all these labels (`key:value[,key:value...]`).
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Time-series granularity (default: day).
+    #[serde(default)]
+    pub bucket: TimeBucket,
+    /// Only include reports created at or after this time.
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// Only include reports created at or before this time.
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Aggregated test results for one time bucket.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TrendPoint {
+    /// Start of the bucket (truncated to day or week).
+    pub bucket_start: DateTime<Utc>,
+    /// Number of reports in the bucket.
+    pub report_count: i64,
+    pub total: i64,
+    pub passed: i64,
+    pub failed: i64,
+    pub skipped: i64,
+    pub flaky: i64,
+    /// passed / total (0.0 when there are no tests).
+    pub pass_rate: f64,
+    /// failed / total (0.0 when there are no tests).
+    pub fail_rate: f64,
+    /// flaky / total (0.0 when there are no tests).
+    pub flaky_rate: f64,
+    /// Summed test duration across all jobs in milliseconds.
+    pub duration_ms: i64,
+    /// Summed per-report wall-clock duration in milliseconds.
+    pub wall_clock_ms: i64,
+}
+
+/// Response for the trends endpoint.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TrendsResponse {
+    pub bucket: TimeBucket,
+    /// Buckets ordered oldest first; empty buckets are omitted.
+    pub points: Vec<TrendPoint>,
+}
+
+/// A test flagged as slower than its historical baseline.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DurationRegression {
+    pub test_case_id: Uuid,
+    pub full_title: String,
+    /// Duration in this report.
+    pub duration_ms: i32,
+    /// Median duration over recent successful runs.
+    pub baseline_median_ms: i32,
+    /// 90th percentile duration over recent successful runs.
+    pub baseline_p90_ms: i32,
+    /// Number of runs in the baseline.
+    pub sample_count: i32,
+    /// Slowdown relative to the baseline median, in percent.
+    pub slowdown_pct: f64,
+}
+
+/// Response for report slow tests.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SlowTestsResponse {
+    pub report_id: Uuid,
+    /// Configured regression threshold in percent.
+    pub threshold_pct: u32,
+    /// Regressions ordered by slowdown (largest first). Empty until the report completes.
+    pub regressions: Vec<DurationRegression>,
+}
+
+/// Query parameters for the slowest tests ranking.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct SlowestTestsQuery {
+    /// Filter by GitHub repository (e.g., "org/repo").
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Filter by framework.
+    #[serde(default)]
+    pub framework: Option<Framework>,
+    /// Only include runs recorded at or after this time (default: 30 days ago).
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// Only include runs recorded at or before this time.
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+    /// Maximum results to return (default 20, max 100).
+    #[serde(default = "default_slowest_limit")]
+    pub limit: i32,
+}
+
+fn default_slowest_limit() -> i32 {
+    20
+}
+
+/// A test ranked by its typical duration.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SlowestTest {
+    pub repository: Option<String>,
+    pub framework: Framework,
+    pub full_title: String,
+    /// Number of successful runs in the window.
+    pub run_count: i64,
+    pub median_ms: i64,
+    pub p90_ms: i64,
+    pub max_ms: i64,
+}
+
+/// Response for the slowest tests ranking.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SlowestTestsResponse {
+    /// Tests ordered by median duration (slowest first).
+    pub tests: Vec<SlowestTest>,
+}
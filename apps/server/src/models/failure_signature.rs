@@ -0,0 +1,113 @@
+//! Failure clustering models and DTOs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{Framework, TimeBucket};
+
+/// A failed test case belonging to a failure group.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FailureGroupTest {
+    pub test_case_id: Uuid,
+    pub suite_id: Uuid,
+    pub job_id: Uuid,
+    pub title: String,
+    pub full_title: String,
+    pub status: String,
+    pub retry_count: i32,
+}
+
+/// Failures within a report sharing the same normalized error signature.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FailureGroup {
+    /// Hash of the normalized error message.
+    pub signature: String,
+    /// Normalized error message the signature was computed from.
+    pub normalized_message: String,
+    /// Full error message (with stack) of the first failure in the group.
+    pub representative_error: Option<String>,
+    /// Number of failed attempts (retries count separately).
+    pub occurrences: i64,
+    /// Number of distinct tests (by full title) affected.
+    pub test_count: i64,
+    /// Affected test cases (capped, see `tests_truncated`).
+    pub tests: Vec<FailureGroupTest>,
+    /// True when more test cases matched than are listed in `tests`.
+    pub tests_truncated: bool,
+}
+
+/// Response for report failure groups.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FailureGroupsResponse {
+    pub report_id: Uuid,
+    /// Total failed attempts with a signature in the report.
+    pub total_failures: i64,
+    /// Groups ordered by occurrences (largest first).
+    pub groups: Vec<FailureGroup>,
+}
+
+/// Query parameters for cross-report failure signatures.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct FailureSignaturesQuery {
+    /// Filter by GitHub repository (e.g., "org/repo").
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Filter by git ref (e.g., "refs/heads/main").
+    #[serde(rename = "ref", default)]
+    pub git_ref: Option<String>,
+    /// Filter by framework.
+    #[serde(default)]
+    pub framework: Option<Framework>,
+    /// Only include failures recorded at or after this time.
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// Only include failures recorded at or before this time.
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+    /// Time-series granularity (default: day).
+    #[serde(default)]
+    pub bucket: TimeBucket,
+    /// Number of signatures to return (default 10, max 50).
+    #[serde(default = "default_signature_limit")]
+    pub limit: i32,
+}
+
+fn default_signature_limit() -> i32 {
+    10
+}
+
+/// Occurrences of a signature within one time bucket.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FailureSignatureBucket {
+    /// Start of the bucket (truncated to day or week).
+    pub bucket_start: DateTime<Utc>,
+    pub occurrences: i64,
+}
+
+/// A failure signature aggregated across reports.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FailureSignatureSummary {
+    pub signature: String,
+    /// Normalized error message the signature was computed from.
+    pub normalized_message: String,
+    /// Most recent full error message for this signature.
+    pub representative_error: Option<String>,
+    /// Total failed attempts across all matching reports.
+    pub occurrences: i64,
+    /// Number of distinct reports the signature appeared in.
+    pub report_count: i64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// Occurrences over time, oldest bucket first.
+    pub buckets: Vec<FailureSignatureBucket>,
+}
+
+/// Response for cross-report failure signatures.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FailureSignaturesResponse {
+    pub bucket: TimeBucket,
+    /// Signatures ordered by occurrences (most common first).
+    pub signatures: Vec<FailureSignatureSummary>,
+}
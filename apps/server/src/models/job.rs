@@ -351,6 +351,18 @@ pub struct QueryJobsParams {
     /// Filter by GitHub job name.
     #[serde(default)]
     pub github_job_name: Option<String>,
+    /// Filter by environment operating system.
+    #[serde(default)]
+    pub os: Option<String>,
+    /// Filter by environment browser.
+    #[serde(default)]
+    pub browser: Option<String>,
+    /// Filter by environment device.
+    #[serde(default)]
+    pub device: Option<String>,
+    /// Filter by environment tag.
+    #[serde(default)]
+    pub tag: Option<String>,
     /// Filter from date.
     #[serde(default)]
     pub from_date: Option<DateTime<Utc>>,
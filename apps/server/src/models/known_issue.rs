@@ -0,0 +1,90 @@
+//! Known issue models and DTOs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Rule linking matching failures to a tracked issue.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct KnownIssue {
+    pub id: Uuid,
+    /// Issue key (e.g., "MM-12345").
+    pub issue_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Repository the rule applies to; null applies everywhere.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// Failure signature the failure must have.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_signature: Option<String>,
+    /// Regular expression the error message must match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_pattern: Option<String>,
+    /// Title pattern (`*` wildcard) the full test title must match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_pattern: Option<String>,
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to register a known issue.
+///
+/// At least one of `failure_signature`, `error_pattern` or `title_pattern` is
+/// required; a failure matches when every matcher that is set matches.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateKnownIssueRequest {
+    pub issue_key: String,
+    #[serde(default)]
+    pub issue_url: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub repository: Option<String>,
+    #[serde(default)]
+    pub failure_signature: Option<String>,
+    #[serde(default)]
+    pub error_pattern: Option<String>,
+    #[serde(default)]
+    pub title_pattern: Option<String>,
+}
+
+/// Request to update a known issue. Omitted fields are left unchanged; an
+/// empty string clears an optional field.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateKnownIssueRequest {
+    #[serde(default)]
+    pub issue_key: Option<String>,
+    #[serde(default)]
+    pub issue_url: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub failure_signature: Option<String>,
+    #[serde(default)]
+    pub error_pattern: Option<String>,
+    #[serde(default)]
+    pub title_pattern: Option<String>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+/// Known issue list response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct KnownIssueListResponse {
+    pub known_issues: Vec<KnownIssue>,
+}
+
+/// Query parameters for listing known issues.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct KnownIssueListQuery {
+    /// Only rules that apply to this repository (unscoped rules included).
+    #[serde(default)]
+    pub repository: Option<String>,
+}
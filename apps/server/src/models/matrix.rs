@@ -0,0 +1,55 @@
+//! Environment matrix models and DTOs.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Query parameters for the environment matrix.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct MatrixQuery {
+    /// Only list tests that failed in at least one cell.
+    #[serde(default)]
+    pub failures_only: bool,
+}
+
+/// One combination of environment dimensions.
+///
+/// Dimensions a job did not report are null.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, ToSchema)]
+pub struct EnvironmentCell {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub browser: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    /// Playwright project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+}
+
+/// A test's status in each environment cell.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MatrixTest {
+    /// Test title without the Playwright project prefix.
+    pub title: String,
+    /// Final status per cell, aligned with `cells`; null where the test did
+    /// not run.
+    pub statuses: Vec<Option<String>>,
+    /// Number of cells in which the test failed.
+    pub failed_cells: i32,
+    /// Failed in exactly one cell and passed in at least one other.
+    pub single_cell_failure: bool,
+}
+
+/// Test statuses across a report's environment cells.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReportMatrixResponse {
+    pub report_id: Uuid,
+    /// Environment cells, sorted by browser, OS, project and device.
+    pub cells: Vec<EnvironmentCell>,
+    /// Tests sorted with single-cell failures first, then by title.
+    pub tests: Vec<MatrixTest>,
+    /// Number of tests failing in exactly one cell.
+    pub single_cell_failures: i32,
+}
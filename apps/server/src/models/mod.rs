@@ -1,18 +1,40 @@
 //! Domain models for Test System IO.
 
+pub mod analytics;
 pub mod api_key;
+pub mod failure_signature;
 pub mod github_oidc;
 pub mod job;
+pub mod known_issue;
+pub mod matrix;
+pub mod ownership;
+pub mod pipeline;
+pub mod quarantine;
+pub mod release;
 pub mod report;
 pub mod report_oidc_claim;
+pub mod rerun;
+pub mod retention;
+pub mod run_lineage;
+pub mod search;
+pub mod sharding;
+pub mod triage;
 pub mod user;
 pub mod ws_event;
 
 // Re-export commonly used types
+pub use analytics::{
+    DurationRegression, SlowTestsResponse, SlowestTest, SlowestTestsQuery, SlowestTestsResponse,
+    TimeBucket, TrendPoint, TrendsQuery, TrendsResponse,
+};
 pub use api_key::{
     ApiKey, ApiKeyCreateResponse, ApiKeyListItem, ApiKeyRole, AuthenticatedCaller,
     CreateApiKeyRequest, OIDC_ADMIN_DENIED_MSG,
 };
+pub use failure_signature::{
+    FailureGroup, FailureGroupTest, FailureGroupsResponse, FailureSignatureBucket,
+    FailureSignatureSummary, FailureSignaturesQuery, FailureSignaturesResponse,
+};
 pub use job::{
     AcceptedHtmlFile, AcceptedJsonFile, AcceptedScreenshot, EnvironmentMetadata, HtmlFileToUpload,
     HtmlUploadProgress, HtmlUploadResponse, InitHtmlRequest, InitHtmlResponse, InitJobRequest,
@@ -21,8 +43,43 @@ pub use job::{
     JobStatusResponse, JobSummary, JsonFileToUpload, JsonUploadProgress, JsonUploadResponse,
     QueryJobsParams, RejectedFile, ScreenshotToUpload, ScreenshotUploadResponse, UploadStatus,
 };
+pub use known_issue::{
+    CreateKnownIssueRequest, KnownIssue, KnownIssueListQuery, KnownIssueListResponse,
+    UpdateKnownIssueRequest,
+};
+pub use matrix::{EnvironmentCell, MatrixQuery, MatrixTest, ReportMatrixResponse};
+pub use ownership::{
+    OwnerFailureSummary, OwnerFilterQuery, OwnershipMapping, PutOwnershipRequest, TitleOwnerRule,
+};
+pub use pipeline::{
+    ListPipelinesQuery, PipelineDetailResponse, PipelineListResponse, PipelineReport,
+    PipelineStatus, PipelineSummary,
+};
+pub use quarantine::{
+    CreateQuarantineRequest, QuarantineEntry, QuarantineEntryResponse, QuarantineFormat,
+    QuarantineListQuery, QuarantineListResponse,
+};
+pub use release::{
+    KnownIssueFailure, MissingCombination, ReadinessCombination, ReadinessDecision, ReadinessQuery,
+    ReleaseReadinessResponse,
+};
 pub use report::{
-    Framework, GitHubMetadata, ListReportsQuery, RegisterReportRequest, RegisterReportResponse,
-    ReportDetailResponse, ReportListResponse, ReportStatus, ReportSummary, TestStats,
+    CloseReportResponse, Framework, GitHubMetadata, ImportReportResponse, ListReportsQuery,
+    MAX_EXPECTED_JOBS, RegisterReportRequest, RegisterReportResponse, ReportDetailResponse,
+    ReportListResponse, ReportMetadataResponse, ReportOutcome, ReportSort, ReportStatus,
+    ReportSummary, SortOrder, TestStats, UpdateReportMetadataRequest, UpdateReportRequest,
+    UpdateReportResponse, labels_from_json,
+};
+pub use rerun::{RerunFormat, RerunManifest, RerunQuery, RerunTest};
+pub use retention::{ReportClass, RetentionClassPreview, RetentionPreview};
+pub use run_lineage::{
+    ReportLineage, RunAttempt, RunLineageQuery, RunLineageResponse, RunSummary, RunTestResult,
+    TestAttemptStatus,
+};
+pub use search::{GlobalSearchQuery, GlobalSearchResponse, SearchHit};
+pub use sharding::{Shard, ShardFile, ShardPlanRequest, ShardPlanResponse};
+pub use triage::{
+    CreateTriageRequest, TriageClassification, TriageListQuery, TriageListResponse, TriageRecord,
+    TriageState, UpdateTriageRequest,
 };
 pub use ws_event::{WsEvent, WsEventMessage};
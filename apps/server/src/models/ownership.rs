@@ -0,0 +1,62 @@
+//! Test ownership models and DTOs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Title rule assigning an owner to tests whose full title matches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TitleOwnerRule {
+    /// Title pattern where `*` matches any sequence of characters.
+    pub pattern: String,
+    /// Owner (e.g., "@org/team").
+    pub owner: String,
+}
+
+/// Ownership mapping for a repository.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OwnershipMapping {
+    pub repository: String,
+    /// CODEOWNERS-syntax rules matched against suite file paths.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codeowners: Option<String>,
+    /// Title rules, checked in order before CODEOWNERS.
+    pub title_rules: Vec<TitleOwnerRule>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to set the ownership mapping for a repository.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PutOwnershipRequest {
+    /// CODEOWNERS file content. Patterns match `test_suites.file_path`;
+    /// the last matching line wins and its first owner is used.
+    #[serde(default)]
+    pub codeowners: Option<String>,
+    /// Title rules; the first matching rule wins.
+    #[serde(default)]
+    pub title_rules: Vec<TitleOwnerRule>,
+}
+
+/// Query parameters for filtering by owner.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct OwnerFilterQuery {
+    /// Only include suites containing tests with this owner.
+    #[serde(default)]
+    pub owner: Option<String>,
+}
+
+/// Per-owner test results within a report.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OwnerFailureSummary {
+    /// Owner, or null for tests without an owner.
+    pub owner: Option<String>,
+    /// Unique tests owned.
+    pub total: i64,
+    /// Failed tests (excluding quarantined).
+    pub failed: i64,
+    pub flaky: i64,
+    pub quarantined: i64,
+}
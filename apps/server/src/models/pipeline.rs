@@ -0,0 +1,139 @@
+//! Pipeline run models and DTOs.
+//!
+//! A pipeline run groups the reports registered by one CI run across
+//! frameworks: reports sharing a repository, commit SHA and run ID, or an
+//! explicit group key.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{Framework, ReportStatus, TestStats};
+
+/// Aggregated status of a pipeline run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineStatus {
+    /// At least one member report is still open.
+    Running,
+    /// Every member report completed without non-quarantined failures.
+    Passed,
+    /// A member report failed or has non-quarantined test failures.
+    Failed,
+    /// A member report was cancelled and none failed.
+    Cancelled,
+}
+
+impl PipelineStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Passed => "passed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl std::fmt::Display for PipelineStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Query parameters for listing pipeline runs.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ListPipelinesQuery {
+    /// Filter by GitHub repository (e.g., "org/repo").
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Filter by git ref (e.g., "refs/heads/main").
+    #[serde(rename = "ref", default)]
+    pub git_ref: Option<String>,
+    /// Filter by commit SHA (full or abbreviated, at least 7 hex characters).
+    #[serde(default)]
+    pub sha: Option<String>,
+    /// Filter by explicit group key.
+    #[serde(default)]
+    pub group_key: Option<String>,
+    /// Maximum results to return.
+    #[serde(default = "default_limit")]
+    pub limit: i32,
+    /// Offset for pagination.
+    #[serde(default)]
+    pub offset: i32,
+}
+
+fn default_limit() -> i32 {
+    20
+}
+
+/// A report belonging to a pipeline run.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PipelineReport {
+    pub report_id: Uuid,
+    pub framework: Framework,
+    pub status: ReportStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
+    /// GitHub run attempt (1 for the original run).
+    pub run_attempt: i32,
+    /// Environment labels.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Replaced by a later attempt with the same workflow, framework and
+    /// labels, so left out of the pipeline status and stats.
+    pub superseded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_stats: Option<TestStats>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Pipeline run with aggregated status and stats.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PipelineSummary {
+    pub id: Uuid,
+    /// Explicit group key sent on registration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    pub status: PipelineStatus,
+    /// Member reports, excluding superseded attempts.
+    pub report_count: i32,
+    /// Member reports that are closed.
+    pub reports_closed: i32,
+    /// Test statistics summed across member reports.
+    pub test_stats: TestStats,
+    pub created_at: DateTime<Utc>,
+    /// Last time a report joined the pipeline.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Pipeline run list response with pagination.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PipelineListResponse {
+    pub pipelines: Vec<PipelineSummary>,
+    /// Total number of pipeline runs matching the filter.
+    pub total: i64,
+    pub limit: i32,
+    pub offset: i32,
+}
+
+/// Pipeline run detail including its reports.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PipelineDetailResponse {
+    #[serde(flatten)]
+    pub summary: PipelineSummary,
+    /// Member reports, oldest first.
+    pub reports: Vec<PipelineReport>,
+}
@@ -0,0 +1,131 @@
+//! Quarantine registry models and DTOs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::Framework;
+
+/// Quarantine entry stored in database.
+#[derive(Debug, Clone)]
+pub struct QuarantineEntry {
+    pub id: Uuid,
+    pub repository: Option<String>,
+    pub framework: Option<Framework>,
+    pub fingerprint: Option<String>,
+    pub full_title: Option<String>,
+    pub title_pattern: Option<String>,
+    pub reason: String,
+    pub owner: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to quarantine a test.
+///
+/// Exactly one of `full_title`, `fingerprint` or `title_pattern` identifies
+/// the test(s). A full title is converted to its fingerprint.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateQuarantineRequest {
+    /// Exact full test title.
+    #[serde(default)]
+    pub full_title: Option<String>,
+    /// Test fingerprint (SHA-256 hex of the full title).
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    /// Title pattern where `*` matches any sequence of characters.
+    #[serde(default)]
+    pub title_pattern: Option<String>,
+    /// Limit the entry to a GitHub repository (e.g., "org/repo").
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Limit the entry to a framework.
+    #[serde(default)]
+    pub framework: Option<Framework>,
+    /// Why the test is quarantined.
+    pub reason: String,
+    /// Team or person responsible for fixing the test.
+    pub owner: String,
+    /// When the quarantine lapses (never when omitted).
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Quarantine entry response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuarantineEntryResponse {
+    pub id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub framework: Option<Framework>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_pattern: Option<String>,
+    pub reason: String,
+    pub owner: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<QuarantineEntry> for QuarantineEntryResponse {
+    fn from(e: QuarantineEntry) -> Self {
+        Self {
+            id: e.id,
+            repository: e.repository,
+            framework: e.framework,
+            fingerprint: e.fingerprint,
+            full_title: e.full_title,
+            title_pattern: e.title_pattern,
+            reason: e.reason,
+            owner: e.owner,
+            expires_at: e.expires_at,
+            created_by: e.created_by,
+            created_at: e.created_at,
+        }
+    }
+}
+
+/// Quarantine list response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuarantineListResponse {
+    pub entries: Vec<QuarantineEntryResponse>,
+}
+
+/// Output format for the quarantine list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QuarantineFormat {
+    /// JSON entries.
+    #[default]
+    Json,
+    /// One title or pattern per line.
+    Text,
+    /// A single regular expression for `--grep-invert` style flags.
+    Grep,
+}
+
+/// Query parameters for listing quarantined tests.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct QuarantineListQuery {
+    /// Only entries that apply to this repository (unscoped entries included).
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Only entries that apply to this framework (unscoped entries included).
+    #[serde(default)]
+    pub framework: Option<Framework>,
+    /// Include expired entries (default: false).
+    #[serde(default)]
+    pub include_expired: bool,
+    /// Output format (default: json).
+    #[serde(default)]
+    pub format: QuarantineFormat,
+}
@@ -0,0 +1,124 @@
+//! Release readiness models and DTOs.
+//!
+//! Readiness rolls up the latest complete report of every repository,
+//! framework, workflow and label combination on a release ref into a
+//! go/no-go decision.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{Framework, TestStats};
+
+/// Query parameters for release readiness.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ReadinessQuery {
+    /// Only reports from this repository.
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Release ref to compare with. Defaults to the closest lower version
+    /// with the same branch prefix (e.g. `release-10.4` for `release-10.5`).
+    #[serde(default)]
+    pub previous_ref: Option<String>,
+}
+
+/// Go/no-go decision for a release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessDecision {
+    /// No unexplained failures.
+    Go,
+    /// Only failures that already failed on the previous release,
+    /// quarantined or known-issue failures, or combinations without a report.
+    Caution,
+    /// New failures since the previous release.
+    NoGo,
+}
+
+/// A failure matching a known issue that is still tracked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct KnownIssueFailure {
+    pub full_title: String,
+    pub issue_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_url: Option<String>,
+}
+
+/// Readiness of one repository, framework, workflow and label combination.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReadinessCombination {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    pub framework: Framework,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
+    /// Environment labels.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    pub decision: ReadinessDecision,
+    /// Latest complete report of the combination on the ref.
+    pub report_id: Uuid,
+    /// Report page in the web UI.
+    pub report_url: String,
+    /// GitHub Actions run of the report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub test_stats: TestStats,
+    /// Passed tests out of all tests.
+    pub pass_rate: f64,
+    /// Report of the same combination on the previous release ref.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_report_id: Option<Uuid>,
+    /// Failures that didn't fail on the previous release ref.
+    pub new_failures: Vec<String>,
+    /// Failures that also failed on the previous release ref.
+    pub existing_failures: Vec<String>,
+    /// Failures of tests that are still quarantined.
+    pub quarantined_failures: Vec<String>,
+    /// Failures matching a known issue that is still tracked.
+    pub known_issue_failures: Vec<KnownIssueFailure>,
+}
+
+/// A combination that ran on the previous release ref but has no complete
+/// report on this one.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MissingCombination {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    pub framework: Framework,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Report of the combination on the previous release ref.
+    pub previous_report_id: Uuid,
+    pub previous_report_url: String,
+}
+
+/// Release readiness across frameworks and environments.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReleaseReadinessResponse {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// Release ref failures were compared with (null if none was found).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_ref: Option<String>,
+    /// Worst decision across combinations.
+    pub decision: ReadinessDecision,
+    /// Why the decision isn't `go`.
+    pub reasons: Vec<String>,
+    /// Test statistics summed across combinations.
+    pub test_stats: TestStats,
+    /// Passed tests out of all tests.
+    pub pass_rate: f64,
+    /// Combinations with a complete report on the ref, worst first.
+    pub combinations: Vec<ReadinessCombination>,
+    /// Combinations from the previous release ref without a report.
+    pub missing_combinations: Vec<MissingCombination>,
+}
@@ -1,5 +1,7 @@
 //! Report domain models and DTOs.
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -125,6 +127,7 @@ pub enum ReportStatus {
     Processing,
     Complete,
     Failed,
+    Cancelled,
 }
 
 impl ReportStatus {
@@ -135,6 +138,7 @@ impl ReportStatus {
             Self::Processing => "processing",
             Self::Complete => "complete",
             Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
         }
     }
 
@@ -145,6 +149,7 @@ impl ReportStatus {
             "processing" => Some(Self::Processing),
             "complete" => Some(Self::Complete),
             "failed" => Some(Self::Failed),
+            "cancelled" => Some(Self::Cancelled),
             _ => None,
         }
     }
@@ -190,16 +195,83 @@ impl std::fmt::Display for Framework {
     }
 }
 
+/// Most jobs a report can have.
+pub const MAX_EXPECTED_JOBS: i32 = 100;
+
 /// Request to register a new report.
 #[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct RegisterReportRequest {
-    /// Number of parallel jobs expected (1-100).
-    pub expected_jobs: i32,
+    /// Number of parallel jobs expected (1-100). Omit for an open report
+    /// that accepts jobs until finalized or idle.
+    #[serde(default)]
+    pub expected_jobs: Option<i32>,
     /// Test framework.
     pub framework: Framework,
     /// GitHub metadata (stored as JSONB).
     #[serde(default)]
     pub github_metadata: Option<GitHubMetadata>,
+    /// Environment labels (e.g., `{"db": "mysql", "edition": "enterprise"}`).
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Free-form metadata object (max 16 KB).
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub custom_metadata: Option<JsonValue>,
+    /// Pipeline group key (max 255 characters). Reports sharing it form one
+    /// pipeline run; by default reports group by repository, SHA and run ID.
+    #[serde(default)]
+    pub group_key: Option<String>,
+}
+
+/// Request to update a report's labels and custom metadata.
+///
+/// Omitted fields are left unchanged; provided fields replace the stored
+/// value.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateReportMetadataRequest {
+    #[serde(default)]
+    pub labels: Option<BTreeMap<String, String>>,
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub custom_metadata: Option<JsonValue>,
+}
+
+/// Request to change a report's expected job count.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateReportRequest {
+    /// Number of jobs expected (1-100). Closes an open report's job list.
+    pub expected_jobs: i32,
+}
+
+/// A report's job expectations after an update.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UpdateReportResponse {
+    pub id: Uuid,
+    pub status: ReportStatus,
+    pub expected_jobs: i32,
+    pub open: bool,
+}
+
+/// A report's labels and custom metadata.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReportMetadataResponse {
+    pub id: Uuid,
+    pub labels: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub custom_metadata: Option<JsonValue>,
+}
+
+/// Parse stored report labels, ignoring non-string values.
+pub fn labels_from_json(value: &JsonValue) -> BTreeMap<String, String> {
+    value
+        .as_object()
+        .map(|o| {
+            o.iter()
+                .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 /// Response after registering a report.
@@ -209,12 +281,85 @@ pub struct RegisterReportResponse {
     pub report_id: Uuid,
     /// Report status.
     pub status: ReportStatus,
-    /// Expected number of jobs.
+    /// Expected number of jobs (0 for open reports).
     pub expected_jobs: i32,
+    /// Whether jobs raise `expected_jobs` until the report is finalized.
+    pub open: bool,
     /// Test framework.
     pub framework: Framework,
+    /// Pipeline run the report belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline_id: Option<Uuid>,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
+    /// True if returning the report registered with the same idempotency key.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_existing: bool,
+}
+
+/// Response after finalizing or cancelling a report.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CloseReportResponse {
+    /// Report UUID.
+    pub report_id: Uuid,
+    /// Status the report closed with.
+    pub status: ReportStatus,
+    /// Expected number of jobs.
+    pub expected_jobs: i32,
+    /// Jobs that completed.
+    pub completed_jobs: i64,
+    /// Jobs that failed.
+    pub failed_jobs: i64,
+    /// Expected jobs that never finished.
+    pub missing_jobs: i32,
+}
+
+/// Response after importing a report bundle.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ImportReportResponse {
+    /// UUID of the imported report.
+    pub report_id: Uuid,
+    /// UUID of the report on the instance it was exported from.
+    pub source_report_id: Uuid,
+    pub jobs: usize,
+    pub test_suites: usize,
+    pub test_cases: usize,
+    /// Stored files (HTML, screenshots, JSON) uploaded.
+    pub artifacts: usize,
+}
+
+/// Overall test outcome of a report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportOutcome {
+    /// No failures.
+    #[default]
+    Passed,
+    /// The only failures are of quarantined tests.
+    PassedExceptQuarantined,
+    /// At least one non-quarantined failure.
+    Failed,
+}
+
+impl ReportOutcome {
+    /// Derive the outcome from failure counts.
+    pub fn from_counts(failed: i32, quarantined: i32) -> Self {
+        if failed > 0 {
+            Self::Failed
+        } else if quarantined > 0 {
+            Self::PassedExceptQuarantined
+        } else {
+            Self::Passed
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Passed => "passed",
+            Self::PassedExceptQuarantined => "passed_except_quarantined",
+            Self::Failed => "failed",
+        }
+    }
 }
 
 /// Test statistics for a report.
@@ -230,6 +375,12 @@ pub struct TestStats {
     pub skipped: i32,
     /// Number of flaky tests.
     pub flaky: i32,
+    /// Number of failed tests on the quarantine list (not included in `failed`).
+    pub quarantined: i32,
+    /// Number of failed tests matching a known issue (included in `failed`).
+    pub known_issues: i32,
+    /// Overall outcome derived from the counts above.
+    pub outcome: ReportOutcome,
     /// Total duration in milliseconds (from JSON stats, null if not available).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration_ms: Option<i64>,
@@ -249,10 +400,15 @@ pub struct ReportSummary {
     pub status: ReportStatus,
     /// Test framework.
     pub framework: Framework,
-    /// Expected number of jobs.
+    /// Expected number of jobs (jobs registered so far for open reports).
     pub expected_jobs: i32,
+    /// Whether jobs raise `expected_jobs` until the report is finalized.
+    pub open: bool,
     /// Number of completed jobs.
     pub jobs_complete: i32,
+    /// Expected jobs that never finished (set when closed early).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub missing_jobs: Option<i32>,
     /// Test statistics aggregated from all jobs.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub test_stats: Option<TestStats>,
@@ -262,6 +418,9 @@ pub struct ReportSummary {
     /// OIDC claims (token-derived, stored separately).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub oidc_claims: Option<super::report_oidc_claim::ReportOidcClaimsResponse>,
+    /// Environment labels.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
 }
@@ -275,20 +434,40 @@ pub struct ReportDetailResponse {
     pub status: ReportStatus,
     /// Test framework.
     pub framework: Framework,
-    /// Expected number of jobs.
+    /// Expected number of jobs (jobs registered so far for open reports).
     pub expected_jobs: i32,
+    /// Whether jobs raise `expected_jobs` until the report is finalized.
+    pub open: bool,
+    /// Expected jobs that never finished (set when closed early).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub missing_jobs: Option<i32>,
     /// GitHub metadata (caller-supplied).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub github_metadata: Option<GitHubMetadata>,
     /// OIDC claims (token-derived, stored separately).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub oidc_claims: Option<super::report_oidc_claim::ReportOidcClaimsResponse>,
+    /// Environment labels.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Free-form caller-supplied metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub custom_metadata: Option<JsonValue>,
+    /// Pipeline run grouping this report with others from the same CI run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline_id: Option<Uuid>,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
     /// Last update timestamp.
     pub updated_at: DateTime<Utc>,
     /// Jobs in this report (ordered by creation time).
     pub jobs: Vec<super::job::JobSummary>,
+    /// Test results per owner, most failures first.
+    pub owners: Vec<super::ownership::OwnerFailureSummary>,
+    /// Other attempts of the same workflow run (null without a GitHub run ID).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lineage: Option<super::run_lineage::ReportLineage>,
 }
 
 /// Report list response with pagination.
@@ -304,6 +483,28 @@ pub struct ReportListResponse {
     pub offset: i32,
 }
 
+/// Sort field for report lists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportSort {
+    /// Report creation time.
+    #[default]
+    CreatedAt,
+    /// Number of non-quarantined failed tests.
+    Failures,
+    /// Summed job duration.
+    Duration,
+}
+
+/// Sort direction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
 /// Query parameters for listing reports.
 #[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct ListReportsQuery {
@@ -319,6 +520,39 @@ pub struct ListReportsQuery {
     /// Filter by git ref (e.g., "refs/heads/main").
     #[serde(rename = "ref", default)]
     pub git_ref: Option<String>,
+    /// Filter by commit SHA (full or abbreviated, at least 7 hex characters).
+    #[serde(default)]
+    pub sha: Option<String>,
+    /// Filter by pull request number.
+    #[serde(default)]
+    pub pr_number: Option<i32>,
+    /// Filter by the GitHub user who triggered the workflow.
+    #[serde(default)]
+    pub actor: Option<String>,
+    /// Filter by workflow name.
+    #[serde(default)]
+    pub workflow: Option<String>,
+    /// Filter by triggering event (e.g., "push", "pull_request").
+    #[serde(default)]
+    pub event_name: Option<String>,
+    /// Only include reports created at or after this time.
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// Only include reports created at or before this time.
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+    /// Only reports with (true) or without (false) non-quarantined failures.
+    #[serde(default)]
+    pub has_failures: Option<bool>,
+    /// Only reports carrying all these labels (`key:value[,key:value...]`).
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Sort field (default: created_at).
+    #[serde(default)]
+    pub sort: ReportSort,
+    /// Sort direction (default: desc).
+    #[serde(default)]
+    pub order: SortOrder,
     /// Maximum results to return.
     #[serde(default = "default_limit")]
     pub limit: i32,
@@ -0,0 +1,76 @@
+//! Rerun manifest models and DTOs.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::Framework;
+
+/// Output format for a rerun manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RerunFormat {
+    /// Playwright CLI arguments, one per line.
+    Playwright,
+    /// Cypress CLI arguments, one per line.
+    Cypress,
+    /// Jest (Detox) CLI arguments, one per line.
+    Detox,
+    /// JSON manifest with the tests, files and arguments for the report's framework.
+    Json,
+}
+
+impl RerunFormat {
+    /// Framework whose CLI arguments the format produces, if any.
+    pub fn framework(&self) -> Option<Framework> {
+        match self {
+            Self::Playwright => Some(Framework::Playwright),
+            Self::Cypress => Some(Framework::Cypress),
+            Self::Detox => Some(Framework::Detox),
+            Self::Json => None,
+        }
+    }
+}
+
+/// Query parameters for the rerun manifest.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct RerunQuery {
+    /// Output format (default: json).
+    #[serde(default)]
+    pub format: Option<RerunFormat>,
+    /// Also rerun tests that passed only after retries.
+    #[serde(default)]
+    pub include_flaky: bool,
+    /// Only tests from this job.
+    #[serde(default)]
+    pub job_id: Option<Uuid>,
+    /// Only tests from this Playwright project.
+    #[serde(default)]
+    pub project: Option<String>,
+}
+
+/// A test to rerun.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct RerunTest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    pub title: String,
+    pub full_title: String,
+    /// Playwright project the test ran in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Final status: failed, timedOut or flaky.
+    pub status: String,
+}
+
+/// Rerun manifest.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RerunManifest {
+    pub report_id: Uuid,
+    pub framework: Framework,
+    /// Spec files containing tests to rerun.
+    pub files: Vec<String>,
+    pub tests: Vec<RerunTest>,
+    /// CLI arguments selecting exactly these tests.
+    pub args: Vec<String>,
+}
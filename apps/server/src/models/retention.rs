@@ -0,0 +1,71 @@
+//! Data retention models and DTOs.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Kind of report, which decides how long it is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportClass {
+    /// Push to a configured default branch.
+    DefaultBranch,
+    /// Push to any other branch, or no git metadata.
+    Branch,
+    /// Pull request run.
+    PullRequest,
+}
+
+impl ReportClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DefaultBranch => "default_branch",
+            Self::Branch => "branch",
+            Self::PullRequest => "pull_request",
+        }
+    }
+}
+
+/// Expired reports of one kind.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RetentionClassPreview {
+    pub class: ReportClass,
+    /// Days reports of this kind are kept (0 = forever).
+    pub retention_days: u32,
+    /// Reports created before this are expired.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cutoff: Option<DateTime<Utc>>,
+    /// Number of expired reports.
+    pub reports: i64,
+}
+
+/// What the next retention run would remove.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RetentionPreview {
+    /// Whether scheduled runs only log instead of removing.
+    pub dry_run: bool,
+    /// Whether scheduled runs are enabled.
+    pub enabled: bool,
+    pub generated_at: DateTime<Utc>,
+    /// Reports to soft-delete, by kind.
+    pub classes: Vec<RetentionClassPreview>,
+    /// Up to 100 of the reports to soft-delete, oldest first.
+    pub sample_report_ids: Vec<Uuid>,
+    /// Days HTML reports and screenshots are kept (0 = forever).
+    pub artifact_retention_days: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact_cutoff: Option<DateTime<Utc>>,
+    /// Jobs of kept reports whose HTML reports and screenshots expire.
+    pub artifact_jobs: i64,
+    /// HTML and screenshot files to delete from storage.
+    pub artifact_files: i64,
+    /// Size of those files in bytes.
+    pub artifact_bytes: i64,
+    /// Days soft-deleted reports are kept before purging.
+    pub purge_grace_days: u32,
+    /// Reports soft-deleted before this are purged.
+    pub purge_cutoff: DateTime<Utc>,
+    /// Soft-deleted reports to purge.
+    pub purgeable_reports: i64,
+}
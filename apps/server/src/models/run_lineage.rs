@@ -0,0 +1,107 @@
+//! Run lineage models and DTOs.
+//!
+//! A run lineage groups the reports registered by each attempt of one GitHub
+//! Actions workflow run (same repository, run ID and workflow).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{Framework, ReportOutcome, ReportStatus, TestStats};
+
+/// Query parameters for a run lineage.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct RunLineageQuery {
+    /// Only reports from this workflow.
+    #[serde(default)]
+    pub workflow: Option<String>,
+    /// Also list tests that passed in every attempt they ran in.
+    #[serde(default)]
+    pub include_passing: bool,
+}
+
+/// A report registered by one attempt of a workflow run.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RunAttempt {
+    /// GitHub run attempt (1 for the original run).
+    pub run_attempt: i32,
+    pub report_id: Uuid,
+    pub framework: Framework,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
+    pub status: ReportStatus,
+    /// Test statistics of this attempt alone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_stats: Option<TestStats>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Status of a test in one attempt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct TestAttemptStatus {
+    pub run_attempt: i32,
+    /// Final status after retries within the attempt.
+    pub status: String,
+}
+
+/// Merged result of a test across attempts.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RunTestResult {
+    pub full_title: String,
+    pub framework: Framework,
+    /// Status in the last attempt the test ran in.
+    pub status: String,
+    /// Attempt the final status comes from.
+    pub final_attempt: i32,
+    /// Failed in an earlier attempt and passed in a later one.
+    pub passed_on_rerun: bool,
+    /// The final failure is of a quarantined test.
+    pub quarantined: bool,
+    /// Status in each attempt the test ran in, oldest first.
+    pub attempts: Vec<TestAttemptStatus>,
+}
+
+/// Merged test counts across all attempts of a run.
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct RunSummary {
+    /// Number of distinct tests.
+    pub total: i32,
+    /// Tests whose final status is passed (including `passed_on_rerun`).
+    pub passed: i32,
+    /// Tests whose final status is a non-quarantined failure.
+    pub failed: i32,
+    pub skipped: i32,
+    pub flaky: i32,
+    /// Tests whose final status is a quarantined failure.
+    pub quarantined: i32,
+    /// Tests that failed in an earlier attempt and passed in a later one.
+    pub passed_on_rerun: i32,
+    /// Final outcome of the run.
+    pub outcome: ReportOutcome,
+}
+
+/// Run lineage with merged per-test results.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RunLineageResponse {
+    pub repository: String,
+    pub run_id: String,
+    /// Reports of every attempt, oldest attempt first.
+    pub attempts: Vec<RunAttempt>,
+    pub summary: RunSummary,
+    /// Merged test results. Tests that passed in every attempt are omitted
+    /// unless `include_passing` is set.
+    pub tests: Vec<RunTestResult>,
+}
+
+/// Lineage of the workflow run a report belongs to.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReportLineage {
+    pub run_id: String,
+    /// Attempt this report was registered by.
+    pub run_attempt: i32,
+    /// Whether no later attempt of the run has registered a report.
+    pub is_latest: bool,
+    /// Reports of every attempt, oldest attempt first.
+    pub attempts: Vec<RunAttempt>,
+}
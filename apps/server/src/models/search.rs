@@ -0,0 +1,83 @@
+//! Global test search models and DTOs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Query parameters for global search.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct GlobalSearchQuery {
+    /// Search terms (web search syntax: quoted phrases, `or`, `-exclude`).
+    pub q: String,
+    /// Filter by GitHub repository (e.g., "org/repo").
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Filter by git ref (e.g., "refs/heads/main").
+    #[serde(rename = "ref", default)]
+    pub git_ref: Option<String>,
+    /// Filter by test status (e.g., "failed").
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Only include reports created at or after this time.
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// Only include reports created at or before this time.
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+    /// Maximum results to return (default 20, max 100).
+    #[serde(default = "default_limit")]
+    pub limit: i32,
+    /// Cursor from a previous response's `next_cursor`.
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+fn default_limit() -> i32 {
+    20
+}
+
+/// A test case matching a global search.
+///
+/// Highlights are HTML-escaped with matched terms wrapped in `<mark>`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SearchHit {
+    pub test_case_id: Uuid,
+    pub report_id: Uuid,
+    pub job_id: Uuid,
+    pub suite_id: Uuid,
+    pub title: String,
+    pub full_title: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// Relevance score; higher is better.
+    pub rank: f64,
+    /// Full title with matched terms highlighted.
+    pub title_highlight: String,
+    /// File path with matched terms highlighted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path_highlight: Option<String>,
+    /// Fragment of the error message around the matched terms.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_highlight: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Global search response.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GlobalSearchResponse {
+    /// The search query.
+    pub query: String,
+    /// Minimum search length configured on server.
+    pub search_min_length: usize,
+    /// Matches, most relevant first.
+    pub hits: Vec<SearchHit>,
+    /// Cursor for the next page; absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
@@ -0,0 +1,56 @@
+//! Shard planning models and DTOs.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::Framework;
+
+/// Request to split spec files across shards.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ShardPlanRequest {
+    /// Spec file paths as reported in suite `file_path` (e.g., "e2e/login.spec.ts").
+    pub files: Vec<String>,
+    /// Number of shards (1-256).
+    pub shards: usize,
+    /// Only use history from this GitHub repository (e.g., "org/repo").
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Only use history from this framework.
+    #[serde(default)]
+    pub framework: Option<Framework>,
+    /// Days of history to consider (1-180, default 30).
+    #[serde(default)]
+    pub lookback_days: Option<i64>,
+}
+
+/// A spec file assigned to a shard.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ShardFile {
+    pub file_path: String,
+    /// Median historical duration, or the estimate for unseen files.
+    pub estimated_duration_ms: i64,
+    /// Number of past runs the median is based on (0 for unseen files).
+    pub samples: i64,
+}
+
+/// Files assigned to one shard.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Shard {
+    /// 1-based shard index (matches Playwright's `--shard=i/n`).
+    pub index: usize,
+    pub files: Vec<ShardFile>,
+    /// Sum of the files' estimated durations.
+    pub estimated_duration_ms: i64,
+}
+
+/// Balanced shard assignment.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ShardPlanResponse {
+    pub shards: Vec<Shard>,
+    /// Estimated duration of the slowest shard.
+    pub estimated_makespan_ms: i64,
+    /// Estimate used for files without history.
+    pub unseen_file_estimate_ms: i64,
+    /// Number of files without history.
+    pub unseen_files: usize,
+}
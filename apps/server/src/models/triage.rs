@@ -0,0 +1,137 @@
+//! Failure triage models and DTOs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What a failure turned out to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TriageClassification {
+    ProductBug,
+    TestBug,
+    Infra,
+    Flaky,
+    KnownIssue,
+}
+
+impl TriageClassification {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ProductBug => "product_bug",
+            Self::TestBug => "test_bug",
+            Self::Infra => "infra",
+            Self::Flaky => "flaky",
+            Self::KnownIssue => "known_issue",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "product_bug" => Some(Self::ProductBug),
+            "test_bug" => Some(Self::TestBug),
+            "infra" => Some(Self::Infra),
+            "flaky" => Some(Self::Flaky),
+            "known_issue" => Some(Self::KnownIssue),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for TriageClassification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Triage record for a test case or failure signature.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TriageRecord {
+    pub id: Uuid,
+    /// Test case the record was created from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_case_id: Option<Uuid>,
+    /// Full title of the triaged test; null applies to any test with the signature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    pub classification: TriageClassification,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_url: Option<String>,
+    /// Display name of the author (GitHub username, OIDC actor or API key name).
+    pub author: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to triage a failure.
+///
+/// Either `test_case_id` or `failure_signature` is required. Triaging a test
+/// case also records its title, signature and repository so the record
+/// carries forward to later failures.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateTriageRequest {
+    #[serde(default)]
+    pub test_case_id: Option<Uuid>,
+    /// Failure signature to triage (ignored when `test_case_id` is set).
+    #[serde(default)]
+    pub failure_signature: Option<String>,
+    /// Limit a signature record to one test title.
+    #[serde(default)]
+    pub full_title: Option<String>,
+    /// Limit a signature record to a GitHub repository (e.g., "org/repo").
+    #[serde(default)]
+    pub repository: Option<String>,
+    pub classification: TriageClassification,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Link to an external issue (http or https).
+    #[serde(default)]
+    pub issue_url: Option<String>,
+}
+
+/// Request to update a triage record. Omitted fields are left unchanged.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateTriageRequest {
+    #[serde(default)]
+    pub classification: Option<TriageClassification>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub issue_url: Option<String>,
+}
+
+/// Query parameters for listing triage records.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct TriageListQuery {
+    #[serde(default)]
+    pub test_case_id: Option<Uuid>,
+    #[serde(default)]
+    pub failure_signature: Option<String>,
+    #[serde(default)]
+    pub repository: Option<String>,
+    #[serde(default)]
+    pub classification: Option<TriageClassification>,
+}
+
+/// Triage record list response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TriageListResponse {
+    pub records: Vec<TriageRecord>,
+}
+
+/// Triage state shown on a test spec.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TriageState {
+    #[serde(flatten)]
+    pub record: TriageRecord,
+    /// True when the record was made for an earlier failure with the same
+    /// signature rather than for this test case.
+    pub inherited: bool,
+}
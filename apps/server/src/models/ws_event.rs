@@ -19,6 +19,11 @@ pub enum WsEvent {
     JobUpdated(JobUpdatedPayload),
     /// Test suites are now available for a job.
     SuitesAvailable(SuitesAvailablePayload),
+    /// A report was deleted.
+    ReportDeleted(ReportDeletedPayload),
+    /// A pipeline run's aggregated status changed as member reports
+    /// registered or closed.
+    PipelineUpdated(PipelineUpdatedPayload),
 }
 
 /// Payload for report_created event.
@@ -50,6 +55,11 @@ pub struct ReportUpdatedPayload {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub completed_jobs: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_jobs: Option<i32>,
+    /// Set for open reports, whose `expected_jobs` grows as jobs register.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub test_stats: Option<TestStatsPayload>,
     pub updated_at: DateTime<Utc>,
 }
@@ -87,7 +97,25 @@ pub struct SuitesAvailablePayload {
     pub suite_count: i32,
 }
 
-/// Test statistics included in report_updated events.
+/// Payload for report_deleted event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportDeletedPayload {
+    pub report_id: Uuid,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Payload for pipeline_updated event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineUpdatedPayload {
+    pub pipeline_id: Uuid,
+    pub status: String,
+    pub report_count: i32,
+    pub reports_closed: i32,
+    pub test_stats: TestStatsPayload,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Test statistics included in report_updated and pipeline_updated events.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestStatsPayload {
     pub passed: i32,
@@ -139,6 +167,27 @@ impl WsEvent {
             report_id,
             status: "updated".to_string(),
             completed_jobs: None,
+            expected_jobs: None,
+            open: None,
+            test_stats: None,
+            updated_at: Utc::now(),
+        })
+    }
+
+    /// Create a report_updated event with job progress.
+    pub fn report_progress(
+        report_id: Uuid,
+        status: &str,
+        completed_jobs: i32,
+        expected_jobs: i32,
+        open: bool,
+    ) -> Self {
+        WsEvent::ReportUpdated(ReportUpdatedPayload {
+            report_id,
+            status: status.to_string(),
+            completed_jobs: Some(completed_jobs),
+            expected_jobs: Some(expected_jobs),
+            open: open.then_some(true),
             test_stats: None,
             updated_at: Utc::now(),
         })
@@ -176,4 +225,31 @@ impl WsEvent {
             suite_count,
         })
     }
+
+    /// Create a pipeline_updated event from a pipeline summary.
+    pub fn pipeline_updated(summary: &super::PipelineSummary) -> Self {
+        let stats = &summary.test_stats;
+        WsEvent::PipelineUpdated(PipelineUpdatedPayload {
+            pipeline_id: summary.id,
+            status: summary.status.as_str().to_string(),
+            report_count: summary.report_count,
+            reports_closed: summary.reports_closed,
+            test_stats: TestStatsPayload {
+                passed: stats.passed,
+                failed: stats.failed,
+                skipped: stats.skipped,
+                flaky: Some(stats.flaky),
+                total: stats.total,
+            },
+            updated_at: Utc::now(),
+        })
+    }
+
+    /// Create a report_deleted event.
+    pub fn report_deleted(report_id: Uuid) -> Self {
+        WsEvent::ReportDeleted(ReportDeletedPayload {
+            report_id,
+            deleted_at: Utc::now(),
+        })
+    }
 }
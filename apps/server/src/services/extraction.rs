@@ -9,11 +9,21 @@ use serde_json::{Value as JsonValue, json};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use crate::config::FeatureSettings;
 use crate::db::DbPool;
 use crate::db::test_results::{NewTestCase, NewTestSuite};
-use crate::models::{JobStatus, WsEvent, WsEventMessage};
+use crate::models::{Framework, GitHubMetadata, JobStatus, WsEvent, WsEventMessage};
+use crate::services::failure_signature::signature_for_case;
+use crate::services::known_issue::KnownIssueMatcher;
+use crate::services::ownership::OwnershipResolver;
+use crate::services::quarantine::QuarantineMatcher;
+use crate::services::report_completion;
+use crate::services::text::non_empty;
 use crate::services::{EventBroadcaster, Storage};
 
+/// Maximum length of an owner annotation, matching the `owner` column.
+const MAX_OWNER_LENGTH: usize = 255;
+
 // ============================================================================
 // Cypress / Mochawesome JSON Structures
 // ============================================================================
@@ -168,6 +178,8 @@ struct PlaywrightTest {
     #[serde(rename = "projectName", default)]
     project_name: String,
     #[serde(default)]
+    annotations: Vec<PlaywrightAnnotation>,
+    #[serde(default)]
     results: Vec<PlaywrightTestResult>,
     #[serde(default)]
     status: String,
@@ -198,6 +210,14 @@ struct PlaywrightError {
     stack: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct PlaywrightAnnotation {
+    #[serde(rename = "type", default)]
+    annotation_type: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct PlaywrightAttachment {
     #[serde(rename = "contentType", default)]
@@ -222,6 +242,8 @@ struct ExtractedTestCase {
     attachments: Vec<ExtractedAttachment>,
     /// Test execution start time (ISO 8601).
     start_time: Option<DateTime<Utc>>,
+    /// Owner declared on the test itself (e.g., a Playwright `owner` annotation).
+    annotated_owner: Option<String>,
 }
 
 /// Attachment extracted from test results.
@@ -256,6 +278,7 @@ pub async fn extract_job(
     pool: &DbPool,
     storage: &Storage,
     broadcaster: &EventBroadcaster,
+    features: &FeatureSettings,
     job_id: Uuid,
     framework: &str,
 ) {
@@ -282,38 +305,22 @@ pub async fn extract_job(
         Ok(files) => files,
         Err(e) => {
             error!("Failed to get JSON files for job {}: {}", job_id, e);
-            if let Err(e) = pool
-                .update_job_status(
-                    job_id,
-                    JobStatus::Failed,
-                    Some(format!("Failed to get JSON files: {}", e)),
-                )
-                .await
-            {
-                error!("Failed to update job status: {}", e);
-            }
+            let message = format!("Failed to get JSON files: {}", e);
+            fail_job(pool, broadcaster, features, &job, message).await;
             return;
         }
     };
 
     if json_files.is_empty() {
         error!("Job {} has no uploaded JSON files", job_id);
-        if let Err(e) = pool
-            .update_job_status(
-                job_id,
-                JobStatus::Failed,
-                Some("No JSON files uploaded".to_string()),
-            )
-            .await
-        {
-            error!("Failed to update job status: {}", e);
-        }
+        let message = "No JSON files uploaded".to_string();
+        fail_job(pool, broadcaster, features, &job, message).await;
         return;
     }
 
     let mut all_suites: Vec<ExtractedTestSuite> = Vec::new();
     let mut total_duration_ms: i64 = 0;
-    let earliest_time: Option<DateTime<Utc>> = None;
+    let mut earliest_time: Option<DateTime<Utc>> = None;
     let mut global_sequence = 0;
 
     // Fetch uploaded screenshots for validation
@@ -373,6 +380,11 @@ pub async fn extract_job(
         };
 
         for suite in suites {
+            if let Some(start) = suite.start_time
+                && earliest_time.is_none_or(|t| start < t)
+            {
+                earliest_time = Some(start);
+            }
             total_duration_ms += suite
                 .test_cases
                 .iter()
@@ -394,22 +406,30 @@ pub async fn extract_job(
         total_duration_ms
     );
 
+    // Load quarantine entries, ownership rules and known issues that apply to this report
+    let repository = report_repository(pool, job.test_report_id).await;
+    let quarantine = load_quarantine(pool, repository.as_deref(), framework).await;
+    let ownership = load_ownership(pool, repository.as_deref()).await;
+    let known_issues = load_known_issues(pool, repository.as_deref()).await;
+
     // Insert test suites and cases into database
     let mut suite_count = 0;
     let mut case_count = 0;
     for suite in all_suites {
-        let (passed, failed, skipped, flaky, unique_count) = count_statuses(&suite.test_cases);
+        let counts = count_statuses(&suite.test_cases, &quarantine, &known_issues);
         let duration_ms = suite.test_cases.iter().map(|tc| tc.duration_ms).sum();
 
         let new_suite = NewTestSuite {
             job_id,
             title: suite.title,
-            file_path: suite.file_path,
-            total_count: unique_count,
-            passed_count: passed,
-            failed_count: failed,
-            skipped_count: skipped,
-            flaky_count: flaky,
+            file_path: suite.file_path.clone(),
+            total_count: counts.unique,
+            passed_count: counts.passed,
+            failed_count: counts.failed,
+            skipped_count: counts.skipped,
+            flaky_count: counts.flaky,
+            quarantined_count: counts.quarantined,
+            known_issue_count: counts.known_issues,
             duration_ms,
             start_time: suite.start_time,
         };
@@ -445,6 +465,16 @@ pub async fn extract_job(
                         ))
                     };
 
+                    let failure_signature =
+                        signature_for_case(&test_case.status, test_case.error_message.as_deref());
+                    let quarantined = quarantine.is_quarantined(&test_case.full_title);
+                    let known_issue_key = match_known_issue(&known_issues, &test_case);
+                    let owner = ownership.resolve(
+                        test_case.annotated_owner.as_deref(),
+                        &test_case.full_title,
+                        suite.file_path.as_deref(),
+                    );
+
                     let new_case = NewTestCase {
                         suite_id,
                         job_id,
@@ -454,6 +484,10 @@ pub async fn extract_job(
                         duration_ms: test_case.duration_ms,
                         retry_count: test_case.retry_count,
                         error_message: test_case.error_message,
+                        failure_signature,
+                        quarantined,
+                        owner,
+                        known_issue_key,
                         sequence: test_case.sequence,
                         attachments: attachments_json,
                     };
@@ -508,8 +542,13 @@ pub async fn extract_job(
     let suites_event = WsEventMessage::new(WsEvent::suites_available(report_id, suite_count));
     broadcaster.send(suites_event);
 
-    // Check if all jobs for the report are complete
-    if let Err(e) = check_report_completion(pool, broadcaster, report_id).await {
+    // Refresh trend rollup with this job's results
+    if let Err(e) = pool.refresh_report_rollup(report_id).await {
+        error!("Failed to refresh rollup for report {}: {}", report_id, e);
+    }
+
+    // Check if all jobs for the report are finished
+    if let Err(e) = report_completion::check(pool, broadcaster, features, report_id).await {
         error!("Failed to check report {} completion: {}", report_id, e);
     }
 
@@ -769,6 +808,7 @@ fn extract_cypress_test(
         sequence,
         attachments,
         start_time: report_start_time, // From stats.start at report level
+        annotated_owner: None,
     }
 }
 
@@ -902,6 +942,7 @@ fn extract_detox(
                 sequence: seq,
                 attachments: Vec::new(), // Detox doesn't embed attachments in JSON
                 start_time: file_start_time, // From perfStats.start at file level
+                annotated_owner: None,
             };
 
             suite_tests.entry(suite_name).or_default().push(test_case);
@@ -1019,6 +1060,15 @@ fn extract_playwright_test(
 ) -> Vec<ExtractedTestCase> {
     let mut cases = Vec::new();
 
+    // Owner declared via `test.info().annotations.push({ type: 'owner', ... })`.
+    // Blank or over-long values are ignored so ownership rules apply instead.
+    let annotated_owner = test
+        .annotations
+        .iter()
+        .find(|a| a.annotation_type.eq_ignore_ascii_case("owner"))
+        .and_then(|a| non_empty(a.description.as_deref()))
+        .filter(|o| o.chars().count() <= MAX_OWNER_LENGTH);
+
     // Playwright can have multiple retry attempts
     for result in &test.results {
         let status = match result.status.as_str() {
@@ -1101,6 +1151,7 @@ fn extract_playwright_test(
             sequence: seq,
             attachments,
             start_time,
+            annotated_owner: annotated_owner.clone(),
         });
     }
 
@@ -1119,6 +1170,7 @@ fn extract_playwright_test(
             sequence: seq,
             attachments: Vec::new(),
             start_time: None,
+            annotated_owner,
         });
     }
 
@@ -1234,85 +1286,173 @@ fn truncate_message(msg: &str, max_len: usize) -> String {
     }
 }
 
+/// Per-suite status counts by unique spec.
+#[derive(Debug, Default)]
+struct StatusCounts {
+    passed: i32,
+    failed: i32,
+    skipped: i32,
+    flaky: i32,
+    quarantined: i32,
+    /// Failures matching a known issue (a subset of `failed`).
+    known_issues: i32,
+    unique: i32,
+}
+
 /// Count test case statuses by unique spec (full_title).
 /// For specs with multiple attempts, use the status of the last attempt (highest retry_count).
-/// Returns (passed, failed, skipped, flaky, unique_count).
-fn count_statuses(test_cases: &[ExtractedTestCase]) -> (i32, i32, i32, i32, i32) {
+/// Failures of quarantined specs are counted as quarantined, not failed.
+fn count_statuses(
+    test_cases: &[ExtractedTestCase],
+    quarantine: &QuarantineMatcher,
+    known_issues: &KnownIssueMatcher,
+) -> StatusCounts {
     use std::collections::HashMap;
 
-    // Group by full_title and find the status of the last attempt (highest retry_count)
-    let mut final_statuses: HashMap<&str, &str> = HashMap::new();
-    let mut max_retries: HashMap<&str, i32> = HashMap::new();
+    // Group by full_title and keep the last attempt (highest retry_count)
+    let mut final_attempts: HashMap<&str, &ExtractedTestCase> = HashMap::new();
 
     for tc in test_cases {
-        let current_max = *max_retries.get(tc.full_title.as_str()).unwrap_or(&-1);
+        let current_max = final_attempts
+            .get(tc.full_title.as_str())
+            .map_or(-1, |c| c.retry_count);
         if tc.retry_count > current_max {
-            max_retries.insert(&tc.full_title, tc.retry_count);
-            final_statuses.insert(&tc.full_title, &tc.status);
+            final_attempts.insert(&tc.full_title, tc);
         }
     }
 
-    let mut passed = 0;
-    let mut failed = 0;
-    let mut skipped = 0;
-    let mut flaky = 0;
-
-    for status in final_statuses.values() {
-        match *status {
-            "passed" => passed += 1,
-            "failed" | "timedOut" => failed += 1,
-            "skipped" => skipped += 1,
-            "flaky" => flaky += 1,
+    let mut counts = StatusCounts {
+        unique: final_attempts.len() as i32,
+        ..Default::default()
+    };
+
+    for (full_title, tc) in &final_attempts {
+        match tc.status.as_str() {
+            "passed" => counts.passed += 1,
+            "failed" | "timedOut" if quarantine.is_quarantined(full_title) => {
+                counts.quarantined += 1
+            }
+            "failed" | "timedOut" => {
+                counts.failed += 1;
+                if match_known_issue(known_issues, tc).is_some() {
+                    counts.known_issues += 1;
+                }
+            }
+            "skipped" => counts.skipped += 1,
+            "flaky" => counts.flaky += 1,
             _ => {}
         }
     }
 
-    let unique_count = final_statuses.len() as i32;
-    (passed, failed, skipped, flaky, unique_count)
+    counts
+}
+
+/// Get the key of the known issue a failed test case matches.
+fn match_known_issue(known_issues: &KnownIssueMatcher, tc: &ExtractedTestCase) -> Option<String> {
+    if !matches!(tc.status.as_str(), "failed" | "timedOut") {
+        return None;
+    }
+
+    let signature = signature_for_case(&tc.status, tc.error_message.as_deref());
+    known_issues
+        .match_failure(
+            &tc.full_title,
+            tc.error_message.as_deref(),
+            signature.as_deref(),
+        )
+        .map(str::to_string)
+}
+
+/// Get the GitHub repository a report was uploaded for.
+async fn report_repository(pool: &DbPool, report_id: Uuid) -> Option<String> {
+    match pool.get_report_by_id(report_id).await {
+        Ok(Some(report)) => GitHubMetadata::from_json(report.github_metadata.as_ref()).repository,
+        Ok(None) => None,
+        Err(e) => {
+            warn!("Failed to get report {} repository: {}", report_id, e);
+            None
+        }
+    }
 }
 
-/// Check if all jobs for a report are complete and update report status.
-async fn check_report_completion(
+/// Load the quarantine entries that apply to a report's repository and framework.
+///
+/// Failing to load the list is not fatal; extraction proceeds without quarantine.
+async fn load_quarantine(
     pool: &DbPool,
-    broadcaster: &EventBroadcaster,
-    report_id: Uuid,
-) -> Result<(), String> {
-    let report = pool
-        .get_report_by_id(report_id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Report {} not found", report_id))?;
+    repository: Option<&str>,
+    framework: &str,
+) -> QuarantineMatcher {
+    match crate::db::quarantined_tests::list(pool.connection(), None, None, false).await {
+        Ok(entries) => QuarantineMatcher::new(
+            &entries,
+            repository,
+            Framework::parse(&framework.to_lowercase()),
+        ),
+        Err(e) => {
+            warn!("Failed to load quarantine list: {}", e);
+            QuarantineMatcher::default()
+        }
+    }
+}
 
-    let completed = pool
-        .count_completed_jobs(report_id)
-        .await
-        .map_err(|e| e.to_string())?;
+/// Load the ownership mapping for a report's repository.
+///
+/// Without a repository or mapping, only owner annotations are used.
+async fn load_ownership(pool: &DbPool, repository: Option<&str>) -> OwnershipResolver {
+    let Some(repository) = repository else {
+        return OwnershipResolver::default();
+    };
 
-    let expected = report.expected_jobs as u64;
+    match crate::db::ownership_mappings::find_by_repository(pool.connection(), repository).await {
+        Ok(mapping) => OwnershipResolver::new(mapping.as_ref()),
+        Err(e) => {
+            warn!("Failed to load ownership mapping for {}: {}", repository, e);
+            OwnershipResolver::default()
+        }
+    }
+}
 
-    if completed >= expected {
-        pool.update_report_status(report_id, crate::models::ReportStatus::Complete)
-            .await
-            .map_err(|e| e.to_string())?;
+/// Load the enabled known issue rules that apply to a report's repository.
+///
+/// Failing to load the rules is not fatal; extraction proceeds without tagging.
+async fn load_known_issues(pool: &DbPool, repository: Option<&str>) -> KnownIssueMatcher {
+    match crate::db::known_issues::list(pool.connection(), repository, true).await {
+        Ok(issues) => KnownIssueMatcher::new(&issues, repository),
+        Err(e) => {
+            warn!("Failed to load known issues: {}", e);
+            KnownIssueMatcher::default()
+        }
+    }
+}
 
-        // Broadcast report_updated event for completion
-        let event = WsEventMessage::new(WsEvent::report_updated(report_id));
-        broadcaster.send(event);
+/// Mark a job failed, broadcast it and close its report if no jobs are left.
+async fn fail_job(
+    pool: &DbPool,
+    broadcaster: &EventBroadcaster,
+    features: &FeatureSettings,
+    job: &crate::entity::test_job::Model,
+    message: String,
+) {
+    if let Err(e) = pool
+        .update_job_status(job.id, JobStatus::Failed, Some(message))
+        .await
+    {
+        error!("Failed to update job status: {}", e);
+        return;
+    }
 
-        info!(
-            "Report {} marked as complete ({}/{} jobs)",
-            report_id, completed, expected
-        );
-    } else {
-        // Broadcast report_updated for progress update
-        let event = WsEventMessage::new(WsEvent::report_updated(report_id));
-        broadcaster.send(event);
+    broadcaster.send(WsEventMessage::new(WsEvent::job_updated(
+        job.test_report_id,
+        job.id,
+        JobStatus::Failed.as_str().to_string(),
+    )));
 
-        info!(
-            "Report {} progress: {}/{} jobs complete",
-            report_id, completed, expected
+    if let Err(e) = report_completion::check(pool, broadcaster, features, job.test_report_id).await
+    {
+        error!(
+            "Failed to check report {} completion: {}",
+            job.test_report_id, e
         );
     }
-
-    Ok(())
 }
@@ -0,0 +1,267 @@
+//! Failure signature computation for clustering test failures.
+//!
+//! Error messages are normalized by stripping the parts that vary between
+//! otherwise identical failures (numbers, UUIDs, timestamps, selectors,
+//! paths, stack frames, ANSI colors) and then hashed. Failures that share a
+//! root cause end up with the same signature, so a broken login flow that
+//! fails 200 tests shows up as a single group.
+
+use sha2::{Digest, Sha256};
+
+/// Number of meaningful message lines that contribute to the signature.
+/// Later lines are typically call logs or diffs that vary per test.
+const MAX_MESSAGE_LINES: usize = 3;
+
+/// Selectors longer than this are unlikely to be selectors at all.
+const MAX_SELECTOR_LENGTH: usize = 200;
+
+/// Compute the failure signature for an error message.
+///
+/// Returns `None` when nothing meaningful remains after normalization.
+pub fn compute_signature(error_message: &str) -> Option<String> {
+    let normalized = normalize_error(error_message);
+    if normalized.is_empty() {
+        return None;
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(normalized.as_bytes());
+    Some(hex::encode(hasher.finalize()))
+}
+
+/// Compute the failure signature for a test case, if it failed.
+pub fn signature_for_case(status: &str, error_message: Option<&str>) -> Option<String> {
+    match status {
+        "failed" | "timedOut" => error_message.and_then(compute_signature),
+        _ => None,
+    }
+}
+
+/// Normalize an error message into its stable, comparable form.
+pub fn normalize_error(error_message: &str) -> String {
+    let cleaned = strip_ansi(error_message);
+
+    let head = cleaned
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !is_stack_frame(line) && !is_code_frame(line))
+        .take(MAX_MESSAGE_LINES)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    mask_selectors(&head)
+        .split_whitespace()
+        .map(mask_token)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Remove ANSI escape sequences (Playwright colors its assertion output).
+pub fn strip_ansi(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            result.push(c);
+            continue;
+        }
+        // CSI sequence: ESC [ ... final byte in '@'..='~'
+        if chars.peek() == Some(&'[') {
+            chars.next();
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Stack frame lines (e.g. "at Context.<anonymous> (/app/spec.ts:12:5)").
+fn is_stack_frame(line: &str) -> bool {
+    line.starts_with("at ")
+}
+
+/// Source excerpt lines printed by Playwright (e.g. "> 12 |   await foo()").
+fn is_code_frame(line: &str) -> bool {
+    let rest = line.trim_start_matches('>').trim_start();
+    let rest = rest
+        .trim_start_matches(|c: char| c.is_ascii_digit())
+        .trim_start();
+    rest.starts_with('|')
+}
+
+/// Replace quoted selectors (e.g. `'#login-button'`, `` `[data-testid="x"]` ``).
+fn mask_selectors(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut result = String::with_capacity(input.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if matches!(c, '\'' | '"' | '`')
+            && let Some(len) = chars[i + 1..].iter().position(|&q| q == c)
+        {
+            let inner: String = chars[i + 1..i + 1 + len].iter().collect();
+            if looks_like_selector(&inner) {
+                result.push(c);
+                result.push_str("<selector>");
+                result.push(c);
+                i += len + 2;
+                continue;
+            }
+        }
+        result.push(c);
+        i += 1;
+    }
+
+    result
+}
+
+/// Heuristic for CSS/XPath/test-id selectors.
+fn looks_like_selector(s: &str) -> bool {
+    if s.is_empty() || s.len() > MAX_SELECTOR_LENGTH {
+        return false;
+    }
+
+    s.starts_with('#')
+        || s.starts_with('.')
+        || s.starts_with('[')
+        || s.starts_with("//")
+        || s.contains("data-test")
+        || s.contains(">>")
+        || s.contains(" > ")
+        || s.contains(":nth")
+        || s.contains('=')
+}
+
+/// Replace URLs, paths, UUIDs, hex ids and numbers within a single token.
+fn mask_token(token: &str) -> String {
+    if token.contains("://") {
+        return "<url>".to_string();
+    }
+    if token.contains('/') || token.contains('\\') {
+        return "<path>".to_string();
+    }
+
+    let chars: Vec<char> = token.chars().collect();
+    let mut result = String::with_capacity(token.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        if is_uuid_at(&chars, i) {
+            result.push_str("<uuid>");
+            i += 36;
+            continue;
+        }
+
+        if !chars[i].is_ascii_alphanumeric() {
+            result.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        let end = chars[i..]
+            .iter()
+            .position(|c| !c.is_ascii_alphanumeric())
+            .map_or(chars.len(), |p| i + p);
+        let word = &chars[i..end];
+        let has_digit = word.iter().any(char::is_ascii_digit);
+
+        if has_digit && word.len() >= 8 && word.iter().all(char::is_ascii_hexdigit) {
+            result.push_str("<hex>");
+        } else if has_digit {
+            // Collapse digit runs, keeping letters (timestamps become "<n>T<n>")
+            let mut in_digits = false;
+            for c in word {
+                if c.is_ascii_digit() {
+                    if !in_digits {
+                        result.push_str("<n>");
+                    }
+                    in_digits = true;
+                } else {
+                    result.push(*c);
+                    in_digits = false;
+                }
+            }
+        } else {
+            result.extend(word);
+        }
+        i = end;
+    }
+
+    result
+}
+
+/// Check for a hyphenated UUID starting at `start`.
+fn is_uuid_at(chars: &[char], start: usize) -> bool {
+    const HYPHENS: [usize; 4] = [8, 13, 18, 23];
+
+    if chars.len() < start + 36 {
+        return false;
+    }
+    if chars
+        .get(start + 36)
+        .is_some_and(|c| c.is_ascii_alphanumeric())
+    {
+        return false;
+    }
+
+    chars[start..start + 36].iter().enumerate().all(|(i, c)| {
+        if HYPHENS.contains(&i) {
+            *c == '-'
+        } else {
+            c.is_ascii_hexdigit()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_masks_volatile_parts() {
+        let normalized = normalize_error(
+            "Timed out 5000ms waiting for user 550e8400-e29b-41d4-a716-446655440000 at 2026-10-18T10:20:30.123Z",
+        );
+        assert_eq!(
+            normalized,
+            "Timed out <n>ms waiting for user <uuid> at <n>-<n>-<n>T<n>:<n>:<n>.<n>Z"
+        );
+    }
+
+    #[test]
+    fn test_same_root_cause_same_signature() {
+        let a = "Error: Timed out 5000ms waiting for locator('#login-button')\n    at /home/runner/work/app/login.spec.ts:12:5";
+        let b = "Error: Timed out 7500ms waiting for locator('[data-testid=\"submit\"]')\n    at /home/runner/work/app/signup.spec.ts:40:9";
+        assert_eq!(compute_signature(a), compute_signature(b));
+    }
+
+    #[test]
+    fn test_different_errors_different_signature() {
+        let a = "expected 'Login' to equal 'Logout'";
+        let b = "Network request failed";
+        assert_ne!(compute_signature(a), compute_signature(b));
+    }
+
+    #[test]
+    fn test_strips_ansi_paths_and_code_frames() {
+        let msg = "\u{1b}[2mexpect(\u{1b}[22mreceived\u{1b}[2m).toBe(expected)\n\n> 12 |   expect(x).toBe(1);\n     |             ^\nfailed to open /tmp/abc123/file.json";
+        assert_eq!(
+            normalize_error(msg),
+            "expect(received).toBe(expected) failed to open <path>"
+        );
+    }
+
+    #[test]
+    fn test_signature_only_for_failures() {
+        assert!(signature_for_case("passed", Some("boom")).is_none());
+        assert!(signature_for_case("failed", None).is_none());
+        assert!(signature_for_case("failed", Some("   ")).is_none());
+        assert!(signature_for_case("timedOut", Some("boom")).is_some());
+    }
+}
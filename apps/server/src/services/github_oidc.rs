@@ -404,7 +404,7 @@ mod tests {
     /// Verifies that iterating over parsed patterns finds the right match.
     #[test]
     fn test_comma_separated_repos_matching() {
-        let patterns = vec!["org/repo-a", "org/repo-b", "other-org/*"];
+        let patterns = ["org/repo-a", "org/repo-b", "other-org/*"];
 
         // Exact matches
         assert!(patterns.iter().any(|p| matches_pattern(p, "org/repo-a")));
@@ -0,0 +1,226 @@
+//! Idempotency keys for report registration.
+//!
+//! A retried CI step must not create a second report for the same run. The
+//! key comes from the `Idempotency-Key` header when the client sends one, and
+//! is otherwise derived from the workflow run identity: repository, workflow,
+//! run ID, run attempt, framework and labels, so reports split by label
+//! within one run stay apart. Registrations without a header or a run ID
+//! aren't deduplicated. Keys are scoped to the caller and the repository, so
+//! one caller can't replay another's key, and are stored hashed so arbitrary
+//! header values fit the index.
+
+use std::collections::BTreeMap;
+
+use sha2::{Digest, Sha256};
+
+use crate::models::{AuthenticatedCaller, Framework, GitHubMetadata};
+use crate::services::run_lineage::parse_run_attempt;
+use crate::services::text::non_empty;
+
+/// Longest accepted `Idempotency-Key` header value.
+pub const MAX_HEADER_KEY_LENGTH: usize = 255;
+
+/// Scope of a caller's keys: the repository of GitHub OIDC callers, whose
+/// key IDs vary by actor, and the API key otherwise.
+pub fn caller_scope(caller: &AuthenticatedCaller) -> String {
+    match caller.oidc_claims {
+        Some(ref claims) => format!("oidc:{}", claims.repository),
+        None => format!("key:{}", caller.key_id),
+    }
+}
+
+/// Compute the idempotency key of a report registration by a caller with
+/// `scope` (see [`caller_scope`]).
+///
+/// Returns `Ok(None)` when the registration can't be deduplicated, and an
+/// error message for an invalid header value.
+pub fn report_key(
+    header: Option<&str>,
+    scope: &str,
+    github_metadata: Option<&GitHubMetadata>,
+    framework: Framework,
+    labels: &BTreeMap<String, String>,
+) -> Result<Option<String>, String> {
+    let repository =
+        non_empty(github_metadata.and_then(|m| m.repository.as_deref())).unwrap_or_default();
+
+    if let Some(header) = header {
+        let header = header.trim();
+        if header.is_empty() || header.len() > MAX_HEADER_KEY_LENGTH {
+            return Err(format!(
+                "Idempotency-Key must be between 1 and {} characters",
+                MAX_HEADER_KEY_LENGTH
+            ));
+        }
+        return Ok(Some(hash(&["header", scope, &repository, header])));
+    }
+
+    let Some(metadata) = github_metadata else {
+        return Ok(None);
+    };
+    let Some(run_id) = non_empty(metadata.run_id.as_deref()) else {
+        return Ok(None);
+    };
+    if repository.is_empty() {
+        return Ok(None);
+    }
+
+    let workflow = non_empty(metadata.workflow.as_deref()).unwrap_or_default();
+    let run_attempt = parse_run_attempt(metadata.run_attempt.as_deref()).to_string();
+    let label_count = labels.len().to_string();
+    let mut parts = vec![
+        "run",
+        scope,
+        &repository,
+        &workflow,
+        &run_id,
+        &run_attempt,
+        framework.as_str(),
+        &label_count,
+    ];
+    // Sorted by the map
+    for (name, value) in labels {
+        parts.push(name);
+        parts.push(value);
+    }
+    Ok(Some(hash(&parts)))
+}
+
+/// Hash key parts, length-prefixed so different splits never collide.
+fn hash(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCOPE: &str = "key:ci";
+
+    fn metadata(run_attempt: Option<&str>) -> GitHubMetadata {
+        GitHubMetadata {
+            repository: Some("org/repo".to_string()),
+            workflow: Some("CI".to_string()),
+            run_id: Some("12345".to_string()),
+            run_attempt: run_attempt.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn run_key(metadata: &GitHubMetadata, framework: Framework) -> Option<String> {
+        report_key(None, SCOPE, Some(metadata), framework, &BTreeMap::new()).unwrap()
+    }
+
+    fn header_key(header: &str, scope: &str, metadata: Option<&GitHubMetadata>) -> Option<String> {
+        report_key(
+            Some(header),
+            scope,
+            metadata,
+            Framework::Playwright,
+            &BTreeMap::new(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_report_key_from_run() {
+        let key = run_key(&metadata(Some("1")), Framework::Playwright).unwrap();
+        assert_eq!(key.len(), 64);
+
+        // The first attempt is the default
+        let same = run_key(&metadata(None), Framework::Playwright);
+        assert_eq!(same.as_deref(), Some(key.as_str()));
+
+        let retry = run_key(&metadata(Some("2")), Framework::Playwright);
+        assert_ne!(retry.as_deref(), Some(key.as_str()));
+
+        let cypress = run_key(&metadata(Some("1")), Framework::Cypress);
+        assert_ne!(cypress.as_deref(), Some(key.as_str()));
+
+        let other_caller = report_key(
+            None,
+            "key:other",
+            Some(&metadata(None)),
+            Framework::Playwright,
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert_ne!(other_caller.as_deref(), Some(key.as_str()));
+    }
+
+    #[test]
+    fn test_report_key_separates_labels() {
+        let labels = |shard: &str| BTreeMap::from([("suite".to_string(), shard.to_string())]);
+        let key = |labels: &BTreeMap<String, String>| {
+            report_key(
+                None,
+                SCOPE,
+                Some(&metadata(None)),
+                Framework::Playwright,
+                labels,
+            )
+            .unwrap()
+        };
+
+        let unlabeled = key(&BTreeMap::new());
+        let smoke = key(&labels("smoke"));
+        assert_ne!(smoke, unlabeled);
+        assert_ne!(smoke, key(&labels("full")));
+        assert_eq!(smoke, key(&labels("smoke")));
+    }
+
+    #[test]
+    fn test_report_key_needs_run_identity() {
+        assert_eq!(
+            report_key(None, SCOPE, None, Framework::Playwright, &BTreeMap::new()),
+            Ok(None)
+        );
+
+        let no_run = GitHubMetadata {
+            run_id: Some(" ".to_string()),
+            ..metadata(None)
+        };
+        assert_eq!(run_key(&no_run, Framework::Playwright), None);
+
+        let no_repository = GitHubMetadata {
+            repository: None,
+            ..metadata(None)
+        };
+        assert_eq!(run_key(&no_repository, Framework::Playwright), None);
+    }
+
+    #[test]
+    fn test_report_key_from_header() {
+        let key = header_key("retry-1", SCOPE, Some(&metadata(None)));
+        assert!(key.is_some());
+        // Retries with different run metadata keep the key
+        assert_eq!(
+            key,
+            header_key("retry-1", SCOPE, Some(&metadata(Some("2"))))
+        );
+
+        // Scoped to the caller and the repository
+        assert_ne!(
+            key,
+            header_key("retry-1", "key:other", Some(&metadata(None)))
+        );
+        assert_ne!(key, header_key("retry-1", SCOPE, None));
+
+        let invalid = |header: &str| {
+            report_key(
+                Some(header),
+                SCOPE,
+                None,
+                Framework::Playwright,
+                &BTreeMap::new(),
+            )
+        };
+        assert!(invalid(" ").is_err());
+        assert!(invalid(&"k".repeat(MAX_HEADER_KEY_LENGTH + 1)).is_err());
+    }
+}
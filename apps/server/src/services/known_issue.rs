@@ -0,0 +1,212 @@
+//! Known issue matching for failed tests.
+//!
+//! A known issue rule matches a failure when every matcher it sets matches:
+//! the failure signature, a regular expression over the error message, and a
+//! title pattern where `*` stands for any sequence of characters, matched
+//! with or without the Playwright project prefix. Rules are evaluated in
+//! creation order and the first match wins.
+
+use regex::{Regex, RegexBuilder};
+use tracing::warn;
+
+use crate::models::KnownIssue;
+use crate::services::quarantine::matches_title_pattern;
+use crate::services::text::strip_project;
+
+/// Compiled size limit for error patterns, in bytes.
+const ERROR_PATTERN_SIZE_LIMIT: usize = 1024 * 1024;
+
+/// Compile an error message pattern.
+pub fn compile_error_pattern(pattern: &str) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .size_limit(ERROR_PATTERN_SIZE_LIMIT)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Debug)]
+struct CompiledRule {
+    issue_key: String,
+    failure_signature: Option<String>,
+    error_pattern: Option<Regex>,
+    title_pattern: Option<String>,
+}
+
+/// Matches failed tests against enabled known issue rules.
+#[derive(Debug, Default)]
+pub struct KnownIssueMatcher {
+    rules: Vec<CompiledRule>,
+}
+
+impl KnownIssueMatcher {
+    /// Build a matcher from the enabled rules that apply to a repository.
+    ///
+    /// Rules without a repository apply everywhere. Patterns are validated on
+    /// save, so a rule whose pattern no longer compiles is skipped.
+    pub fn new(issues: &[KnownIssue], repository: Option<&str>) -> Self {
+        let mut rules = Vec::new();
+        for issue in issues {
+            if !issue.enabled
+                || issue
+                    .repository
+                    .as_deref()
+                    .is_some_and(|r| Some(r) != repository)
+            {
+                continue;
+            }
+
+            let error_pattern = match issue.error_pattern.as_deref().map(compile_error_pattern) {
+                Some(Ok(regex)) => Some(regex),
+                Some(Err(e)) => {
+                    warn!("Skipping known issue {}: {}", issue.issue_key, e);
+                    continue;
+                }
+                None => None,
+            };
+
+            rules.push(CompiledRule {
+                issue_key: issue.issue_key.clone(),
+                failure_signature: issue.failure_signature.clone(),
+                error_pattern,
+                title_pattern: issue.title_pattern.clone(),
+            });
+        }
+        Self { rules }
+    }
+
+    /// Find the known issue a failure matches, returning its key.
+    pub fn match_failure(
+        &self,
+        full_title: &str,
+        error_message: Option<&str>,
+        failure_signature: Option<&str>,
+    ) -> Option<&str> {
+        let title = strip_project(full_title);
+        self.rules
+            .iter()
+            .find(|rule| {
+                rule.failure_signature
+                    .as_deref()
+                    .is_none_or(|s| Some(s) == failure_signature)
+                    && rule
+                        .error_pattern
+                        .as_ref()
+                        .is_none_or(|re| error_message.is_some_and(|m| re.is_match(m)))
+                    && rule.title_pattern.as_deref().is_none_or(|p| {
+                        matches_title_pattern(p, full_title) || matches_title_pattern(p, title)
+                    })
+            })
+            .map(|rule| rule.issue_key.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn issue(
+        key: &str,
+        failure_signature: Option<&str>,
+        error_pattern: Option<&str>,
+        title_pattern: Option<&str>,
+    ) -> KnownIssue {
+        KnownIssue {
+            id: Uuid::now_v7(),
+            issue_key: key.to_string(),
+            issue_url: None,
+            description: None,
+            repository: None,
+            failure_signature: failure_signature.map(str::to_string),
+            error_pattern: error_pattern.map(str::to_string),
+            title_pattern: title_pattern.map(str::to_string),
+            enabled: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_all_matchers_must_match() {
+        let matcher = KnownIssueMatcher::new(
+            &[issue(
+                "MM-1",
+                None,
+                Some(r"Timeout \d+ms exceeded"),
+                Some("Channels > *"),
+            )],
+            None,
+        );
+
+        assert_eq!(
+            matcher.match_failure(
+                "Channels > create",
+                Some("Error: Timeout 5000ms exceeded"),
+                None
+            ),
+            Some("MM-1")
+        );
+        assert_eq!(
+            matcher.match_failure("Search > find", Some("Timeout 5000ms exceeded"), None),
+            None
+        );
+        assert_eq!(
+            matcher.match_failure("Channels > create", Some("expected true"), None),
+            None
+        );
+        assert_eq!(matcher.match_failure("Channels > create", None, None), None);
+
+        // Playwright titles match without their project prefix
+        assert_eq!(
+            matcher.match_failure(
+                "[chromium] Channels > create",
+                Some("Timeout 5000ms exceeded"),
+                None
+            ),
+            Some("MM-1")
+        );
+    }
+
+    #[test]
+    fn test_signature_rule_and_first_match_wins() {
+        let matcher = KnownIssueMatcher::new(
+            &[
+                issue("MM-1", Some("abc"), None, None),
+                issue("MM-2", None, None, Some("*")),
+            ],
+            None,
+        );
+
+        assert_eq!(
+            matcher.match_failure("Any > test", Some("boom"), Some("abc")),
+            Some("MM-1")
+        );
+        assert_eq!(
+            matcher.match_failure("Any > test", Some("boom"), Some("def")),
+            Some("MM-2")
+        );
+    }
+
+    #[test]
+    fn test_scope_and_disabled_rules() {
+        let mut scoped = issue("MM-1", None, Some("boom"), None);
+        scoped.repository = Some("org/repo".to_string());
+        let mut disabled = issue("MM-2", None, Some("boom"), None);
+        disabled.enabled = false;
+        let issues = [scoped, disabled];
+
+        let matcher = KnownIssueMatcher::new(&issues, Some("org/repo"));
+        assert_eq!(matcher.match_failure("T", Some("boom"), None), Some("MM-1"));
+
+        let matcher = KnownIssueMatcher::new(&issues, Some("org/other"));
+        assert_eq!(matcher.match_failure("T", Some("boom"), None), None);
+    }
+
+    #[test]
+    fn test_compile_error_pattern() {
+        assert!(compile_error_pattern(r"^Error: .+$").is_ok());
+        assert!(compile_error_pattern(r"(unclosed").is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Report label validation and filter parsing.
+//!
+//! Labels are string key/value pairs (e.g. `db=mysql`, `edition=enterprise`)
+//! stored as a flat JSONB object and filtered by containment. Filters use
+//! `key:value` pairs separated by commas, so neither may contain a comma and
+//! keys may not contain a colon.
+
+use std::collections::BTreeMap;
+
+/// Maximum number of labels per report.
+pub const MAX_LABELS: usize = 20;
+
+/// Maximum label key length.
+pub const MAX_LABEL_KEY_LENGTH: usize = 64;
+
+/// Maximum label value length.
+pub const MAX_LABEL_VALUE_LENGTH: usize = 128;
+
+/// Maximum serialized size of `custom_metadata` in bytes.
+pub const MAX_CUSTOM_METADATA_SIZE: usize = 16 * 1024;
+
+fn valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_LABEL_KEY_LENGTH
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'))
+}
+
+fn valid_value(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_LABEL_VALUE_LENGTH
+        && !value.chars().any(|c| c == ',' || c.is_control())
+}
+
+/// Validate a label set.
+pub fn validate_labels(labels: &BTreeMap<String, String>) -> Result<(), String> {
+    if labels.len() > MAX_LABELS {
+        return Err(format!("At most {} labels are allowed", MAX_LABELS));
+    }
+
+    for (key, value) in labels {
+        if !valid_key(key) {
+            return Err(format!(
+                "Invalid label key '{}': use 1-{} letters, digits, '_', '-', '.' or '/'",
+                key, MAX_LABEL_KEY_LENGTH
+            ));
+        }
+        if !valid_value(value) {
+            return Err(format!(
+                "Invalid value for label '{}': 1-{} characters without commas",
+                key, MAX_LABEL_VALUE_LENGTH
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate a custom metadata document: a JSON object of bounded size.
+pub fn validate_custom_metadata(metadata: &serde_json::Value) -> Result<(), String> {
+    if !metadata.is_object() {
+        return Err("custom_metadata must be a JSON object".to_string());
+    }
+    if metadata.to_string().len() > MAX_CUSTOM_METADATA_SIZE {
+        return Err(format!(
+            "custom_metadata exceeds {} bytes",
+            MAX_CUSTOM_METADATA_SIZE
+        ));
+    }
+    Ok(())
+}
+
+/// Parse a `key:value[,key:value...]` label filter.
+///
+/// Reports must carry every listed label.
+pub fn parse_label_filter(filter: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut labels = BTreeMap::new();
+
+    for pair in filter.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = pair
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid label filter '{}': expected key:value", pair))?;
+        let (key, value) = (key.trim(), value.trim());
+        if let Some(existing) = labels.insert(key.to_string(), value.to_string())
+            && existing != value
+        {
+            return Err(format!("Label '{}' is filtered by two values", key));
+        }
+    }
+
+    validate_labels(&labels)?;
+    Ok(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_labels() {
+        let mut labels = BTreeMap::new();
+        labels.insert("db".to_string(), "mysql".to_string());
+        labels.insert("server.version".to_string(), "10.2.0".to_string());
+        assert!(validate_labels(&labels).is_ok());
+
+        labels.insert("bad key".to_string(), "x".to_string());
+        assert!(validate_labels(&labels).is_err());
+
+        let mut labels = BTreeMap::new();
+        labels.insert("flags".to_string(), "a,b".to_string());
+        assert!(validate_labels(&labels).is_err());
+
+        let many: BTreeMap<_, _> = (0..=MAX_LABELS)
+            .map(|i| (format!("k{}", i), "v".to_string()))
+            .collect();
+        assert!(validate_labels(&many).is_err());
+    }
+
+    #[test]
+    fn test_validate_custom_metadata() {
+        assert!(validate_custom_metadata(&serde_json::json!({"flags": ["a"]})).is_ok());
+        assert!(validate_custom_metadata(&serde_json::json!(["a"])).is_err());
+        let large = "x".repeat(MAX_CUSTOM_METADATA_SIZE);
+        assert!(validate_custom_metadata(&serde_json::json!({ "x": large })).is_err());
+    }
+
+    #[test]
+    fn test_parse_label_filter() {
+        let labels = parse_label_filter("db:mysql, edition:enterprise").unwrap();
+        assert_eq!(labels.get("db").map(String::as_str), Some("mysql"));
+        assert_eq!(
+            labels.get("edition").map(String::as_str),
+            Some("enterprise")
+        );
+
+        // Values may contain colons
+        let labels = parse_label_filter("server:host:8065").unwrap();
+        assert_eq!(labels.get("server").map(String::as_str), Some("host:8065"));
+
+        assert!(parse_label_filter("db").is_err());
+        assert!(parse_label_filter("db:").is_err());
+        assert!(parse_label_filter("db:mysql,db:postgres").is_err());
+        assert!(parse_label_filter("").unwrap().is_empty());
+    }
+}
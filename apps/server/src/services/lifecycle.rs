@@ -0,0 +1,207 @@
+//! Report, job and upload lifecycles as state machines.
+//!
+//! Reports go from `initializing` through `uploading` (and `processing`) to
+//! one of the final states `complete`, `failed` or `cancelled`; only an admin
+//! reopen moves a final report back to `uploading`. Jobs go from `pending`
+//! through `processing` to `complete`, or fail from either. Each upload
+//! (HTML, screenshots, JSON) starts from no status, can be restarted until it
+//! completes, and may still complete after timing out.
+//!
+//! The db layer only writes a status when the current one may transition to
+//! it, so concurrent requests can't skip or undo steps.
+
+use crate::models::{JobStatus, ReportStatus, UploadStatus};
+
+/// A status with a fixed set of allowed transitions.
+pub trait Lifecycle: Copy + Eq + Sized + 'static {
+    /// Every state.
+    const STATES: &'static [Self];
+
+    /// Name stored in the database.
+    fn name(self) -> &'static str;
+
+    /// Whether `self` may be followed by `next`.
+    fn can_transition_to(self, next: Self) -> bool;
+
+    /// Whether records start in this state (or, for uploads, may start
+    /// without any state).
+    fn is_initial(self) -> bool;
+
+    /// Whether no further transition is allowed.
+    fn is_final(self) -> bool {
+        !Self::STATES
+            .iter()
+            .any(|&next| self.can_transition_to(next))
+    }
+
+    /// Names of the states that may transition to `next`, for conditional
+    /// updates.
+    fn sources(next: Self) -> Vec<&'static str> {
+        Self::STATES
+            .iter()
+            .filter(|state| state.can_transition_to(next))
+            .map(|state| state.name())
+            .collect()
+    }
+
+    /// Names of the states that aren't final.
+    fn active() -> Vec<&'static str> {
+        Self::STATES
+            .iter()
+            .filter(|state| !state.is_final())
+            .map(|state| state.name())
+            .collect()
+    }
+}
+
+impl Lifecycle for ReportStatus {
+    const STATES: &'static [Self] = &[
+        Self::Initializing,
+        Self::Uploading,
+        Self::Processing,
+        Self::Complete,
+        Self::Failed,
+        Self::Cancelled,
+    ];
+
+    fn name(self) -> &'static str {
+        self.as_str()
+    }
+
+    fn can_transition_to(self, next: Self) -> bool {
+        use ReportStatus::*;
+        match (self, next) {
+            // Concurrent job inits may both move the report to uploading
+            (Initializing, Uploading | Processing) | (Uploading, Uploading | Processing) => true,
+            (Initializing | Uploading | Processing, Complete | Failed | Cancelled) => true,
+            _ => false,
+        }
+    }
+
+    fn is_initial(self) -> bool {
+        self == Self::Initializing
+    }
+}
+
+impl Lifecycle for JobStatus {
+    const STATES: &'static [Self] = &[
+        Self::Pending,
+        Self::Processing,
+        Self::Complete,
+        Self::Failed,
+    ];
+
+    fn name(self) -> &'static str {
+        self.as_str()
+    }
+
+    fn can_transition_to(self, next: Self) -> bool {
+        use JobStatus::*;
+        matches!(
+            (self, next),
+            (Pending, Processing) | (Pending | Processing, Failed) | (Processing, Complete)
+        )
+    }
+
+    fn is_initial(self) -> bool {
+        self == Self::Pending
+    }
+}
+
+impl Lifecycle for UploadStatus {
+    const STATES: &'static [Self] = &[Self::Started, Self::Completed, Self::Failed, Self::Timedout];
+
+    fn name(self) -> &'static str {
+        self.as_str()
+    }
+
+    fn can_transition_to(self, next: Self) -> bool {
+        use UploadStatus::*;
+        match (self, next) {
+            // Clients re-init to retry an upload
+            (Started | Failed | Timedout, Started) => true,
+            // Files that arrive after the timeout still count
+            (Started | Timedout, Completed) => true,
+            (Started, Failed | Timedout) => true,
+            _ => false,
+        }
+    }
+
+    fn is_initial(self) -> bool {
+        self == Self::Started
+    }
+}
+
+/// Report state of a report reopened by an admin.
+pub const REOPENED_REPORT_STATUS: ReportStatus = ReportStatus::Uploading;
+
+/// Quote state names as a list for raw SQL, e.g. `('pending', 'processing')`.
+pub fn sql_list(names: &[&str]) -> String {
+    let quoted: Vec<String> = names.iter().map(|name| format!("'{}'", name)).collect();
+    format!("({})", quoted.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lifecycle() {
+        use ReportStatus::*;
+        assert!(Initializing.can_transition_to(Uploading));
+        assert!(Uploading.can_transition_to(Uploading));
+        assert!(Uploading.can_transition_to(Cancelled));
+        assert!(!Uploading.can_transition_to(Initializing));
+        assert!(!Complete.can_transition_to(Failed));
+        assert!(!Cancelled.can_transition_to(Uploading));
+
+        assert!(Complete.is_final() && Failed.is_final() && Cancelled.is_final());
+        assert!(!Processing.is_final());
+        assert_eq!(
+            ReportStatus::sources(Complete),
+            vec!["initializing", "uploading", "processing"]
+        );
+        assert_eq!(
+            ReportStatus::active(),
+            vec!["initializing", "uploading", "processing"]
+        );
+    }
+
+    #[test]
+    fn test_job_lifecycle() {
+        use JobStatus::*;
+        assert!(Pending.can_transition_to(Processing));
+        assert!(Processing.can_transition_to(Complete));
+        // Extraction can't overwrite a failed job
+        assert!(!Failed.can_transition_to(Complete));
+        assert!(!Pending.can_transition_to(Complete));
+        assert!(!Processing.can_transition_to(Processing));
+
+        assert_eq!(JobStatus::sources(Failed), vec!["pending", "processing"]);
+        assert_eq!(JobStatus::active(), vec!["pending", "processing"]);
+    }
+
+    #[test]
+    fn test_upload_lifecycle() {
+        use UploadStatus::*;
+        assert!(Started.is_initial());
+        assert!(Timedout.can_transition_to(Started));
+        assert!(Timedout.can_transition_to(Completed));
+        assert!(!Completed.can_transition_to(Started));
+        assert!(!Completed.can_transition_to(Completed));
+        assert!(Completed.is_final());
+
+        assert_eq!(
+            UploadStatus::sources(Started),
+            vec!["started", "failed", "timedout"]
+        );
+    }
+
+    #[test]
+    fn test_sql_list() {
+        assert_eq!(
+            sql_list(&JobStatus::sources(JobStatus::Failed)),
+            "('pending', 'processing')"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Environment matrix: test statuses across browser × OS × project × device.
+//!
+//! Each job reports its environment; for Playwright reports, projects come
+//! from the `[project] title` prefix of full titles, so the same test in
+//! different projects lines up in one row. When several jobs of a cell ran
+//! the same test (e.g. shards), the worst status counts.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::models::{EnvironmentCell, EnvironmentMetadata, Framework, MatrixTest};
+use crate::services::text::split_project;
+
+/// Final status of a test in one job.
+#[derive(Debug, Clone)]
+pub struct MatrixEntry<'a> {
+    pub environment: &'a EnvironmentMetadata,
+    pub full_title: &'a str,
+    pub status: &'a str,
+}
+
+/// Split a full title into its environment cell and the project-less title.
+///
+/// Only Playwright titles carry a project prefix; other frameworks keep
+/// bracketed titles as they are.
+fn locate<'a>(framework: Framework, entry: &MatrixEntry<'a>) -> (EnvironmentCell, &'a str) {
+    let (project, title) = match framework {
+        Framework::Playwright => split_project(entry.full_title),
+        _ => (None, entry.full_title),
+    };
+
+    let cell = EnvironmentCell {
+        browser: entry.environment.browser.clone(),
+        os: entry.environment.os.clone(),
+        project: project.map(str::to_string),
+        device: entry.environment.device.clone(),
+    };
+    (cell, title)
+}
+
+fn is_failure(status: &str) -> bool {
+    matches!(status, "failed" | "timedOut")
+}
+
+/// Rank statuses so the worst one wins within a cell.
+fn severity(status: &str) -> u8 {
+    match status {
+        "failed" | "timedOut" => 4,
+        "flaky" => 3,
+        "passed" => 2,
+        "skipped" => 1,
+        _ => 0,
+    }
+}
+
+/// Build the matrix for a report of `framework`: sorted cells and one row
+/// per test.
+pub fn build_matrix(
+    framework: Framework,
+    entries: &[MatrixEntry<'_>],
+) -> (Vec<EnvironmentCell>, Vec<MatrixTest>) {
+    let mut cells = BTreeSet::new();
+    let mut by_test: BTreeMap<&str, BTreeMap<EnvironmentCell, &str>> = BTreeMap::new();
+
+    for entry in entries {
+        let (cell, title) = locate(framework, entry);
+        cells.insert(cell.clone());
+        let status = by_test
+            .entry(title)
+            .or_default()
+            .entry(cell)
+            .or_insert(entry.status);
+        if severity(entry.status) > severity(status) {
+            *status = entry.status;
+        }
+    }
+
+    let cells: Vec<EnvironmentCell> = cells.into_iter().collect();
+
+    let mut tests: Vec<MatrixTest> = by_test
+        .into_iter()
+        .map(|(title, statuses)| {
+            let failed_cells = statuses.values().filter(|s| is_failure(s)).count() as i32;
+            let passed_elsewhere = statuses.values().any(|s| matches!(*s, "passed" | "flaky"));
+
+            MatrixTest {
+                title: title.to_string(),
+                statuses: cells
+                    .iter()
+                    .map(|c| statuses.get(c).map(|s| s.to_string()))
+                    .collect(),
+                failed_cells,
+                single_cell_failure: failed_cells == 1 && passed_elsewhere,
+            }
+        })
+        .collect();
+
+    // Stable sort keeps title order within each group
+    tests.sort_by_key(|t| !t.single_cell_failure);
+
+    (cells, tests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(browser: &str, os: &str) -> EnvironmentMetadata {
+        EnvironmentMetadata {
+            browser: Some(browser.to_string()),
+            os: Some(os.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_build_matrix() {
+        let chrome = env("chrome", "linux");
+        let firefox = env("firefox", "linux");
+        let entry = |environment, full_title, status| MatrixEntry {
+            environment,
+            full_title,
+            status,
+        };
+
+        let (cells, tests) = build_matrix(
+            Framework::Playwright,
+            &[
+                entry(&chrome, "[chromium] Login > works", "passed"),
+                entry(&chrome, "[webkit] Login > works", "failed"),
+                entry(&firefox, "[chromium] Login > works", "passed"),
+                entry(&chrome, "[chromium] Cart > adds", "failed"),
+                entry(&chrome, "[chromium] Cart > adds", "passed"),
+                entry(&firefox, "[chromium] Cart > adds", "failed"),
+                entry(&chrome, "[webkit] Search > finds", "skipped"),
+            ],
+        );
+
+        assert_eq!(cells.len(), 3);
+        assert_eq!(cells[0].browser.as_deref(), Some("chrome"));
+        assert_eq!(cells[0].project.as_deref(), Some("chromium"));
+        assert_eq!(cells[1].project.as_deref(), Some("webkit"));
+        assert_eq!(cells[2].browser.as_deref(), Some("firefox"));
+
+        let titles: Vec<_> = tests.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["Login > works", "Cart > adds", "Search > finds"]
+        );
+
+        assert!(tests[0].single_cell_failure);
+        assert_eq!(
+            tests[0].statuses,
+            vec![
+                Some("passed".to_string()),
+                Some("failed".to_string()),
+                Some("passed".to_string())
+            ]
+        );
+
+        // Worst status within a cell; failing everywhere it ran is not isolated
+        assert_eq!(tests[1].failed_cells, 2);
+        assert!(!tests[1].single_cell_failure);
+        assert_eq!(tests[2].statuses[0], None);
+    }
+
+    #[test]
+    fn test_build_matrix_keeps_brackets_for_other_frameworks() {
+        let chrome = env("chrome", "linux");
+        let entries = [MatrixEntry {
+            environment: &chrome,
+            full_title: "[MM-T123] Login > works",
+            status: "passed",
+        }];
+
+        let (cells, tests) = build_matrix(Framework::Cypress, &entries);
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].project, None);
+        assert_eq!(tests[0].title, "[MM-T123] Login > works");
+    }
+}
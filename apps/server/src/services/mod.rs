@@ -4,10 +4,32 @@ pub mod api_key;
 pub mod auth_admin;
 pub mod event_broadcaster;
 pub mod extraction;
+pub mod failure_signature;
 pub mod github_oauth;
 pub mod github_oidc;
+pub mod idempotency;
+pub mod known_issue;
+pub mod labels;
+pub mod lifecycle;
+pub mod matrix;
 pub mod oidc_policy;
+pub mod ownership;
+pub mod pipeline;
+pub mod quarantine;
+pub mod readiness;
+pub mod report_bundle;
+pub mod report_completion;
+pub mod rerun;
+pub mod results_export;
+pub mod retention;
+pub mod run_lineage;
+pub mod search;
+pub mod sharding;
 pub mod storage;
+pub mod text;
+pub mod triage;
+pub mod upload_sweeper;
+pub mod zip_stream;
 
 pub use auth_admin::configure_routes as configure_auth_routes;
 pub use event_broadcaster::EventBroadcaster;
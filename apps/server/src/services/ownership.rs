@@ -0,0 +1,294 @@
+//! Test ownership resolution.
+//!
+//! Owners are resolved in priority order: an explicit owner annotation on the
+//! test, then the first matching title rule, then the last matching
+//! CODEOWNERS line for the suite's file path. When a CODEOWNERS line lists
+//! several owners, the first one is used.
+
+use crate::models::{OwnershipMapping, TitleOwnerRule};
+use crate::services::quarantine::matches_title_pattern;
+use crate::services::text::strip_project;
+
+/// A parsed CODEOWNERS line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeOwnersRule {
+    pub pattern: String,
+    /// Owners in declaration order; empty means explicitly unowned.
+    pub owners: Vec<String>,
+}
+
+/// Parse CODEOWNERS content.
+///
+/// Returns an error naming the first line that uses syntax CODEOWNERS does not
+/// support (negation and character ranges).
+pub fn parse_codeowners(content: &str) -> Result<Vec<CodeOwnersRule>, String> {
+    let mut rules = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        let Some(pattern) = tokens.next() else {
+            continue;
+        };
+
+        if pattern.starts_with('!') || pattern.contains('[') {
+            return Err(format!(
+                "line {}: unsupported pattern '{}' (negation and ranges are not allowed)",
+                idx + 1,
+                pattern
+            ));
+        }
+
+        let owners = tokens
+            .take_while(|t| !t.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+
+        rules.push(CodeOwnersRule {
+            pattern: pattern.to_string(),
+            owners,
+        });
+    }
+
+    Ok(rules)
+}
+
+/// Check whether a CODEOWNERS pattern matches a file path.
+///
+/// Follows gitignore-style semantics: a leading or inner `/` anchors the
+/// pattern to the repository root, a trailing `/` matches directories only,
+/// `*` stays within a path segment and `**` spans segments. A pattern that
+/// names a directory also matches everything below it.
+pub fn matches_codeowners_pattern(pattern: &str, path: &str) -> bool {
+    let path = path.trim_start_matches("./").trim_start_matches('/');
+
+    let (anchored, pattern) = match pattern.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (pattern.trim_end_matches('/').contains('/'), pattern),
+    };
+    let dir_only = pattern.ends_with('/');
+    let pattern = pattern.trim_end_matches('/');
+    if pattern.is_empty() {
+        return false;
+    }
+
+    let pattern: Vec<char> = if anchored {
+        pattern.chars().collect()
+    } else {
+        format!("**/{}", pattern).chars().collect()
+    };
+    let path_chars: Vec<char> = path.chars().collect();
+
+    if !dir_only && glob_match(&pattern, &path_chars) {
+        return true;
+    }
+
+    // `docs/*` covers files directly inside docs/, not nested directories
+    if pattern.ends_with(&['/', '*']) && !pattern.ends_with(&['*', '*']) {
+        return false;
+    }
+
+    // Match any ancestor directory
+    path_chars
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| **c == '/')
+        .any(|(i, _)| glob_match(&pattern, &path_chars[..i]))
+}
+
+/// Glob matching where `*` and `?` don't cross `/` and `**` does.
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            let rest = &pattern[2..];
+            // `**/` may also match zero directories
+            if rest.first() == Some(&'/') && glob_match(&rest[1..], text) {
+                return true;
+            }
+            (0..=text.len()).any(|i| glob_match(rest, &text[i..]))
+        }
+        Some('*') => {
+            let rest = &pattern[1..];
+            for i in 0..=text.len() {
+                if glob_match(rest, &text[i..]) {
+                    return true;
+                }
+                if text.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => !text.is_empty() && text[0] != '/' && glob_match(&pattern[1..], &text[1..]),
+        Some(c) => text.first() == Some(c) && glob_match(&pattern[1..], &text[1..]),
+    }
+}
+
+/// Resolves test owners from a repository's ownership mapping.
+#[derive(Debug, Default)]
+pub struct OwnershipResolver {
+    title_rules: Vec<TitleOwnerRule>,
+    codeowners: Vec<CodeOwnersRule>,
+}
+
+impl OwnershipResolver {
+    /// Build a resolver from a stored mapping.
+    ///
+    /// CODEOWNERS content is validated on save, so a parse failure here leaves
+    /// only title rules and annotations in effect.
+    pub fn new(mapping: Option<&OwnershipMapping>) -> Self {
+        let Some(mapping) = mapping else {
+            return Self::default();
+        };
+
+        Self {
+            title_rules: mapping.title_rules.clone(),
+            codeowners: mapping
+                .codeowners
+                .as_deref()
+                .and_then(|c| parse_codeowners(c).ok())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Resolve the owner of a test.
+    pub fn resolve(
+        &self,
+        annotated_owner: Option<&str>,
+        full_title: &str,
+        file_path: Option<&str>,
+    ) -> Option<String> {
+        if let Some(owner) = annotated_owner.map(str::trim).filter(|o| !o.is_empty()) {
+            return Some(owner.to_string());
+        }
+
+        // Rules may be written against the title without its project prefix
+        let title = strip_project(full_title);
+        if let Some(rule) = self.title_rules.iter().find(|r| {
+            matches_title_pattern(&r.pattern, full_title)
+                || matches_title_pattern(&r.pattern, title)
+        }) {
+            return Some(rule.owner.clone());
+        }
+
+        let path = file_path?;
+        self.codeowners
+            .iter()
+            .rev()
+            .find(|r| matches_codeowners_pattern(&r.pattern, path))
+            .and_then(|r| r.owners.first().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    const CODEOWNERS: &str = "
+# Default owners
+*                       @org/qa
+*.cy.ts                 @org/web # trailing comment
+/e2e/checkout/          @org/payments @alice
+docs/*                  @org/docs
+apps/**/login.spec.ts   @org/auth
+/e2e/legacy/
+";
+
+    fn mapping() -> OwnershipMapping {
+        OwnershipMapping {
+            repository: "org/repo".to_string(),
+            codeowners: Some(CODEOWNERS.to_string()),
+            title_rules: vec![TitleOwnerRule {
+                pattern: "Search > *".to_string(),
+                owner: "@org/search".to_string(),
+            }],
+            updated_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_parse_codeowners() {
+        let rules = parse_codeowners(CODEOWNERS).unwrap();
+        assert_eq!(rules.len(), 6);
+        assert_eq!(rules[1].owners, vec!["@org/web"]);
+        assert_eq!(rules[2].owners, vec!["@org/payments", "@alice"]);
+        assert!(rules[5].owners.is_empty());
+
+        assert!(parse_codeowners("!*.md @org/docs").is_err());
+        assert!(parse_codeowners("*.[ch] @org/c").is_err());
+    }
+
+    #[test]
+    fn test_matches_codeowners_pattern() {
+        assert!(matches_codeowners_pattern("*", "a/b/c.ts"));
+        assert!(matches_codeowners_pattern("*.ts", "a/b/c.ts"));
+        assert!(!matches_codeowners_pattern("*.ts", "a/b/c.js"));
+        assert!(matches_codeowners_pattern("/e2e/", "e2e/login.spec.ts"));
+        assert!(!matches_codeowners_pattern(
+            "/e2e/",
+            "apps/e2e/login.spec.ts"
+        ));
+        assert!(matches_codeowners_pattern("e2e/", "apps/e2e/login.spec.ts"));
+        assert!(matches_codeowners_pattern("e2e", "apps/e2e/login.spec.ts"));
+        assert!(matches_codeowners_pattern("docs/*", "docs/a.md"));
+        assert!(!matches_codeowners_pattern("docs/*", "docs/nested/a.md"));
+        assert!(matches_codeowners_pattern("docs/**", "docs/nested/a.md"));
+        assert!(matches_codeowners_pattern(
+            "**/login.spec.ts",
+            "login.spec.ts"
+        ));
+        assert!(matches_codeowners_pattern(
+            "apps/**/login.spec.ts",
+            "apps/web/e2e/login.spec.ts"
+        ));
+        assert!(matches_codeowners_pattern("/e2e/a?.ts", "./e2e/ab.ts"));
+    }
+
+    #[test]
+    fn test_resolve_priority() {
+        let resolver = OwnershipResolver::new(Some(&mapping()));
+
+        assert_eq!(
+            resolver.resolve(
+                Some("@org/annotated"),
+                "Search > finds",
+                Some("e2e/x.cy.ts")
+            ),
+            Some("@org/annotated".to_string())
+        );
+        assert_eq!(
+            resolver.resolve(None, "Search > finds", Some("e2e/x.cy.ts")),
+            Some("@org/search".to_string())
+        );
+        assert_eq!(
+            resolver.resolve(None, "Cart > adds", Some("e2e/checkout/cart.cy.ts")),
+            Some("@org/payments".to_string())
+        );
+        assert_eq!(
+            resolver.resolve(None, "Cart > adds", Some("e2e/cart.cy.ts")),
+            Some("@org/web".to_string())
+        );
+        assert_eq!(
+            resolver.resolve(None, "Old > test", Some("e2e/legacy/old.cy.ts")),
+            None
+        );
+        // Playwright titles match rules without their project prefix
+        assert_eq!(
+            resolver.resolve(None, "[chromium] Search > finds", None),
+            Some("@org/search".to_string())
+        );
+        assert_eq!(resolver.resolve(None, "Cart > adds", None), None);
+        assert_eq!(
+            OwnershipResolver::default().resolve(None, "Cart > adds", Some("a.ts")),
+            None
+        );
+    }
+}
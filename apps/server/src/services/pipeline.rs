@@ -0,0 +1,354 @@
+//! Pipeline runs: reports of one CI run grouped across frameworks.
+//!
+//! A report joins a pipeline run on registration. The pipeline is picked by
+//! the explicit group key when the client sends one, and otherwise by the
+//! repository, commit SHA and run ID of its GitHub metadata; reports without
+//! either don't join a pipeline. Explicit group keys aren't scoped to a
+//! repository, so one key can group reports from several repositories.
+//!
+//! Status and test stats are aggregated from the member reports when read.
+//! Re-running a workflow registers new reports in the same pipeline; a report
+//! replaced by a later attempt with the same workflow, framework and labels
+//! is superseded and left out of the aggregates.
+
+use std::collections::HashMap;
+
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::entity::pipeline_run;
+use crate::error::AppResult;
+use crate::models::{
+    Framework, GitHubMetadata, PipelineReport, PipelineStatus, PipelineSummary, ReportOutcome,
+    ReportStatus, TestStats, WsEvent, WsEventMessage, labels_from_json,
+};
+use crate::services::EventBroadcaster;
+use crate::services::lifecycle::Lifecycle;
+use crate::services::run_lineage::parse_run_attempt;
+use crate::services::text::non_empty;
+
+/// Longest accepted group key.
+pub const MAX_GROUP_KEY_LENGTH: usize = 255;
+
+/// Identity of the pipeline run a report joins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineIdentity {
+    /// Unique key of the pipeline run.
+    pub key: String,
+    pub group_key: Option<String>,
+    pub repository: Option<String>,
+    pub git_ref: Option<String>,
+    pub sha: Option<String>,
+    pub run_id: Option<String>,
+}
+
+/// Pick the pipeline run of a report registration.
+///
+/// Returns `Ok(None)` when the report can't be grouped, and an error message
+/// for an invalid group key.
+pub fn identity(
+    group_key: Option<&str>,
+    github_metadata: Option<&GitHubMetadata>,
+) -> Result<Option<PipelineIdentity>, String> {
+    let metadata = github_metadata.cloned().unwrap_or_default();
+    let repository = non_empty(metadata.repository.as_deref());
+    let sha = non_empty(metadata.sha.as_deref()).map(|s| s.to_lowercase());
+    let run_id = non_empty(metadata.run_id.as_deref());
+
+    let key = match group_key {
+        Some(group_key) => {
+            let group_key = group_key.trim();
+            if group_key.is_empty() || group_key.len() > MAX_GROUP_KEY_LENGTH {
+                return Err(format!(
+                    "group_key must be between 1 and {} characters",
+                    MAX_GROUP_KEY_LENGTH
+                ));
+            }
+            format!("group:{}", group_key)
+        }
+        None => match (repository.as_deref(), sha.as_deref(), run_id.as_deref()) {
+            // Repository names can't contain ':'
+            (Some(repository), Some(sha), Some(run_id)) => {
+                format!("run:{}:{}:{}", repository, sha, run_id)
+            }
+            _ => return Ok(None),
+        },
+    };
+
+    Ok(Some(PipelineIdentity {
+        key,
+        group_key: group_key.map(|k| k.trim().to_string()),
+        repository,
+        git_ref: non_empty(metadata.git_ref.as_deref()),
+        sha,
+        run_id,
+    }))
+}
+
+/// Flag reports replaced by a later attempt with the same workflow,
+/// framework and labels.
+pub fn mark_superseded(reports: &mut [PipelineReport]) {
+    let key = |r: &PipelineReport| (r.workflow.clone(), r.framework.as_str(), r.labels.clone());
+
+    let mut latest: HashMap<_, i32> = HashMap::new();
+    for r in reports.iter() {
+        let attempt = latest.entry(key(r)).or_default();
+        *attempt = (*attempt).max(r.run_attempt);
+    }
+
+    for r in reports.iter_mut() {
+        r.superseded = latest.get(&key(r)).is_some_and(|&a| r.run_attempt < a);
+    }
+}
+
+/// Aggregate status of the reports that aren't superseded.
+pub fn status(reports: &[PipelineReport]) -> PipelineStatus {
+    let current: Vec<_> = reports.iter().filter(|r| !r.superseded).collect();
+
+    if current.iter().any(|r| !r.status.is_final()) {
+        PipelineStatus::Running
+    } else if current.iter().any(|r| {
+        r.status == ReportStatus::Failed
+            || r.test_stats
+                .as_ref()
+                .is_some_and(|s| s.outcome == ReportOutcome::Failed)
+    }) {
+        PipelineStatus::Failed
+    } else if current.iter().any(|r| r.status == ReportStatus::Cancelled) {
+        PipelineStatus::Cancelled
+    } else {
+        PipelineStatus::Passed
+    }
+}
+
+/// Sum the test stats of the reports that aren't superseded.
+///
+/// Reports run in parallel, so there's no combined wall clock time.
+pub fn sum_stats(reports: &[PipelineReport]) -> TestStats {
+    add_stats(
+        reports
+            .iter()
+            .filter(|r| !r.superseded)
+            .filter_map(|r| r.test_stats.as_ref()),
+    )
+}
+
+/// Sum test stats across reports.
+pub fn add_stats<'a>(all_stats: impl IntoIterator<Item = &'a TestStats>) -> TestStats {
+    let mut sum = TestStats::default();
+    for stats in all_stats {
+        sum.total += stats.total;
+        sum.passed += stats.passed;
+        sum.failed += stats.failed;
+        sum.skipped += stats.skipped;
+        sum.flaky += stats.flaky;
+        sum.quarantined += stats.quarantined;
+        sum.known_issues += stats.known_issues;
+        if let Some(duration_ms) = stats.duration_ms {
+            sum.duration_ms = Some(sum.duration_ms.unwrap_or(0) + duration_ms);
+        }
+    }
+    sum.outcome = ReportOutcome::from_counts(sum.failed, sum.quarantined);
+    sum
+}
+
+/// Summarize a pipeline run from its member reports.
+pub fn summarize(pipeline: &pipeline_run::Model, reports: &[PipelineReport]) -> PipelineSummary {
+    let current = reports.iter().filter(|r| !r.superseded);
+    PipelineSummary {
+        id: pipeline.id,
+        group_key: pipeline.group_key.clone(),
+        repository: pipeline.repository.clone(),
+        git_ref: pipeline.git_ref.clone(),
+        sha: pipeline.sha.clone(),
+        run_id: pipeline.run_id.clone(),
+        status: status(reports),
+        report_count: current.clone().count() as i32,
+        reports_closed: current.filter(|r| r.status.is_final()).count() as i32,
+        test_stats: sum_stats(reports),
+        created_at: pipeline.created_at,
+        updated_at: pipeline.updated_at,
+    }
+}
+
+/// Load the live member reports of pipeline runs with their test stats,
+/// oldest first.
+pub async fn load_reports(
+    pool: &DbPool,
+    pipeline_ids: &[Uuid],
+) -> AppResult<HashMap<Uuid, Vec<PipelineReport>>> {
+    let reports = pool.get_reports_by_pipeline_ids(pipeline_ids).await?;
+    let ids: Vec<_> = reports.iter().map(|r| r.id).collect();
+    let mut stats = pool.get_test_stats_by_report_ids(&ids).await?;
+
+    let mut by_pipeline: HashMap<Uuid, Vec<PipelineReport>> = HashMap::new();
+    for r in reports {
+        let Some(pipeline_id) = r.pipeline_id else {
+            continue;
+        };
+        let metadata = GitHubMetadata::from_json(r.github_metadata.as_ref());
+        by_pipeline
+            .entry(pipeline_id)
+            .or_default()
+            .push(PipelineReport {
+                report_id: r.id,
+                framework: Framework::parse(&r.framework).unwrap_or(Framework::Playwright),
+                status: ReportStatus::parse(&r.status).unwrap_or(ReportStatus::Initializing),
+                workflow: metadata.workflow,
+                run_attempt: parse_run_attempt(metadata.run_attempt.as_deref()),
+                labels: labels_from_json(&r.labels),
+                superseded: false,
+                test_stats: stats.remove(&r.id),
+                created_at: r.created_at,
+            });
+    }
+
+    for reports in by_pipeline.values_mut() {
+        mark_superseded(reports);
+    }
+
+    Ok(by_pipeline)
+}
+
+/// Load a pipeline run and its live member reports.
+///
+/// Returns `None` if the pipeline doesn't exist or all its reports were
+/// deleted.
+pub async fn load_pipeline(
+    pool: &DbPool,
+    pipeline_id: Uuid,
+) -> AppResult<Option<(pipeline_run::Model, Vec<PipelineReport>)>> {
+    let Some(pipeline) = pool.get_pipeline_run(pipeline_id).await? else {
+        return Ok(None);
+    };
+    let reports = load_reports(pool, &[pipeline_id])
+        .await?
+        .remove(&pipeline_id)
+        .unwrap_or_default();
+    if reports.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some((pipeline, reports)))
+}
+
+/// Broadcast a pipeline run's aggregated progress.
+///
+/// Called when a member report registers or closes. Failures are logged, not
+/// returned, since the report change itself succeeded.
+pub async fn broadcast_progress(pool: &DbPool, broadcaster: &EventBroadcaster, pipeline_id: Uuid) {
+    match load_pipeline(pool, pipeline_id).await {
+        Ok(Some((pipeline, reports))) => {
+            let summary = summarize(&pipeline, &reports);
+            broadcaster.send(WsEventMessage::new(WsEvent::pipeline_updated(&summary)));
+        }
+        Ok(None) => {}
+        Err(e) => warn!(
+            "Failed to broadcast progress of pipeline {}: {}",
+            pipeline_id, e
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn report(
+        framework: Framework,
+        run_attempt: i32,
+        status: ReportStatus,
+        failed: i32,
+    ) -> PipelineReport {
+        PipelineReport {
+            report_id: Uuid::now_v7(),
+            framework,
+            status,
+            workflow: Some("E2E".to_string()),
+            run_attempt,
+            labels: BTreeMap::new(),
+            superseded: false,
+            test_stats: Some(TestStats {
+                total: 10,
+                passed: 10 - failed,
+                failed,
+                outcome: ReportOutcome::from_counts(failed, 0),
+                duration_ms: Some(1000),
+                ..Default::default()
+            }),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_identity() {
+        let metadata = GitHubMetadata {
+            repository: Some("org/repo".to_string()),
+            sha: Some("ABC123".to_string()),
+            run_id: Some("42".to_string()),
+            ..Default::default()
+        };
+        let run = identity(None, Some(&metadata)).unwrap().unwrap();
+        assert_eq!(run.key, "run:org/repo:abc123:42");
+        assert_eq!(run.group_key, None);
+
+        let group = identity(Some(" release-10.5 "), Some(&metadata))
+            .unwrap()
+            .unwrap();
+        assert_eq!(group.key, "group:release-10.5");
+        assert_eq!(group.group_key.as_deref(), Some("release-10.5"));
+        assert_eq!(group.repository.as_deref(), Some("org/repo"));
+
+        let no_sha = GitHubMetadata {
+            sha: None,
+            ..metadata
+        };
+        assert_eq!(identity(None, Some(&no_sha)), Ok(None));
+        assert_eq!(identity(None, None), Ok(None));
+        assert!(identity(Some(""), None).is_err());
+    }
+
+    #[test]
+    fn test_superseded_attempts() {
+        let mut reports = vec![
+            report(Framework::Playwright, 1, ReportStatus::Complete, 2),
+            report(Framework::Cypress, 1, ReportStatus::Complete, 0),
+            report(Framework::Playwright, 2, ReportStatus::Complete, 0),
+        ];
+        mark_superseded(&mut reports);
+        assert!(reports[0].superseded);
+        assert!(!reports[1].superseded);
+        assert!(!reports[2].superseded);
+
+        // The failing first attempt no longer counts
+        assert_eq!(status(&reports), PipelineStatus::Passed);
+        let stats = sum_stats(&reports);
+        assert_eq!(stats.total, 20);
+        assert_eq!(stats.failed, 0);
+        assert_eq!(stats.duration_ms, Some(2000));
+    }
+
+    #[test]
+    fn test_status() {
+        let passed = report(Framework::Playwright, 1, ReportStatus::Complete, 0);
+        let failing = report(Framework::Cypress, 1, ReportStatus::Complete, 1);
+        let running = report(Framework::Detox, 1, ReportStatus::Uploading, 0);
+        let cancelled = report(Framework::Detox, 1, ReportStatus::Cancelled, 0);
+
+        assert_eq!(
+            status(&[passed.clone(), failing.clone(), running]),
+            PipelineStatus::Running
+        );
+        assert_eq!(
+            status(&[passed.clone(), failing, cancelled.clone()]),
+            PipelineStatus::Failed
+        );
+        assert_eq!(
+            status(&[passed.clone(), cancelled]),
+            PipelineStatus::Cancelled
+        );
+        assert_eq!(status(&[passed]), PipelineStatus::Passed);
+    }
+}
@@ -0,0 +1,240 @@
+//! Quarantine matching for known-flaky tests.
+//!
+//! A test is identified by its fingerprint (SHA-256 of the full title without
+//! the Playwright project prefix) or matched by a title pattern where `*`
+//! stands for any sequence of characters. Failures of matching tests are
+//! counted as quarantined instead of failed.
+
+use std::collections::HashSet;
+
+use sha2::{Digest, Sha256};
+
+use crate::models::{Framework, QuarantineEntry};
+use crate::services::text::{escape_regex, strip_project};
+
+/// Compute the fingerprint of a test from its full title.
+///
+/// The `[project] ` prefix of Playwright titles is ignored, so a test has
+/// the same fingerprint in every project and as a bare title.
+pub fn test_fingerprint(full_title: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(strip_project(full_title.trim()).as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Check whether a fingerprint is a lowercase SHA-256 hex digest.
+pub fn is_valid_fingerprint(fingerprint: &str) -> bool {
+    fingerprint.len() == 64
+        && fingerprint
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+}
+
+/// Check if a title matches a pattern (`*` matches any sequence, including empty).
+pub fn matches_title_pattern(pattern: &str, title: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == title;
+    }
+
+    let first = parts[0];
+    let last = parts[parts.len() - 1];
+    if title.len() < first.len() + last.len() || !title.starts_with(first) || !title.ends_with(last)
+    {
+        return false;
+    }
+
+    // Match the middle parts greedily left to right within the remaining span
+    let mut rest = &title[first.len()..title.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+
+    true
+}
+
+/// Translate a title pattern into a regular expression for Playwright's
+/// `--grep`/`--grep-invert`.
+///
+/// Playwright matches against the project, file and title path joined by
+/// spaces, so the expression is unanchored and has no project prefix.
+pub fn pattern_to_regex(pattern: &str) -> String {
+    strip_project(pattern)
+        .split('*')
+        .map(escape_regex)
+        .collect::<Vec<_>>()
+        .join(".*")
+        .trim_start_matches(".*")
+        .trim_end_matches(".*")
+        .to_string()
+}
+
+/// Translate an exact title into a regular expression for Playwright's
+/// `--grep`/`--grep-invert`; see [`pattern_to_regex`].
+pub fn title_to_regex(title: &str) -> String {
+    escape_regex(strip_project(title))
+}
+
+/// Matches test titles against a set of active quarantine entries.
+#[derive(Debug, Default)]
+pub struct QuarantineMatcher {
+    fingerprints: HashSet<String>,
+    patterns: Vec<String>,
+}
+
+impl QuarantineMatcher {
+    /// Build a matcher from the entries that apply to a repository and framework.
+    ///
+    /// Entries without a repository or framework apply everywhere.
+    pub fn new(
+        entries: &[QuarantineEntry],
+        repository: Option<&str>,
+        framework: Option<Framework>,
+    ) -> Self {
+        let mut matcher = Self::default();
+        for entry in entries {
+            if entry
+                .repository
+                .as_deref()
+                .is_some_and(|r| Some(r) != repository)
+                || entry.framework.is_some_and(|f| Some(f) != framework)
+            {
+                continue;
+            }
+            if let Some(ref fingerprint) = entry.fingerprint {
+                matcher.fingerprints.insert(fingerprint.clone());
+            }
+            if let Some(ref pattern) = entry.title_pattern {
+                matcher.patterns.push(pattern.clone());
+            }
+        }
+        matcher
+    }
+
+    /// Check whether a test is quarantined.
+    ///
+    /// Patterns match the full title or the title without its project prefix.
+    pub fn is_quarantined(&self, full_title: &str) -> bool {
+        if self.fingerprints.is_empty() && self.patterns.is_empty() {
+            return false;
+        }
+
+        let title = strip_project(full_title);
+        self.fingerprints.contains(&test_fingerprint(full_title))
+            || self.patterns.iter().any(|pattern| {
+                matches_title_pattern(pattern, full_title) || matches_title_pattern(pattern, title)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn entry(fingerprint: Option<&str>, title_pattern: Option<&str>) -> QuarantineEntry {
+        QuarantineEntry {
+            id: Uuid::now_v7(),
+            repository: None,
+            framework: None,
+            fingerprint: fingerprint.map(str::to_string),
+            full_title: None,
+            title_pattern: title_pattern.map(str::to_string),
+            reason: "flaky".to_string(),
+            owner: "team".to_string(),
+            expires_at: None,
+            created_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let a = test_fingerprint("Login > signs in");
+        assert_eq!(a, test_fingerprint("  Login > signs in "));
+        assert_ne!(a, test_fingerprint("Login > signs out"));
+        // Playwright project prefixes don't change the fingerprint
+        assert_eq!(a, test_fingerprint("[chromium] Login > signs in"));
+        assert_eq!(a, test_fingerprint("[Mobile Safari] Login > signs in"));
+        assert!(is_valid_fingerprint(&a));
+        assert!(!is_valid_fingerprint("abc"));
+    }
+
+    #[test]
+    fn test_matches_title_pattern() {
+        assert!(matches_title_pattern(
+            "Login > signs in",
+            "Login > signs in"
+        ));
+        assert!(!matches_title_pattern(
+            "Login > signs in",
+            "Login > signs out"
+        ));
+        assert!(matches_title_pattern("Login > *", "Login > signs in"));
+        assert!(matches_title_pattern(
+            "* [webkit]",
+            "Cart > adds item [webkit]"
+        ));
+        assert!(matches_title_pattern(
+            "*checkout*",
+            "Cart > checkout > pays"
+        ));
+        assert!(matches_title_pattern("a*b*c", "abc"));
+        assert!(!matches_title_pattern("a*b*c", "acb"));
+        assert!(!matches_title_pattern("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn test_pattern_to_regex() {
+        assert_eq!(pattern_to_regex("Login > *"), "Login > ");
+        assert_eq!(pattern_to_regex("*checkout*pays"), "checkout.*pays");
+        assert_eq!(pattern_to_regex("a (b) [c]"), r"a \(b\) \[c\]");
+        assert_eq!(pattern_to_regex("[webkit] Login > *"), "Login > ");
+        assert_eq!(title_to_regex("a * b"), r"a \* b");
+        assert_eq!(title_to_regex("[chromium] a.b"), r"a\.b");
+    }
+
+    #[test]
+    fn test_matcher() {
+        let fingerprint = test_fingerprint("Cart > adds item");
+        let matcher = QuarantineMatcher::new(
+            &[
+                entry(Some(&fingerprint), None),
+                entry(None, Some("Search > *")),
+            ],
+            Some("org/repo"),
+            Some(Framework::Playwright),
+        );
+
+        assert!(matcher.is_quarantined("Cart > adds item"));
+        assert!(matcher.is_quarantined("Search > filters results"));
+        assert!(matcher.is_quarantined("[chromium] Cart > adds item"));
+        assert!(matcher.is_quarantined("[webkit] Search > filters results"));
+        assert!(!matcher.is_quarantined("Cart > removes item"));
+        assert!(!QuarantineMatcher::default().is_quarantined("Cart > adds item"));
+    }
+
+    #[test]
+    fn test_matcher_scope() {
+        let mut scoped = entry(None, Some("Cart > *"));
+        scoped.repository = Some("org/shop".to_string());
+        scoped.framework = Some(Framework::Cypress);
+        let entries = [scoped];
+
+        let matcher = QuarantineMatcher::new(&entries, Some("org/shop"), Some(Framework::Cypress));
+        assert!(matcher.is_quarantined("Cart > adds item"));
+
+        let matcher = QuarantineMatcher::new(&entries, Some("org/other"), Some(Framework::Cypress));
+        assert!(!matcher.is_quarantined("Cart > adds item"));
+
+        let matcher = QuarantineMatcher::new(&entries, None, Some(Framework::Cypress));
+        assert!(!matcher.is_quarantined("Cart > adds item"));
+
+        let matcher = QuarantineMatcher::new(&entries, Some("org/shop"), Some(Framework::Detox));
+        assert!(!matcher.is_quarantined("Cart > adds item"));
+    }
+}
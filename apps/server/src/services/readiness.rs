@@ -0,0 +1,285 @@
+//! Release readiness: go/no-go across the latest reports on a release ref.
+//!
+//! Each repository, framework, workflow and label combination contributes its
+//! latest complete report. Its failures are compared with the same
+//! combination on the previous release ref: failures that didn't fail there
+//! are new and block the release, while failures that already failed, tests
+//! still quarantined and failures matching a tracked known issue only call
+//! for caution. Quarantined or known-issue failures whose quarantine entry or
+//! known issue rule is gone count as regular failures again.
+
+use std::collections::{BTreeMap, HashMap};
+
+use uuid::Uuid;
+
+use crate::db::test_results::RunTestStatus;
+use crate::models::{Framework, KnownIssueFailure, ReadinessDecision, TestStats};
+use crate::services::run_lineage::is_failure;
+
+/// Split a release ref into its branch prefix and version.
+///
+/// `refs/heads/release-10.5` gives `("release-", [10, 5])`. Returns `None`
+/// for refs without a version.
+pub fn parse_release_ref(git_ref: &str) -> Option<(&str, Vec<u32>)> {
+    let name = git_ref
+        .strip_prefix("refs/heads/")
+        .or_else(|| git_ref.strip_prefix("refs/tags/"))
+        .unwrap_or(git_ref);
+    let start = name.find(|c: char| c.is_ascii_digit())?;
+    let version: Vec<u32> = name[start..]
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect();
+    if version.is_empty() {
+        return None;
+    }
+    Some((&name[..start], version))
+}
+
+/// Pick the release ref preceding `git_ref`: the highest lower version with
+/// the same branch prefix.
+pub fn previous_release_ref(git_ref: &str, candidates: &[String]) -> Option<String> {
+    let (prefix, version) = parse_release_ref(git_ref)?;
+    candidates
+        .iter()
+        .filter_map(|c| {
+            let (p, v) = parse_release_ref(c)?;
+            (p == prefix && v < version).then_some((v, c))
+        })
+        .max()
+        .map(|(_, c)| c.clone())
+}
+
+/// Failed tests of a report by full title, with the known issue each
+/// matched.
+#[derive(Debug, Default)]
+pub struct ReportFailures {
+    pub failed: BTreeMap<String, Option<String>>,
+    /// Failures of tests quarantined at extraction time.
+    pub quarantined: BTreeMap<String, Option<String>>,
+}
+
+impl ReportFailures {
+    fn contains(&self, full_title: &str) -> bool {
+        self.failed.contains_key(full_title) || self.quarantined.contains_key(full_title)
+    }
+}
+
+/// Group failed tests by report. A test failing in any job of a report
+/// counts as failed.
+pub fn failures_by_report(statuses: &[RunTestStatus]) -> HashMap<Uuid, ReportFailures> {
+    let mut by_report: HashMap<Uuid, ReportFailures> = HashMap::new();
+    for s in statuses.iter().filter(|s| is_failure(&s.status)) {
+        let failures = by_report.entry(s.report_id).or_default();
+        let bucket = if s.quarantined {
+            &mut failures.quarantined
+        } else {
+            &mut failures.failed
+        };
+        bucket.insert(s.full_title.clone(), s.known_issue_key.clone());
+    }
+    by_report
+}
+
+/// Failures of one combination, classified against the previous release.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Assessment {
+    pub new_failures: Vec<String>,
+    pub existing_failures: Vec<String>,
+    pub quarantined_failures: Vec<String>,
+    pub known_issue_failures: Vec<KnownIssueFailure>,
+}
+
+impl Assessment {
+    pub fn decision(&self) -> ReadinessDecision {
+        if !self.new_failures.is_empty() {
+            ReadinessDecision::NoGo
+        } else if !self.existing_failures.is_empty()
+            || !self.quarantined_failures.is_empty()
+            || !self.known_issue_failures.is_empty()
+        {
+            ReadinessDecision::Caution
+        } else {
+            ReadinessDecision::Go
+        }
+    }
+}
+
+/// Classify a report's failures.
+///
+/// `previous` holds the failures of the same combination on the previous
+/// release ref; without it every unexplained failure is new. `open_issues`
+/// maps the keys of enabled known issues to their links.
+pub fn assess(
+    current: &ReportFailures,
+    previous: Option<&ReportFailures>,
+    is_quarantined: impl Fn(&str) -> bool,
+    open_issues: &HashMap<String, Option<String>>,
+) -> Assessment {
+    let mut assessment = Assessment::default();
+
+    let mut failed: Vec<(&String, &Option<String>)> = current.failed.iter().collect();
+    for (title, key) in &current.quarantined {
+        if is_quarantined(title) {
+            assessment.quarantined_failures.push(title.clone());
+        } else {
+            failed.push((title, key));
+        }
+    }
+    failed.sort();
+
+    for (title, key) in failed {
+        if let Some((issue_key, issue_url)) =
+            key.as_ref().and_then(|k| Some((k, open_issues.get(k)?)))
+        {
+            assessment.known_issue_failures.push(KnownIssueFailure {
+                full_title: title.clone(),
+                issue_key: issue_key.clone(),
+                issue_url: issue_url.clone(),
+            });
+        } else if previous.is_some_and(|p| p.contains(title)) {
+            assessment.existing_failures.push(title.clone());
+        } else {
+            assessment.new_failures.push(title.clone());
+        }
+    }
+
+    assessment
+}
+
+/// Passed tests out of all tests.
+pub fn pass_rate(stats: &TestStats) -> f64 {
+    if stats.total > 0 {
+        stats.passed as f64 / stats.total as f64
+    } else {
+        0.0
+    }
+}
+
+/// Short description of a combination for decision reasons, e.g.
+/// `playwright (E2E, db=postgres) in org/repo`.
+pub fn describe(
+    repository: Option<&str>,
+    framework: Framework,
+    workflow: Option<&str>,
+    labels: &BTreeMap<String, String>,
+) -> String {
+    let details: Vec<String> = workflow
+        .map(str::to_string)
+        .into_iter()
+        .chain(labels.iter().map(|(k, v)| format!("{}={}", k, v)))
+        .collect();
+
+    let mut description = framework.as_str().to_string();
+    if !details.is_empty() {
+        description.push_str(&format!(" ({})", details.join(", ")));
+    }
+    if let Some(repository) = repository {
+        description.push_str(&format!(" in {}", repository));
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(title: &str, status: &str, quarantined: bool, key: Option<&str>) -> RunTestStatus {
+        RunTestStatus {
+            report_id: Uuid::nil(),
+            full_title: title.to_string(),
+            status: status.to_string(),
+            quarantined,
+            known_issue_key: key.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_previous_release_ref() {
+        assert_eq!(
+            parse_release_ref("refs/heads/release-10.5"),
+            Some(("release-", vec![10, 5]))
+        );
+        assert_eq!(parse_release_ref("main"), None);
+
+        let candidates = vec![
+            "refs/heads/release-10.3".to_string(),
+            "refs/heads/release-10.4".to_string(),
+            "release-10.5".to_string(),
+            "release-9.11".to_string(),
+            "hotfix-10.4".to_string(),
+        ];
+        assert_eq!(
+            previous_release_ref("release-10.5", &candidates).as_deref(),
+            Some("refs/heads/release-10.4")
+        );
+        assert_eq!(
+            previous_release_ref("release-10.0", &candidates).as_deref(),
+            Some("release-9.11")
+        );
+        assert_eq!(previous_release_ref("release-9.0", &candidates), None);
+        assert_eq!(previous_release_ref("main", &candidates), None);
+    }
+
+    #[test]
+    fn test_assess() {
+        let current = failures_by_report(&[
+            status("new", "failed", false, None),
+            status("old", "timedOut", false, None),
+            status("tracked", "failed", false, Some("MM-1")),
+            status("closed issue", "failed", false, Some("MM-2")),
+            status("quarantined", "failed", true, None),
+            status("unquarantined", "failed", true, None),
+            status("passing", "passed", false, None),
+        ])
+        .remove(&Uuid::nil())
+        .unwrap();
+        let previous = failures_by_report(&[status("old", "failed", false, None)])
+            .remove(&Uuid::nil())
+            .unwrap();
+        let open_issues = HashMap::from([("MM-1".to_string(), None)]);
+
+        let assessment = assess(
+            &current,
+            Some(&previous),
+            |title| title == "quarantined",
+            &open_issues,
+        );
+        assert_eq!(
+            assessment.new_failures,
+            vec!["closed issue", "new", "unquarantined"]
+        );
+        assert_eq!(assessment.existing_failures, vec!["old"]);
+        assert_eq!(assessment.quarantined_failures, vec!["quarantined"]);
+        assert_eq!(assessment.known_issue_failures[0].issue_key, "MM-1");
+        assert_eq!(assessment.decision(), ReadinessDecision::NoGo);
+
+        // Without a previous release every unexplained failure is new
+        let assessment = assess(&previous, None, |_| false, &open_issues);
+        assert_eq!(assessment.new_failures, vec!["old"]);
+
+        let assessment = assess(&previous, Some(&previous), |_| false, &open_issues);
+        assert_eq!(assessment.decision(), ReadinessDecision::Caution);
+
+        let assessment = assess(&ReportFailures::default(), None, |_| false, &open_issues);
+        assert_eq!(assessment.decision(), ReadinessDecision::Go);
+    }
+
+    #[test]
+    fn test_describe() {
+        let labels = BTreeMap::from([("db".to_string(), "postgres".to_string())]);
+        assert_eq!(
+            describe(
+                Some("org/repo"),
+                Framework::Playwright,
+                Some("E2E"),
+                &labels
+            ),
+            "playwright (E2E, db=postgres) in org/repo"
+        );
+        assert_eq!(
+            describe(None, Framework::Detox, None, &BTreeMap::new()),
+            "detox"
+        );
+    }
+}
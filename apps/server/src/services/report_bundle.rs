@@ -0,0 +1,830 @@
+//! Report bundles: a report with its results and stored files in one zip.
+//!
+//! Bundles move reports between instances or hand them to someone without
+//! access to the server. Layout:
+//!
+//! - `report.json`: format, version, export time, the report row and its
+//!   OIDC claims
+//! - `duration_regressions.json`: the report's duration regressions
+//! - `jobs.json`: the report's jobs
+//! - `jobs/{job_id}/suites.json`, `cases.json`, `triage.json`, `files.json`:
+//!   a job's test suites, test cases, their triage records and HTML, JSON and
+//!   screenshot file records
+//! - `artifacts/{s3_key}`: every stored file of the report
+//!
+//! Rows keep all their columns, so importing recreates the report as it was,
+//! under fresh IDs. Storage keys embed the report and job IDs and are
+//! rewritten to match. Registration-only state (idempotency key, pipeline
+//! run) isn't carried over.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use actix_web::web::{self, Bytes};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value as JsonValue;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::db::report_bundles::{JobRows, ReportRows};
+use crate::entity::{
+    duration_regression, html_file, json_file, report_oidc_claim, screenshot, test_case, test_job,
+    test_report, test_suite, triage_record,
+};
+use crate::error::{AppError, AppResult};
+use crate::models::ImportReportResponse;
+use crate::services::Storage;
+use crate::services::zip_stream::ZipStream;
+
+/// Identifies report bundles in `report.json`.
+pub const BUNDLE_FORMAT: &str = "tsio-report-bundle";
+/// Current bundle layout version; imports reject other versions.
+pub const BUNDLE_VERSION: u32 = 1;
+/// Maximum size of an uploaded bundle, and of any file in it (2 GiB).
+pub const MAX_BUNDLE_SIZE: u64 = 2 * 1024 * 1024 * 1024;
+/// Maximum size of a JSON entry, which is read into memory to parse
+/// (256 MiB). Stored files are spooled to disk instead.
+const MAX_JSON_ENTRY_SIZE: u64 = 256 * 1024 * 1024;
+
+const MANIFEST_ENTRY: &str = "report.json";
+const REGRESSIONS_ENTRY: &str = "duration_regressions.json";
+const JOBS_ENTRY: &str = "jobs.json";
+const ARTIFACTS_DIR: &str = "artifacts/";
+
+/// Chunks buffered between the bundle writer and the HTTP response.
+const EXPORT_BUFFER: usize = 16;
+
+/// A temporary file, removed when dropped.
+pub struct TempFile(pub PathBuf);
+
+impl TempFile {
+    /// A fresh path in the system temp directory.
+    pub fn new(prefix: &str, extension: &str) -> Self {
+        Self(std::env::temp_dir().join(format!("{}-{}{}", prefix, Uuid::now_v7(), extension)))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Contents of `report.json`.
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    format: String,
+    version: u32,
+    exported_at: DateTime<Utc>,
+    report: test_report::Model,
+    oidc_claims: Option<report_oidc_claim::Model>,
+}
+
+/// Contents of a job's `files.json`.
+#[derive(Default, Serialize, Deserialize)]
+struct JobFiles {
+    html_files: Vec<html_file::Model>,
+    json_files: Vec<json_file::Model>,
+    screenshots: Vec<screenshot::Model>,
+}
+
+fn job_entry(job_id: Uuid, name: &str) -> String {
+    format!("jobs/{}/{}", job_id, name)
+}
+
+/// File name for a report's bundle download.
+pub fn bundle_filename(report_id: Uuid) -> String {
+    format!("report-{}.zip", report_id)
+}
+
+// ============================================================================
+// Export
+// ============================================================================
+
+/// Stream the bundle of a report.
+///
+/// Rows are loaded one job at a time and stored files one at a time, so
+/// memory stays bounded by the largest job or file. A failure midway ends the
+/// stream with an error, leaving the client with a truncated archive.
+pub fn export(
+    pool: DbPool,
+    storage: Storage,
+    report: test_report::Model,
+) -> impl futures_util::Stream<Item = Result<Bytes, std::io::Error>> {
+    let (tx, rx) = mpsc::channel(EXPORT_BUFFER);
+
+    actix_web::rt::spawn(async move {
+        let report_id = report.id;
+        let mut writer = BundleWriter {
+            zip: ZipStream::new(Utc::now()),
+            tx,
+        };
+        match write_bundle(&pool, &storage, report, &mut writer).await {
+            Ok(artifacts) => info!(
+                "Report {} exported with {} stored files",
+                report_id, artifacts
+            ),
+            Err(_) if writer.tx.is_closed() => {
+                info!("Report {} export cancelled by the client", report_id)
+            }
+            Err(e) => {
+                warn!("Failed to export report {}: {}", report_id, e);
+                let _ = writer.tx.send(Err(std::io::Error::other(e))).await;
+            }
+        }
+    });
+
+    futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    })
+}
+
+/// Write the bundle entries; returns the number of stored files.
+async fn write_bundle(
+    pool: &DbPool,
+    storage: &Storage,
+    report: test_report::Model,
+    writer: &mut BundleWriter,
+) -> AppResult<usize> {
+    let report_id = report.id;
+    let report_rows = pool.get_report_rows(report_id).await?;
+    let jobs = pool.get_jobs_by_report_id(report_id).await?;
+
+    writer
+        .add_json(
+            MANIFEST_ENTRY,
+            &Manifest {
+                format: BUNDLE_FORMAT.to_string(),
+                version: BUNDLE_VERSION,
+                exported_at: Utc::now(),
+                report,
+                oidc_claims: report_rows.oidc_claims,
+            },
+        )
+        .await?;
+    writer
+        .add_json(REGRESSIONS_ENTRY, &report_rows.duration_regressions)
+        .await?;
+    writer.add_json(JOBS_ENTRY, &jobs).await?;
+
+    for job in &jobs {
+        let rows = pool.get_job_rows(job.id).await?;
+        writer
+            .add_json(&job_entry(job.id, "suites.json"), &rows.suites)
+            .await?;
+        writer
+            .add_json(&job_entry(job.id, "cases.json"), &rows.cases)
+            .await?;
+        writer
+            .add_json(&job_entry(job.id, "triage.json"), &rows.triage_records)
+            .await?;
+        writer
+            .add_json(
+                &job_entry(job.id, "files.json"),
+                &JobFiles {
+                    html_files: rows.html_files,
+                    json_files: rows.json_files,
+                    screenshots: rows.screenshots,
+                },
+            )
+            .await?;
+    }
+
+    let prefix = format!("{}/", Storage::report_key_prefix(&report_id.to_string()));
+    let keys = storage.list(&prefix).await?;
+    for key in &keys {
+        let (data, _) = storage.get(key).await?;
+        writer
+            .add(&format!("{}{}", ARTIFACTS_DIR, key), data)
+            .await?;
+    }
+
+    writer.finish().await?;
+    Ok(keys.len())
+}
+
+/// Sends zip entries to the response stream as they're added.
+struct BundleWriter {
+    zip: ZipStream,
+    tx: mpsc::Sender<Result<Bytes, std::io::Error>>,
+}
+
+impl BundleWriter {
+    async fn add(&mut self, name: &str, data: Vec<u8>) -> AppResult<()> {
+        let header = self.zip.add(name, &data);
+        self.send(header).await?;
+        self.send(data).await
+    }
+
+    async fn add_json<T: Serialize>(&mut self, name: &str, value: &T) -> AppResult<()> {
+        let data = serde_json::to_vec(value)
+            .map_err(|e| AppError::InvalidInput(format!("Failed to serialize {}: {}", name, e)))?;
+        self.add(name, data).await
+    }
+
+    async fn finish(&mut self) -> AppResult<()> {
+        let trailer = self.zip.finish();
+        self.send(trailer).await
+    }
+
+    async fn send(&self, chunk: Vec<u8>) -> AppResult<()> {
+        self.tx
+            .send(Ok(Bytes::from(chunk)))
+            .await
+            .map_err(|_| AppError::Storage("Export stream closed".to_string()))
+    }
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// Rewrites the IDs of an imported report and the storage keys embedding
+/// them.
+struct IdMap {
+    source_report_id: Uuid,
+    report_id: Uuid,
+    jobs: HashMap<Uuid, Uuid>,
+    suites: HashMap<Uuid, Uuid>,
+    cases: HashMap<Uuid, Uuid>,
+}
+
+impl IdMap {
+    /// Rewrite a key under `reports/{report_id}[/jobs/{job_id}]`.
+    ///
+    /// Returns `None` for keys outside the bundled report, which an import
+    /// must not write to.
+    fn rekey(&self, key: &str) -> Option<String> {
+        let mut segments = key.split('/');
+        if segments.next()? != "reports" || segments.next()? != self.source_report_id.to_string() {
+            return None;
+        }
+        let rest: Vec<&str> = segments.collect();
+        if rest.iter().any(|s| s.is_empty() || *s == "." || *s == "..") {
+            return None;
+        }
+
+        let report_id = self.report_id.to_string();
+        let (mut key, rest) = match rest.as_slice() {
+            ["jobs", job_id, rest @ ..] => {
+                let job_id = self.jobs.get(&Uuid::parse_str(job_id).ok()?)?;
+                (
+                    Storage::job_key_prefix(&report_id, &job_id.to_string()),
+                    rest,
+                )
+            }
+            rest => (Storage::report_key_prefix(&report_id), rest),
+        };
+        for segment in rest {
+            key.push('/');
+            key.push_str(segment);
+        }
+        Some(key)
+    }
+
+    fn job(&self, id: Uuid) -> AppResult<Uuid> {
+        lookup(&self.jobs, id, "job")
+    }
+
+    fn suite(&self, id: Uuid) -> AppResult<Uuid> {
+        lookup(&self.suites, id, "test suite")
+    }
+
+    fn key(&self, key: &str) -> AppResult<String> {
+        self.rekey(key).ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "Bundle references {} outside report {}",
+                key, self.source_report_id
+            ))
+        })
+    }
+
+    /// Rewrite the storage keys of test case attachments.
+    fn attachments(&self, attachments: Option<JsonValue>) -> AppResult<Option<JsonValue>> {
+        let Some(mut attachments) = attachments else {
+            return Ok(None);
+        };
+        if let Some(items) = attachments.as_array_mut() {
+            for item in items {
+                if let Some(key) = item.get("s3_key").and_then(JsonValue::as_str) {
+                    item["s3_key"] = JsonValue::String(self.key(key)?);
+                }
+            }
+        }
+        Ok(Some(attachments))
+    }
+}
+
+fn lookup(ids: &HashMap<Uuid, Uuid>, id: Uuid, what: &str) -> AppResult<Uuid> {
+    ids.get(&id)
+        .copied()
+        .ok_or_else(|| AppError::InvalidInput(format!("Bundle references unknown {} {}", what, id)))
+}
+
+/// Fresh time-ordered IDs, assigned in the order of the original IDs so
+/// listings ordered by ID keep their order.
+fn fresh_ids(ids: impl Iterator<Item = Uuid>) -> HashMap<Uuid, Uuid> {
+    let mut ids: Vec<Uuid> = ids.collect();
+    ids.sort();
+    ids.dedup();
+    ids.into_iter().map(|id| (id, Uuid::now_v7())).collect()
+}
+
+/// A report read from a bundle.
+struct Bundle {
+    report: test_report::Model,
+    report_rows: ReportRows,
+    jobs: Vec<test_job::Model>,
+    /// Rows of each job, in the order of `jobs`.
+    rows: Vec<JobRows>,
+}
+
+/// Give a bundled report fresh IDs and point its rows and storage keys at
+/// them.
+fn remap(bundle: Bundle) -> AppResult<(Bundle, IdMap)> {
+    let ids = IdMap {
+        source_report_id: bundle.report.id,
+        report_id: Uuid::now_v7(),
+        jobs: fresh_ids(bundle.jobs.iter().map(|j| j.id)),
+        suites: fresh_ids(
+            bundle
+                .rows
+                .iter()
+                .flat_map(|r| r.suites.iter().map(|s| s.id)),
+        ),
+        cases: fresh_ids(
+            bundle
+                .rows
+                .iter()
+                .flat_map(|r| r.cases.iter().map(|c| c.id)),
+        ),
+    };
+    let file_ids = |rows: &JobRows| -> Vec<Uuid> {
+        rows.html_files
+            .iter()
+            .map(|f| f.id)
+            .chain(rows.json_files.iter().map(|f| f.id))
+            .chain(rows.screenshots.iter().map(|f| f.id))
+            .collect()
+    };
+    let files = fresh_ids(bundle.rows.iter().flat_map(file_ids));
+    let triage_ids = fresh_ids(
+        bundle
+            .rows
+            .iter()
+            .flat_map(|r| r.triage_records.iter().map(|t| t.id)),
+    );
+
+    let report = test_report::Model {
+        id: ids.report_id,
+        idempotency_key: None,
+        pipeline_id: None,
+        deleted_at: None,
+        ..bundle.report
+    };
+
+    let report_rows = ReportRows {
+        oidc_claims: bundle
+            .report_rows
+            .oidc_claims
+            .map(|claims| report_oidc_claim::Model {
+                id: Uuid::now_v7(),
+                report_id: ids.report_id,
+                ..claims
+            }),
+        duration_regressions: bundle
+            .report_rows
+            .duration_regressions
+            .into_iter()
+            .map(|r| {
+                Ok(duration_regression::Model {
+                    report_id: ids.report_id,
+                    test_case_id: lookup(&ids.cases, r.test_case_id, "test case")?,
+                    ..r
+                })
+            })
+            .collect::<AppResult<_>>()?,
+    };
+
+    let jobs = bundle
+        .jobs
+        .into_iter()
+        .map(|job| {
+            Ok(test_job::Model {
+                id: ids.job(job.id)?,
+                test_report_id: ids.report_id,
+                html_path: job.html_path.as_deref().map(|p| ids.key(p)).transpose()?,
+                deleted_at: None,
+                ..job
+            })
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+
+    let rows = bundle
+        .rows
+        .into_iter()
+        .map(|rows| {
+            Ok(JobRows {
+                suites: rows
+                    .suites
+                    .into_iter()
+                    .map(|s| {
+                        Ok(test_suite::Model {
+                            id: ids.suite(s.id)?,
+                            test_job_id: ids.job(s.test_job_id)?,
+                            deleted_at: None,
+                            ..s
+                        })
+                    })
+                    .collect::<AppResult<_>>()?,
+                cases: rows
+                    .cases
+                    .into_iter()
+                    .map(|c| {
+                        Ok(test_case::Model {
+                            id: lookup(&ids.cases, c.id, "test case")?,
+                            test_suite_id: ids.suite(c.test_suite_id)?,
+                            test_job_id: ids.job(c.test_job_id)?,
+                            attachments: ids.attachments(c.attachments)?,
+                            deleted_at: None,
+                            ..c
+                        })
+                    })
+                    .collect::<AppResult<_>>()?,
+                triage_records: rows
+                    .triage_records
+                    .into_iter()
+                    .map(|t| {
+                        Ok(triage_record::Model {
+                            id: lookup(&triage_ids, t.id, "triage record")?,
+                            test_case_id: t
+                                .test_case_id
+                                .map(|id| lookup(&ids.cases, id, "test case"))
+                                .transpose()?,
+                            deleted_at: None,
+                            ..t
+                        })
+                    })
+                    .collect::<AppResult<_>>()?,
+                html_files: rows
+                    .html_files
+                    .into_iter()
+                    .map(|f| {
+                        Ok(html_file::Model {
+                            id: lookup(&files, f.id, "file")?,
+                            test_job_id: ids.job(f.test_job_id)?,
+                            s3_key: ids.key(&f.s3_key)?,
+                            deleted_at: None,
+                            ..f
+                        })
+                    })
+                    .collect::<AppResult<_>>()?,
+                json_files: rows
+                    .json_files
+                    .into_iter()
+                    .map(|f| {
+                        Ok(json_file::Model {
+                            id: lookup(&files, f.id, "file")?,
+                            test_job_id: ids.job(f.test_job_id)?,
+                            s3_key: ids.key(&f.s3_key)?,
+                            deleted_at: None,
+                            ..f
+                        })
+                    })
+                    .collect::<AppResult<_>>()?,
+                screenshots: rows
+                    .screenshots
+                    .into_iter()
+                    .map(|f| {
+                        Ok(screenshot::Model {
+                            id: lookup(&files, f.id, "file")?,
+                            test_job_id: ids.job(f.test_job_id)?,
+                            test_case_id: f
+                                .test_case_id
+                                .map(|id| lookup(&ids.cases, id, "test case"))
+                                .transpose()?,
+                            s3_key: ids.key(&f.s3_key)?,
+                            deleted_at: None,
+                            ..f
+                        })
+                    })
+                    .collect::<AppResult<_>>()?,
+            })
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+
+    Ok((
+        Bundle {
+            report,
+            report_rows,
+            jobs,
+            rows,
+        },
+        ids,
+    ))
+}
+
+/// Read a bundle's rows from a zip archive.
+fn read_bundle(archive: &mut zip::ZipArchive<File>) -> AppResult<Bundle> {
+    let manifest: Manifest = read_json(archive, MANIFEST_ENTRY)?;
+    if manifest.format != BUNDLE_FORMAT || manifest.version != BUNDLE_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "Unsupported bundle format {} version {} (expected {} version {})",
+            manifest.format, manifest.version, BUNDLE_FORMAT, BUNDLE_VERSION
+        )));
+    }
+
+    let duration_regressions = read_json(archive, REGRESSIONS_ENTRY)?;
+    let jobs: Vec<test_job::Model> = read_json(archive, JOBS_ENTRY)?;
+    let rows = jobs
+        .iter()
+        .map(|job| {
+            let files: JobFiles = read_json(archive, &job_entry(job.id, "files.json"))?;
+            Ok(JobRows {
+                suites: read_json(archive, &job_entry(job.id, "suites.json"))?,
+                cases: read_json(archive, &job_entry(job.id, "cases.json"))?,
+                triage_records: read_json(archive, &job_entry(job.id, "triage.json"))?,
+                html_files: files.html_files,
+                json_files: files.json_files,
+                screenshots: files.screenshots,
+            })
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+
+    Ok(Bundle {
+        report: manifest.report,
+        report_rows: ReportRows {
+            oidc_claims: manifest.oidc_claims,
+            duration_regressions,
+        },
+        jobs,
+        rows,
+    })
+}
+
+fn read_json<T: DeserializeOwned>(archive: &mut zip::ZipArchive<File>, name: &str) -> AppResult<T> {
+    let mut data = Vec::new();
+    copy_entry(archive.by_name(name), name, MAX_JSON_ENTRY_SIZE, &mut data)?;
+    serde_json::from_slice(&data)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid {} in bundle: {}", name, e)))
+}
+
+/// Copy an entry to `out`, refusing to inflate more than its declared size
+/// or `max_size`.
+fn copy_entry(
+    entry: zip::result::ZipResult<zip::read::ZipFile<File>>,
+    name: &str,
+    max_size: u64,
+    out: &mut impl Write,
+) -> AppResult<()> {
+    let entry =
+        entry.map_err(|e| AppError::InvalidInput(format!("Bundle has no {}: {}", name, e)))?;
+    let size = entry.size();
+    if size > max_size {
+        return Err(AppError::InvalidInput(format!(
+            "{} in bundle exceeds maximum size of {} bytes",
+            name, max_size
+        )));
+    }
+
+    let copied = std::io::copy(&mut entry.take(size + 1), out).map_err(|e| {
+        AppError::InvalidInput(format!("Failed to read {} from bundle: {}", name, e))
+    })?;
+    if copied > size {
+        return Err(AppError::InvalidInput(format!(
+            "{} in bundle is larger than its declared size",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Run blocking archive reads on the blocking thread pool.
+async fn blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> AppResult<T> {
+    web::block(f)
+        .await
+        .map_err(|e| AppError::Storage(format!("Failed to read bundle: {}", e)))
+}
+
+/// Recreate a report from the bundle at `path` under fresh IDs.
+///
+/// Stored files are uploaded first and the rows inserted in one transaction
+/// after, so a failed import leaves nothing behind.
+pub async fn import(
+    pool: &DbPool,
+    storage: &Storage,
+    path: &Path,
+) -> AppResult<ImportReportResponse> {
+    let path = path.to_path_buf();
+    let (archive, bundle) = blocking(move || -> AppResult<_> {
+        let file = File::open(&path)
+            .map_err(|e| AppError::Storage(format!("Failed to open uploaded bundle: {}", e)))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| AppError::InvalidInput(format!("Not a zip archive: {}", e)))?;
+        let bundle = read_bundle(&mut archive)?;
+        Ok((archive, bundle))
+    })
+    .await??;
+
+    let (bundle, ids) = remap(bundle)?;
+    let report_prefix = Storage::report_key_prefix(&ids.report_id.to_string());
+
+    let imported = match upload_artifacts(storage, archive, &ids).await {
+        Ok(artifacts) => pool
+            .insert_report_rows(
+                &bundle.report,
+                &bundle.report_rows,
+                &bundle.jobs,
+                &bundle.rows,
+            )
+            .await
+            .map(|()| artifacts),
+        Err(e) => Err(e),
+    };
+    let artifacts = match imported {
+        Ok(artifacts) => artifacts,
+        Err(e) => {
+            if let Err(cleanup) = storage.delete_prefix(&report_prefix).await {
+                warn!(
+                    "Failed to clean up files of failed import {}: {}",
+                    ids.report_id, cleanup
+                );
+            }
+            return Err(e);
+        }
+    };
+
+    // Trends and report listings read the rollup, not the imported rows
+    if let Err(e) = pool.refresh_report_rollup(ids.report_id).await {
+        warn!(
+            "Failed to refresh rollup of imported report {}: {}",
+            ids.report_id, e
+        );
+    }
+
+    let response = ImportReportResponse {
+        report_id: ids.report_id,
+        source_report_id: ids.source_report_id,
+        jobs: bundle.jobs.len(),
+        test_suites: bundle.rows.iter().map(|r| r.suites.len()).sum(),
+        test_cases: bundle.rows.iter().map(|r| r.cases.len()).sum(),
+        artifacts,
+    };
+
+    info!(
+        "Report {} imported as {} ({} jobs, {} test cases, {} stored files)",
+        response.source_report_id,
+        response.report_id,
+        response.jobs,
+        response.test_cases,
+        response.artifacts
+    );
+
+    Ok(response)
+}
+
+/// Upload the bundle's stored files under the new report's keys.
+async fn upload_artifacts(
+    storage: &Storage,
+    mut archive: zip::ZipArchive<File>,
+    ids: &IdMap,
+) -> AppResult<usize> {
+    let names: Vec<String> = archive
+        .file_names()
+        .filter(|name| name.starts_with(ARTIFACTS_DIR) && !name.ends_with('/'))
+        .map(String::from)
+        .collect();
+
+    // Each file is extracted to disk and streamed from there, so large
+    // artifacts aren't held in memory
+    let spool = TempFile::new("tsio-artifact", "");
+    let count = names.len();
+    for name in names {
+        let key = ids.key(&name[ARTIFACTS_DIR.len()..])?;
+        // The archive moves to the blocking pool for each read and back
+        let path = spool.0.clone();
+        let (returned, copied) = blocking(move || {
+            let copied = File::create(&path)
+                .map_err(|e| AppError::Storage(format!("Failed to spool {}: {}", name, e)))
+                .and_then(|mut file| {
+                    copy_entry(archive.by_name(&name), &name, MAX_BUNDLE_SIZE, &mut file)
+                });
+            (archive, copied)
+        })
+        .await?;
+        archive = returned;
+        copied?;
+        let ext = key.rsplit('.').next().unwrap_or("");
+        storage
+            .put_file(
+                &key,
+                &spool.0,
+                Some(Storage::content_type_for_extension(ext)),
+            )
+            .await?;
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids() -> IdMap {
+        let source_job = Uuid::parse_str("00000000-0000-0000-0000-00000000000b").unwrap();
+        let job = Uuid::parse_str("00000000-0000-0000-0000-0000000000bb").unwrap();
+        IdMap {
+            source_report_id: Uuid::parse_str("00000000-0000-0000-0000-00000000000a").unwrap(),
+            report_id: Uuid::parse_str("00000000-0000-0000-0000-0000000000aa").unwrap(),
+            jobs: HashMap::from([(source_job, job)]),
+            suites: HashMap::new(),
+            cases: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_rekey() {
+        let ids = ids();
+        assert_eq!(
+            ids.rekey("reports/00000000-0000-0000-0000-00000000000a/jobs/00000000-0000-0000-0000-00000000000b/data/a.png")
+                .as_deref(),
+            Some("reports/00000000-0000-0000-0000-0000000000aa/jobs/00000000-0000-0000-0000-0000000000bb/data/a.png")
+        );
+        // html_path has no trailing file name
+        assert_eq!(
+            ids.rekey("reports/00000000-0000-0000-0000-00000000000a/jobs/00000000-0000-0000-0000-00000000000b")
+                .as_deref(),
+            Some("reports/00000000-0000-0000-0000-0000000000aa/jobs/00000000-0000-0000-0000-0000000000bb")
+        );
+        // Other reports, unknown jobs and path tricks are rejected
+        assert_eq!(
+            ids.rekey("reports/00000000-0000-0000-0000-00000000000c/x"),
+            None
+        );
+        assert_eq!(
+            ids.rekey("reports/00000000-0000-0000-0000-00000000000a/jobs/00000000-0000-0000-0000-00000000000c/x"),
+            None
+        );
+        assert_eq!(
+            ids.rekey("reports/00000000-0000-0000-0000-00000000000a/../00000000-0000-0000-0000-00000000000c"),
+            None
+        );
+        assert_eq!(
+            ids.rekey("other/00000000-0000-0000-0000-00000000000a"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_attachments() {
+        let ids = ids();
+        let attachments = serde_json::json!([
+            {"path": "a.png", "s3_key": "reports/00000000-0000-0000-0000-00000000000a/jobs/00000000-0000-0000-0000-00000000000b/a.png"},
+            {"path": "missing.png", "missing": true}
+        ]);
+        let rewritten = ids.attachments(Some(attachments)).unwrap().unwrap();
+        assert_eq!(
+            rewritten[0]["s3_key"],
+            "reports/00000000-0000-0000-0000-0000000000aa/jobs/00000000-0000-0000-0000-0000000000bb/a.png"
+        );
+        assert_eq!(rewritten[1]["path"], "missing.png");
+        assert!(ids.attachments(None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_fresh_ids_keep_order() {
+        let source: Vec<Uuid> = (1..=5u128).rev().map(Uuid::from_u128).collect();
+        let ids = fresh_ids(source.iter().copied());
+        assert_eq!(ids.len(), 5);
+        let mut mapped: Vec<(Uuid, Uuid)> = ids.into_iter().collect();
+        mapped.sort();
+        assert!(mapped.windows(2).all(|w| w[0].1 < w[1].1));
+    }
+
+    #[test]
+    fn test_copy_entry_limits_size() {
+        let mut file = tempfile::tempfile().unwrap();
+        let mut writer = zip::ZipWriter::new(&mut file);
+        writer
+            .start_file("jobs.json", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"[1, 2, 3]").unwrap();
+        writer.finish().unwrap();
+        let mut archive = zip::ZipArchive::new(file).unwrap();
+
+        let mut data = Vec::new();
+        copy_entry(archive.by_name("jobs.json"), "jobs.json", 9, &mut data).unwrap();
+        assert_eq!(data, b"[1, 2, 3]");
+
+        let mut data = Vec::new();
+        assert!(copy_entry(archive.by_name("jobs.json"), "jobs.json", 8, &mut data).is_err());
+        assert!(data.is_empty());
+        assert!(copy_entry(archive.by_name("cases.json"), "cases.json", 9, &mut data).is_err());
+    }
+}
@@ -0,0 +1,280 @@
+//! Report completion: closing reports once their jobs stop running.
+//!
+//! A report closes as `complete` when at least one job completed and as
+//! `failed` otherwise. It closes on its own once every expected job completed
+//! or failed. CI can close it early with [`finalize`] when fewer jobs ran than
+//! expected (a cancelled matrix job, a skipped shard), or stop it with
+//! [`cancel`]. Early closes record how many expected jobs never finished.
+//!
+//! Closed reports are immutable until an admin reopens them with [`reopen`].
+//!
+//! Open reports don't know their job count up front: each registered job
+//! raises `expected_jobs`, so they only close on [`finalize`], once the upload
+//! sweeper finds them idle, or after `PATCH /reports/{id}` fixes the count.
+
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::config::FeatureSettings;
+use crate::db::DbPool;
+use crate::db::test_jobs::FinishedJobs;
+use crate::entity::test_report;
+use crate::error::{AppError, AppResult};
+use crate::models::{CloseReportResponse, ReportStatus, WsEvent, WsEventMessage};
+use crate::services::EventBroadcaster;
+use crate::services::lifecycle::Lifecycle;
+use crate::services::pipeline;
+
+/// Error message of pending jobs failed by cancelling their report.
+const CANCELLED_JOB_MESSAGE: &str = "Report cancelled";
+
+/// Expected jobs that haven't completed or failed.
+pub fn missing_jobs(expected_jobs: i32, finished: &FinishedJobs) -> i32 {
+    let finished = finished.completed + finished.failed;
+    (expected_jobs as i64 - finished).clamp(0, i32::MAX as i64) as i32
+}
+
+/// Close a report if all its expected jobs have completed or failed.
+///
+/// Called whenever a job finishes or the expected job count changes;
+/// otherwise broadcasts the progress.
+pub async fn check(
+    pool: &DbPool,
+    broadcaster: &EventBroadcaster,
+    features: &FeatureSettings,
+    report_id: Uuid,
+) -> AppResult<()> {
+    let report = get_report(pool, report_id).await?;
+    let finished = pool.count_finished_jobs(report_id).await?;
+
+    // Reports finalized or cancelled earlier stay as they are, and open
+    // reports may still get more jobs
+    if !report.is_open
+        && missing_jobs(report.expected_jobs, &finished) == 0
+        && let Some(report) = pool.close_report(report_id, None).await?
+    {
+        info!(
+            "Report {} marked as {} ({} complete, {} failed of {} expected jobs)",
+            report_id, report.status, finished.completed, finished.failed, report.expected_jobs
+        );
+        on_closed(pool, broadcaster, features, &report, &finished).await;
+        return Ok(());
+    }
+
+    broadcaster.send(WsEventMessage::new(progress_event(&report, &finished)));
+
+    info!(
+        "Report {} progress: {} complete, {} failed of {} expected jobs",
+        report_id, finished.completed, finished.failed, report.expected_jobs
+    );
+
+    Ok(())
+}
+
+/// Close an open report with the jobs that finished so far.
+pub async fn finalize(
+    pool: &DbPool,
+    broadcaster: &EventBroadcaster,
+    features: &FeatureSettings,
+    report_id: Uuid,
+) -> AppResult<CloseReportResponse> {
+    let report = get_open_report(pool, report_id).await?;
+    let finished = pool.count_finished_jobs(report_id).await?;
+    let missing = missing_jobs(report.expected_jobs, &finished);
+
+    let report = pool
+        .close_report(report_id, Some(missing))
+        .await?
+        .ok_or_else(|| already_closed(&report))?;
+
+    info!(
+        "Report {} finalized as {} ({} of {} expected jobs missing)",
+        report_id, report.status, missing, report.expected_jobs
+    );
+    on_closed(pool, broadcaster, features, &report, &finished).await;
+
+    Ok(close_response(&report, &finished, missing))
+}
+
+/// Cancel an open report, failing its pending jobs.
+pub async fn cancel(
+    pool: &DbPool,
+    broadcaster: &EventBroadcaster,
+    report_id: Uuid,
+) -> AppResult<CloseReportResponse> {
+    let report = get_open_report(pool, report_id).await?;
+    let finished = pool.count_finished_jobs(report_id).await?;
+    let missing = missing_jobs(report.expected_jobs, &finished);
+
+    let report = pool
+        .cancel_report(report_id, missing)
+        .await?
+        .ok_or_else(|| already_closed(&report))?;
+
+    let jobs = pool
+        .fail_pending_jobs(report_id, CANCELLED_JOB_MESSAGE)
+        .await?;
+    for job_id in &jobs {
+        broadcaster.send(WsEventMessage::new(WsEvent::job_updated(
+            report_id,
+            *job_id,
+            "failed".to_string(),
+        )));
+    }
+    broadcaster.send(WsEventMessage::new(progress_event(&report, &finished)));
+    if let Some(pipeline_id) = report.pipeline_id {
+        pipeline::broadcast_progress(pool, broadcaster, pipeline_id).await;
+    }
+
+    info!(
+        "Report {} cancelled ({} pending jobs failed, {} of {} expected jobs missing)",
+        report_id,
+        jobs.len(),
+        missing,
+        report.expected_jobs
+    );
+
+    Ok(close_response(&report, &finished, missing))
+}
+
+/// Reopen a closed report so jobs can be added and uploaded again.
+///
+/// The report stays open until a new job finishes and its expected jobs are
+/// all done, it is finalized, or it stalls.
+pub async fn reopen(
+    pool: &DbPool,
+    broadcaster: &EventBroadcaster,
+    report_id: Uuid,
+) -> AppResult<test_report::Model> {
+    let Some(report) = pool.reopen_report(report_id).await? else {
+        let report = get_report(pool, report_id).await?;
+        return Err(AppError::Conflict(format!(
+            "Report {} is {}, not closed",
+            report.id, report.status
+        )));
+    };
+
+    let finished = pool.count_finished_jobs(report_id).await?;
+    broadcaster.send(WsEventMessage::new(progress_event(&report, &finished)));
+    if let Some(pipeline_id) = report.pipeline_id {
+        pipeline::broadcast_progress(pool, broadcaster, pipeline_id).await;
+    }
+
+    info!("Report {} reopened", report_id);
+
+    Ok(report)
+}
+
+async fn get_report(pool: &DbPool, report_id: Uuid) -> AppResult<test_report::Model> {
+    pool.get_report_by_id(report_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report {}", report_id)))
+}
+
+async fn get_open_report(pool: &DbPool, report_id: Uuid) -> AppResult<test_report::Model> {
+    let report = get_report(pool, report_id).await?;
+    if is_closed(&report) {
+        return Err(already_closed(&report));
+    }
+    Ok(report)
+}
+
+/// Whether a report reached a final status.
+pub fn is_closed(report: &test_report::Model) -> bool {
+    ReportStatus::parse(&report.status).is_some_and(|s| s.is_final())
+}
+
+/// Error for changes to a closed report.
+pub fn already_closed(report: &test_report::Model) -> AppError {
+    AppError::Conflict(format!("Report {} is already {}", report.id, report.status))
+}
+
+fn close_response(
+    report: &test_report::Model,
+    finished: &FinishedJobs,
+    missing_jobs: i32,
+) -> CloseReportResponse {
+    CloseReportResponse {
+        report_id: report.id,
+        status: ReportStatus::parse(&report.status).unwrap_or(ReportStatus::Failed),
+        expected_jobs: report.expected_jobs,
+        completed_jobs: finished.completed,
+        failed_jobs: finished.failed,
+        missing_jobs,
+    }
+}
+
+/// Report status and job progress for a report_updated event.
+fn progress_event(report: &test_report::Model, finished: &FinishedJobs) -> WsEvent {
+    WsEvent::report_progress(
+        report.id,
+        &report.status,
+        finished.completed.clamp(0, i32::MAX as i64) as i32,
+        report.expected_jobs,
+        report.is_open,
+    )
+}
+
+/// Flag duration regressions of a complete report and broadcast the close
+/// to the report's and its pipeline run's subscribers.
+async fn on_closed(
+    pool: &DbPool,
+    broadcaster: &EventBroadcaster,
+    features: &FeatureSettings,
+    report: &test_report::Model,
+    finished: &FinishedJobs,
+) {
+    let report_id = report.id;
+
+    if report.status == ReportStatus::Complete.as_str() {
+        // Flag tests that got slower than their baseline
+        match pool.detect_duration_regressions(report_id, features).await {
+            Ok(0) => {}
+            Ok(count) => info!("Report {} has {} duration regressions", report_id, count),
+            Err(e) => warn!(
+                "Failed to detect duration regressions for report {}: {}",
+                report_id, e
+            ),
+        }
+
+        // Quarantined failures don't count against the outcome
+        match pool.get_test_stats_by_report_ids(&[report_id]).await {
+            Ok(stats) => {
+                if let Some(stats) = stats.get(&report_id) {
+                    info!(
+                        "Report {} outcome: {} ({} failed, {} matched known issues, {} quarantined)",
+                        report_id,
+                        stats.outcome.as_str(),
+                        stats.failed,
+                        stats.known_issues,
+                        stats.quarantined
+                    );
+                }
+            }
+            Err(e) => warn!("Failed to get stats for report {}: {}", report_id, e),
+        }
+    }
+
+    broadcaster.send(WsEventMessage::new(progress_event(report, finished)));
+    if let Some(pipeline_id) = report.pipeline_id {
+        pipeline::broadcast_progress(pool, broadcaster, pipeline_id).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_jobs() {
+        let finished = FinishedJobs {
+            completed: 3,
+            failed: 1,
+        };
+        assert_eq!(missing_jobs(6, &finished), 2);
+        assert_eq!(missing_jobs(4, &finished), 0);
+        // Reruns can add more jobs than expected
+        assert_eq!(missing_jobs(2, &finished), 0);
+        assert_eq!(missing_jobs(1, &FinishedJobs::default()), 1);
+    }
+}
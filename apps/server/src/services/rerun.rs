@@ -0,0 +1,136 @@
+//! Rerun manifest generation for failed tests.
+//!
+//! Builds CLI arguments that select exactly the tests to rerun:
+//! - Playwright: spec file filters, a `--grep` over escaped titles and the
+//!   `--project`s the tests ran in
+//! - Cypress: a `--spec` list of files
+//! - Detox (Jest): spec file filters and an anchored `--testNamePattern`
+//!   over escaped full test names
+
+use std::collections::BTreeSet;
+
+use crate::models::{Framework, RerunTest};
+use crate::services::text::escape_regex;
+
+/// Spec files of the tests, sorted and deduplicated.
+pub fn rerun_files(tests: &[RerunTest]) -> Vec<String> {
+    tests
+        .iter()
+        .filter_map(|t| t.file_path.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Build CLI arguments selecting the given tests, one argument per element.
+pub fn rerun_args(framework: Framework, tests: &[RerunTest]) -> Vec<String> {
+    if tests.is_empty() {
+        return Vec::new();
+    }
+
+    let files = rerun_files(tests);
+    let mut args = Vec::new();
+
+    match framework {
+        Framework::Playwright => {
+            args.extend(files.iter().map(|f| escape_regex(f)));
+
+            let titles: BTreeSet<String> = tests.iter().map(|t| escape_regex(&t.title)).collect();
+            args.push("--grep".to_string());
+            args.push(titles.into_iter().collect::<Vec<_>>().join("|"));
+
+            let projects: BTreeSet<&str> =
+                tests.iter().filter_map(|t| t.project.as_deref()).collect();
+            for project in projects {
+                args.push("--project".to_string());
+                args.push(project.to_string());
+            }
+        }
+        Framework::Cypress => {
+            if !files.is_empty() {
+                args.push("--spec".to_string());
+                args.push(files.join(","));
+            }
+        }
+        Framework::Detox => {
+            args.extend(files.iter().map(|f| escape_regex(f)));
+
+            let names: BTreeSet<String> =
+                tests.iter().map(|t| escape_regex(&t.full_title)).collect();
+            args.push("--testNamePattern".to_string());
+            args.push(format!(
+                "^(?:{})$",
+                names.into_iter().collect::<Vec<_>>().join("|")
+            ));
+        }
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::text::split_project;
+
+    fn test(file_path: &str, title: &str, full_title: &str) -> RerunTest {
+        RerunTest {
+            file_path: Some(file_path.to_string()),
+            title: title.to_string(),
+            full_title: full_title.to_string(),
+            project: split_project(full_title).0.map(str::to_string),
+            status: "failed".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_playwright_args() {
+        let tests = [
+            test(
+                "e2e/login.spec.ts",
+                "logs in (SSO)",
+                "[chromium] logs in (SSO)",
+            ),
+            test("e2e/login.spec.ts", "logs out", "[firefox] logs out"),
+        ];
+        assert_eq!(
+            rerun_args(Framework::Playwright, &tests),
+            vec![
+                r"e2e/login\.spec\.ts",
+                "--grep",
+                r"logs in \(SSO\)|logs out",
+                "--project",
+                "chromium",
+                "--project",
+                "firefox",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cypress_and_detox_args() {
+        let tests = [
+            test("cypress/e2e/b.cy.ts", "b", "Suite b"),
+            test("cypress/e2e/a.cy.ts", "a", "Suite a"),
+        ];
+        assert_eq!(
+            rerun_args(Framework::Cypress, &tests),
+            vec!["--spec", "cypress/e2e/a.cy.ts,cypress/e2e/b.cy.ts"]
+        );
+
+        let tests = [test(
+            "channels/create.e2e.ts",
+            "creates $1",
+            "Channels creates $1",
+        )];
+        assert_eq!(
+            rerun_args(Framework::Detox, &tests),
+            vec![
+                r"channels/create\.e2e\.ts",
+                "--testNamePattern",
+                r"^(?:Channels creates \$1)$",
+            ]
+        );
+        assert!(rerun_args(Framework::Detox, &[]).is_empty());
+    }
+}
@@ -0,0 +1,815 @@
+//! JUnit XML and CSV exports of report results.
+//!
+//! Generated from the stored test suites and test cases for tools that don't
+//! speak the JSON API (test management, BI dashboards). Playwright stores one
+//! test case row per attempt, next to each other in sequence order; an export
+//! folds them back into one test whose earlier attempts become JUnit
+//! `rerunFailure` (the test still failed) or `flakyFailure` (it passed on a
+//! retry) elements. Attachments are written as links relative to the server,
+//! like the HTML report links of the jobs API.
+//!
+//! Test cases are read a page at a time and sent as soon as they're rendered,
+//! so memory stays bounded however many tests a report has.
+
+use std::fmt::Write;
+
+use actix_web::web::Bytes;
+use chrono::SecondsFormat;
+use serde_json::Value as JsonValue;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::entity::{test_case, test_job, test_report, test_suite};
+use crate::error::{AppError, AppResult};
+use crate::models::{EnvironmentMetadata, JobGitHubMetadata};
+use crate::services::failure_signature::strip_ansi;
+use crate::services::run_lineage::is_failure;
+
+/// Test cases read per query.
+const CASE_PAGE_SIZE: u64 = 1000;
+
+/// Chunks buffered between the exporter and the HTTP response.
+const EXPORT_BUFFER: usize = 16;
+
+/// Route serving stored files.
+const FILES_ROUTE: &str = "/files";
+
+/// CSV columns, one row per test.
+const CSV_COLUMNS: [&str; 18] = [
+    "test_case_id",
+    "job_id",
+    "job_name",
+    "os",
+    "browser",
+    "device",
+    "suite",
+    "file_path",
+    "title",
+    "full_title",
+    "status",
+    "duration_ms",
+    "retries",
+    "quarantined",
+    "known_issue_key",
+    "owner",
+    "error_message",
+    "attachments",
+];
+
+/// Export format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultsFormat {
+    JUnit,
+    Csv,
+}
+
+impl ResultsFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::JUnit => "application/xml; charset=utf-8",
+            Self::Csv => "text/csv; charset=utf-8",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::JUnit => "xml",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// File name for a results download, per report or per job.
+pub fn results_filename(report_id: Uuid, job_id: Option<Uuid>, format: ResultsFormat) -> String {
+    match job_id {
+        Some(job_id) => format!(
+            "report-{}-job-{}-results.{}",
+            report_id,
+            job_id,
+            format.extension()
+        ),
+        None => format!("report-{}-results.{}", report_id, format.extension()),
+    }
+}
+
+/// A job to export with its suites, ordered by ID.
+pub struct ExportJob {
+    pub job: test_job::Model,
+    pub suites: Vec<test_suite::Model>,
+}
+
+// ============================================================================
+// Streaming
+// ============================================================================
+
+/// Stream the results of `jobs`.
+///
+/// A failure midway ends the stream with an error, leaving the client with a
+/// truncated document.
+pub fn export(
+    pool: DbPool,
+    report: test_report::Model,
+    jobs: Vec<ExportJob>,
+    format: ResultsFormat,
+) -> impl futures_util::Stream<Item = Result<Bytes, std::io::Error>> {
+    let (tx, rx) = mpsc::channel(EXPORT_BUFFER);
+
+    actix_web::rt::spawn(async move {
+        let report_id = report.id;
+        match write_results(&pool, &report, &jobs, format, &tx).await {
+            Ok(tests) => info!(
+                "Report {} results exported as {} with {} tests",
+                report_id,
+                format.extension(),
+                tests
+            ),
+            Err(_) if tx.is_closed() => {
+                info!(
+                    "Report {} results export cancelled by the client",
+                    report_id
+                )
+            }
+            Err(e) => {
+                warn!("Failed to export results of report {}: {}", report_id, e);
+                let _ = tx.send(Err(std::io::Error::other(e))).await;
+            }
+        }
+    });
+
+    futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    })
+}
+
+/// Write the document; returns the number of tests.
+async fn write_results(
+    pool: &DbPool,
+    report: &test_report::Model,
+    jobs: &[ExportJob],
+    format: ResultsFormat,
+    tx: &mpsc::Sender<Result<Bytes, std::io::Error>>,
+) -> AppResult<usize> {
+    let send = |chunk: String| async move {
+        if chunk.is_empty() {
+            return Ok(());
+        }
+        tx.send(Ok(Bytes::from(chunk)))
+            .await
+            .map_err(|_| AppError::Storage("Export stream closed".to_string()))
+    };
+
+    send(header(
+        format,
+        report,
+        jobs.iter().flat_map(|j| j.suites.iter()),
+    ))
+    .await?;
+
+    let mut tests = 0;
+    for export_job in jobs {
+        let mut section = JobResults::new(format, &export_job.job, &export_job.suites);
+        let mut after = None;
+        loop {
+            let page = pool
+                .get_test_case_page(export_job.job.id, after.as_ref(), CASE_PAGE_SIZE)
+                .await?;
+            let last_page = page.len() < CASE_PAGE_SIZE as usize;
+            after = page.last().cloned();
+
+            let mut out = String::new();
+            for case in page {
+                section.push(case, &mut out);
+            }
+            send(out).await?;
+
+            if last_page {
+                break;
+            }
+        }
+
+        let mut out = String::new();
+        section.finish(&mut out);
+        send(out).await?;
+        tests += section.tests;
+    }
+
+    send(footer(format).to_string()).await?;
+    Ok(tests)
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+/// Opening of the document: the XML declaration and `<testsuites>` with
+/// totals over `suites`, or the CSV header row.
+pub fn header<'a>(
+    format: ResultsFormat,
+    report: &test_report::Model,
+    suites: impl IntoIterator<Item = &'a test_suite::Model>,
+) -> String {
+    match format {
+        ResultsFormat::JUnit => {
+            let totals = SuiteCounts::total(suites);
+            format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{}\" timestamp=\"{}\">\n",
+                escape_xml(&format!("{} report {}", report.framework, report.id)),
+                totals.tests,
+                totals.failures,
+                totals.skipped,
+                seconds(totals.duration_ms),
+                report.created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            )
+        }
+        ResultsFormat::Csv => {
+            let mut out = CSV_COLUMNS.join(",");
+            out.push_str("\r\n");
+            out
+        }
+    }
+}
+
+/// Closing of the document.
+pub fn footer(format: ResultsFormat) -> &'static str {
+    match format {
+        ResultsFormat::JUnit => "</testsuites>\n",
+        ResultsFormat::Csv => "",
+    }
+}
+
+/// JUnit counts of a suite. Quarantined failures count as skipped, matching
+/// the report's own stats.
+#[derive(Debug, Default, PartialEq)]
+struct SuiteCounts {
+    tests: i64,
+    failures: i64,
+    skipped: i64,
+    duration_ms: i64,
+}
+
+impl SuiteCounts {
+    fn of(suite: &test_suite::Model) -> Self {
+        Self {
+            tests: suite.total_count as i64,
+            failures: suite.failed_count as i64,
+            skipped: (suite.skipped_count + suite.quarantined_count) as i64,
+            duration_ms: suite.duration_ms as i64,
+        }
+    }
+
+    fn total<'a>(suites: impl IntoIterator<Item = &'a test_suite::Model>) -> Self {
+        suites
+            .into_iter()
+            .map(Self::of)
+            .fold(Self::default(), |acc, c| Self {
+                tests: acc.tests + c.tests,
+                failures: acc.failures + c.failures,
+                skipped: acc.skipped + c.skipped,
+                duration_ms: acc.duration_ms + c.duration_ms,
+            })
+    }
+}
+
+/// Job columns repeated on every CSV row and JUnit suite properties.
+struct JobInfo {
+    id: Uuid,
+    name: Option<String>,
+    os: Option<String>,
+    browser: Option<String>,
+    device: Option<String>,
+}
+
+impl JobInfo {
+    fn of(job: &test_job::Model) -> Self {
+        let environment = EnvironmentMetadata::from_json(job.environment.as_ref());
+        Self {
+            id: job.id,
+            name: JobGitHubMetadata::from_json(job.github_metadata.as_ref()).job_name,
+            os: environment.os,
+            browser: environment.browser,
+            device: environment.device,
+        }
+    }
+}
+
+/// Renders one job's tests, fed its test cases in suite, sequence and ID
+/// order.
+pub struct JobResults<'a> {
+    format: ResultsFormat,
+    job: JobInfo,
+    suites: &'a [test_suite::Model],
+    /// Next suite to open (JUnit writes every suite, even without tests).
+    next_suite: usize,
+    open_suite: Option<usize>,
+    /// Attempts of the test being collected.
+    attempts: Vec<test_case::Model>,
+    /// Tests written so far.
+    pub tests: usize,
+}
+
+impl<'a> JobResults<'a> {
+    /// `suites` must be the job's suites ordered by ID.
+    pub fn new(
+        format: ResultsFormat,
+        job: &test_job::Model,
+        suites: &'a [test_suite::Model],
+    ) -> Self {
+        Self {
+            format,
+            job: JobInfo::of(job),
+            suites,
+            next_suite: 0,
+            open_suite: None,
+            attempts: Vec::new(),
+            tests: 0,
+        }
+    }
+
+    /// Add the next test case, writing the previous test once all its
+    /// attempts are in.
+    pub fn push(&mut self, case: test_case::Model, out: &mut String) {
+        if let Some(first) = self.attempts.first()
+            && (first.test_suite_id != case.test_suite_id || first.full_title != case.full_title)
+        {
+            self.flush(out);
+        }
+        self.attempts.push(case);
+    }
+
+    /// Write the last test and close the job's suites.
+    pub fn finish(&mut self, out: &mut String) {
+        self.flush(out);
+        if self.format == ResultsFormat::JUnit {
+            self.open_suites_until(None, out);
+            if self.open_suite.take().is_some() {
+                out.push_str("  </testsuite>\n");
+            }
+        }
+    }
+
+    fn flush(&mut self, out: &mut String) {
+        if self.attempts.is_empty() {
+            return;
+        }
+        let mut attempts = std::mem::take(&mut self.attempts);
+        attempts.sort_by_key(|a| (a.retry_count, a.sequence));
+
+        let suite_id = attempts[0].test_suite_id;
+        let Ok(index) = self.suites.binary_search_by_key(&suite_id, |s| s.id) else {
+            // Suite deleted under a live case; nothing to file it under
+            return;
+        };
+
+        match self.format {
+            ResultsFormat::JUnit => {
+                self.open_suites_until(Some(index), out);
+                junit_testcase(&self.suites[index], &attempts, out);
+            }
+            ResultsFormat::Csv => {
+                csv_row(&self.job, &self.suites[index], &attempts, out);
+            }
+        }
+        self.tests += 1;
+    }
+
+    /// Open suites in order up to `index` (or all of them), writing the ones
+    /// passed over without tests as empty suites.
+    fn open_suites_until(&mut self, index: Option<usize>, out: &mut String) {
+        let end = index.map_or(self.suites.len(), |i| i + 1);
+        while self.next_suite < end {
+            if self.open_suite.take().is_some() {
+                out.push_str("  </testsuite>\n");
+            }
+            junit_testsuite_open(&self.job, &self.suites[self.next_suite], out);
+            self.open_suite = Some(self.next_suite);
+            self.next_suite += 1;
+        }
+    }
+}
+
+fn junit_testsuite_open(job: &JobInfo, suite: &test_suite::Model, out: &mut String) {
+    let counts = SuiteCounts::of(suite);
+    let _ = write!(
+        out,
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{}\"",
+        escape_xml(&suite.title),
+        counts.tests,
+        counts.failures,
+        counts.skipped,
+        seconds(counts.duration_ms),
+    );
+    if let Some(start_time) = suite.start_time {
+        let _ = write!(
+            out,
+            " timestamp=\"{}\"",
+            start_time.to_rfc3339_opts(SecondsFormat::Secs, true)
+        );
+    }
+    if let Some(file_path) = &suite.file_path {
+        let _ = write!(out, " file=\"{}\"", escape_xml(file_path));
+    }
+    out.push_str(">\n    <properties>\n");
+    junit_property(out, 6, "job_id", Some(&job.id.to_string()));
+    junit_property(out, 6, "job_name", job.name.as_deref());
+    junit_property(out, 6, "os", job.os.as_deref());
+    junit_property(out, 6, "browser", job.browser.as_deref());
+    junit_property(out, 6, "device", job.device.as_deref());
+    out.push_str("    </properties>\n");
+}
+
+fn junit_property(out: &mut String, indent: usize, name: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        let _ = writeln!(
+            out,
+            "{:indent$}<property name=\"{}\" value=\"{}\"/>",
+            "",
+            name,
+            escape_xml(value),
+        );
+    }
+}
+
+/// Write a test from its attempts, the final one last.
+fn junit_testcase(suite: &test_suite::Model, attempts: &[test_case::Model], out: &mut String) {
+    let Some((last, earlier)) = attempts.split_last() else {
+        return;
+    };
+    let duration_ms: i64 = attempts.iter().map(|a| a.duration_ms as i64).sum();
+
+    let _ = write!(
+        out,
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+        escape_xml(&last.title),
+        escape_xml(suite.file_path.as_deref().unwrap_or(&suite.title)),
+        seconds(duration_ms),
+    );
+    if let Some(file_path) = &suite.file_path {
+        let _ = write!(out, " file=\"{}\"", escape_xml(file_path));
+    }
+    out.push_str(">\n");
+
+    if last.quarantined || last.owner.is_some() || last.known_issue_key.is_some() {
+        out.push_str("      <properties>\n");
+        junit_property(out, 8, "quarantined", last.quarantined.then_some("true"));
+        junit_property(out, 8, "owner", last.owner.as_deref());
+        junit_property(out, 8, "known_issue", last.known_issue_key.as_deref());
+        out.push_str("      </properties>\n");
+    }
+
+    if is_failure(&last.status) && last.quarantined {
+        let _ = writeln!(
+            out,
+            "      <skipped message=\"Quarantined failure: {}\"/>",
+            escape_xml(&first_line(last.error_message.as_deref()))
+        );
+    } else if is_failure(&last.status) {
+        junit_failure(out, "failure", last);
+    } else if last.status == "skipped" {
+        out.push_str("      <skipped/>\n");
+    }
+
+    let retry_element = if is_failure(&last.status) {
+        "rerunFailure"
+    } else {
+        "flakyFailure"
+    };
+    for attempt in earlier.iter().filter(|a| is_failure(&a.status)) {
+        junit_failure(out, retry_element, attempt);
+    }
+
+    let links: Vec<String> = attempts
+        .iter()
+        .flat_map(|a| attachment_links(a.attachments.as_ref()))
+        .collect();
+    if !links.is_empty() {
+        out.push_str("      <system-out>");
+        for link in &links {
+            let _ = writeln!(out, "[[ATTACHMENT|{}]]", escape_xml(link));
+        }
+        out.push_str("</system-out>\n");
+    }
+
+    out.push_str("    </testcase>\n");
+}
+
+fn junit_failure(out: &mut String, element: &str, attempt: &test_case::Model) {
+    let error = attempt.error_message.as_deref().map(strip_ansi);
+    let _ = write!(
+        out,
+        "      <{} message=\"{}\" type=\"{}\"",
+        element,
+        escape_xml(&first_line(error.as_deref())),
+        escape_xml(&attempt.status),
+    );
+    match error {
+        Some(error) if !error.is_empty() => {
+            let _ = writeln!(out, ">{}</{}>", escape_xml(&error), element);
+        }
+        _ => out.push_str("/>\n"),
+    }
+}
+
+/// Write a test as a CSV row; status, error and retries are the final
+/// attempt's, attachments are those of every attempt.
+fn csv_row(
+    job: &JobInfo,
+    suite: &test_suite::Model,
+    attempts: &[test_case::Model],
+    out: &mut String,
+) {
+    let Some(last) = attempts.last() else {
+        return;
+    };
+    let duration_ms: i64 = attempts.iter().map(|a| a.duration_ms as i64).sum();
+    let links: Vec<String> = attempts
+        .iter()
+        .flat_map(|a| attachment_links(a.attachments.as_ref()))
+        .collect();
+
+    let fields = [
+        last.id.to_string(),
+        job.id.to_string(),
+        job.name.clone().unwrap_or_default(),
+        job.os.clone().unwrap_or_default(),
+        job.browser.clone().unwrap_or_default(),
+        job.device.clone().unwrap_or_default(),
+        suite.title.clone(),
+        suite.file_path.clone().unwrap_or_default(),
+        last.title.clone(),
+        last.full_title.clone(),
+        last.status.clone(),
+        duration_ms.to_string(),
+        last.retry_count.to_string(),
+        last.quarantined.to_string(),
+        last.known_issue_key.clone().unwrap_or_default(),
+        last.owner.clone().unwrap_or_default(),
+        last.error_message
+            .as_deref()
+            .map(strip_ansi)
+            .unwrap_or_default(),
+        links.join(" "),
+    ];
+
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&escape_csv(field));
+    }
+    out.push_str("\r\n");
+}
+
+/// Links to a test case's stored attachments; attachments missing from the
+/// upload have no stored file and are left out.
+fn attachment_links(attachments: Option<&JsonValue>) -> Vec<String> {
+    attachments
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|a| a.get("s3_key").and_then(JsonValue::as_str))
+        .map(file_url)
+        .collect()
+}
+
+/// Server-relative URL of a stored file, with each key segment
+/// percent-encoded.
+fn file_url(key: &str) -> String {
+    let path: Vec<_> = key.split('/').map(urlencoding::encode).collect();
+    format!("{}/{}", FILES_ROUTE, path.join("/"))
+}
+
+fn first_line(error: Option<&str>) -> String {
+    error
+        .map(strip_ansi)
+        .and_then(|e| {
+            e.lines()
+                .map(str::trim)
+                .find(|l| !l.is_empty())
+                .map(str::to_string)
+        })
+        .unwrap_or_default()
+}
+
+fn seconds(duration_ms: i64) -> String {
+    format!("{:.3}", duration_ms as f64 / 1000.0)
+}
+
+/// Escape text for XML content and attribute values, dropping characters
+/// XML 1.0 can't represent (e.g. stray control characters in error output).
+fn escape_xml(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break.
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn job() -> test_job::Model {
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        test_job::Model {
+            id: Uuid::from_u128(0xb),
+            test_report_id: Uuid::from_u128(0xa),
+            status: "complete".to_string(),
+            html_upload_status: None,
+            screenshots_upload_status: None,
+            json_upload_status: None,
+            html_path: None,
+            github_metadata: Some(serde_json::json!({"job_name": "e2e (chromium)"})),
+            environment: Some(serde_json::json!({"os": "linux", "browser": "chromium"})),
+            error_message: None,
+            duration_ms: None,
+            start_time: None,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    fn suite(id: u128, title: &str) -> test_suite::Model {
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        test_suite::Model {
+            id: Uuid::from_u128(id),
+            test_job_id: Uuid::from_u128(0xb),
+            title: title.to_string(),
+            file_path: Some(format!("tests/{}.spec.ts", title)),
+            total_count: 1,
+            passed_count: 0,
+            failed_count: 1,
+            skipped_count: 0,
+            flaky_count: 0,
+            quarantined_count: 0,
+            known_issue_count: 0,
+            duration_ms: 1500,
+            start_time: None,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    fn case(
+        suite_id: u128,
+        sequence: i32,
+        title: &str,
+        status: &str,
+        retry: i32,
+    ) -> test_case::Model {
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        test_case::Model {
+            id: Uuid::from_u128(0x100 + sequence as u128),
+            test_suite_id: Uuid::from_u128(suite_id),
+            test_job_id: Uuid::from_u128(0xb),
+            title: title.to_string(),
+            full_title: format!("[chromium] {}", title),
+            status: status.to_string(),
+            duration_ms: 500,
+            retry_count: retry,
+            error_message: is_failure(status).then(|| {
+                format!(
+                    "\u{1b}[31mError\u{1b}[39m: attempt {} <failed>\n  at spec.ts:3",
+                    retry
+                )
+            }),
+            failure_signature: None,
+            quarantined: false,
+            owner: None,
+            known_issue_key: None,
+            sequence,
+            attachments: None,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    fn render(
+        format: ResultsFormat,
+        suites: &[test_suite::Model],
+        cases: Vec<test_case::Model>,
+    ) -> String {
+        let job = job();
+        let mut results = JobResults::new(format, &job, suites);
+        let mut out = String::new();
+        for case in cases {
+            results.push(case, &mut out);
+        }
+        results.finish(&mut out);
+        out
+    }
+
+    #[test]
+    fn test_junit_retries() {
+        let suites = [suite(1, "login"), suite(2, "empty"), suite(3, "cart")];
+        let out = render(
+            ResultsFormat::JUnit,
+            &suites,
+            vec![
+                case(1, 0, "logs in", "failed", 0),
+                case(1, 1, "logs in", "failed", 1),
+                case(1, 2, "logs in", "failed", 2),
+                case(3, 3, "adds item", "failed", 0),
+                case(3, 4, "adds item", "flaky", 1),
+            ],
+        );
+
+        assert_eq!(out.matches("<testcase ").count(), 2);
+        assert_eq!(out.matches("<testsuite ").count(), 3);
+        assert_eq!(out.matches("</testsuite>").count(), 3);
+        assert!(out.contains("<testsuite name=\"empty\""));
+        assert!(out.contains(
+            "<failure message=\"Error: attempt 2 &lt;failed&gt;\" type=\"failed\">Error: attempt 2 &lt;failed&gt;\n  at spec.ts:3</failure>"
+        ));
+        assert_eq!(out.matches("<rerunFailure ").count(), 2);
+        assert_eq!(out.matches("<flakyFailure ").count(), 1);
+        assert!(out.contains("time=\"1.500\""));
+        assert!(out.contains("<property name=\"job_name\" value=\"e2e (chromium)\"/>"));
+        assert!(!out.contains('\u{1b}'));
+
+        // Suites open in ID order, each test inside its own suite
+        let login = out.find("name=\"login\"").unwrap();
+        let empty = out.find("name=\"empty\"").unwrap();
+        let cart = out.find("name=\"cart\"").unwrap();
+        assert!(login < out.find("logs in").unwrap());
+        assert!(empty < cart && cart < out.find("adds item").unwrap());
+    }
+
+    #[test]
+    fn test_junit_quarantine_and_attachments() {
+        let suites = [suite(1, "login")];
+        let mut quarantined = case(1, 0, "logs in", "failed", 0);
+        quarantined.quarantined = true;
+        quarantined.attachments = Some(serde_json::json!([
+            {"path": "a b.png", "s3_key": "reports/r/jobs/j/a b.png", "missing": false},
+            {"path": "gone.png", "missing": true}
+        ]));
+
+        let out = render(ResultsFormat::JUnit, &suites, vec![quarantined]);
+        assert!(out.contains(
+            "<skipped message=\"Quarantined failure: Error: attempt 0 &lt;failed&gt;\"/>"
+        ));
+        assert!(!out.contains("<failure"));
+        assert!(out.contains("<property name=\"quarantined\" value=\"true\"/>"));
+        assert!(out.contains(
+            "<system-out>[[ATTACHMENT|/files/reports/r/jobs/j/a%20b.png]]\n</system-out>"
+        ));
+    }
+
+    #[test]
+    fn test_csv_rows() {
+        let suites = [suite(1, "login")];
+        let out = render(
+            ResultsFormat::Csv,
+            &suites,
+            vec![
+                case(1, 0, "logs in, twice", "failed", 0),
+                case(1, 1, "logs in, twice", "flaky", 1),
+                case(9, 2, "orphan", "passed", 0),
+            ],
+        );
+
+        let rows: Vec<&str> = out.split("\r\n").filter(|r| !r.is_empty()).collect();
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].starts_with(&format!(
+            "{},{},e2e (chromium),linux,chromium,,login,tests/login.spec.ts,\"logs in, twice\",\"[chromium] logs in, twice\",flaky,1000,1,false,,,,",
+            Uuid::from_u128(0x101),
+            Uuid::from_u128(0xb)
+        )));
+    }
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(
+            escape_xml("a<b>&\"c'\u{1b}\u{0}\n"),
+            "a&lt;b&gt;&amp;&quot;c&apos;\n"
+        );
+        assert_eq!(escape_csv("plain"), "plain");
+        assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv("two\nlines"), "\"two\nlines\"");
+    }
+}
@@ -0,0 +1,326 @@
+//! Data retention: expire old reports and artifacts, purge deleted rows.
+//!
+//! A scheduled run works in three steps:
+//! 1. Soft-delete reports past their kind's retention (default branch, other
+//!    branch, pull request) and delete their jobs' files from storage.
+//! 2. Delete HTML reports and screenshots of kept reports past the artifact
+//!    retention; extracted results stay.
+//! 3. Hard-delete reports soft-deleted longer than the grace period.
+//!
+//! Each step claims its rows with a conditional update, so instances running
+//! at the same time don't process the same report twice. Deleting storage
+//! objects is idempotent, and purging deletes a report's prefixes again
+//! before removing rows, so failed deletions are retried.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::{error, info};
+
+use crate::config::RetentionSettings;
+use crate::db::DbPool;
+use crate::db::retention::JobKey;
+use crate::error::AppResult;
+use crate::models::{ReportClass, RetentionClassPreview, RetentionPreview};
+use crate::services::Storage;
+
+/// Reports or jobs handled per batch.
+const BATCH_SIZE: u64 = 200;
+
+/// Maximum number of report IDs listed in a preview.
+const PREVIEW_SAMPLE_SIZE: u64 = 100;
+
+/// Retention cutoffs at a point in time. `None` keeps data forever.
+#[derive(Debug, Clone)]
+pub struct RetentionCutoffs {
+    pub default_branch: Option<DateTime<Utc>>,
+    pub branch: Option<DateTime<Utc>>,
+    pub pull_request: Option<DateTime<Utc>>,
+    pub artifacts: Option<DateTime<Utc>>,
+    pub purge: DateTime<Utc>,
+    /// Git refs of default branches, both bare (`main`) and full
+    /// (`refs/heads/main`).
+    pub default_refs: Vec<String>,
+}
+
+impl RetentionCutoffs {
+    /// Compute cutoffs from settings.
+    pub fn new(settings: &RetentionSettings, now: DateTime<Utc>) -> Self {
+        let days_before = |days: u32| (days > 0).then(|| now - chrono::Duration::days(days as i64));
+
+        let default_refs = settings
+            .default_branches
+            .iter()
+            .flat_map(|b| {
+                let branch = b.trim_start_matches("refs/heads/");
+                [branch.to_string(), format!("refs/heads/{}", branch)]
+            })
+            .collect();
+
+        Self {
+            default_branch: days_before(settings.default_branch_days),
+            branch: days_before(settings.branch_days),
+            pull_request: days_before(settings.pr_days),
+            artifacts: days_before(settings.artifact_days),
+            purge: now - chrono::Duration::days(settings.purge_grace_days as i64),
+            default_refs,
+        }
+    }
+
+    /// Whether any kind of report expires.
+    pub fn expires_reports(&self) -> bool {
+        self.default_branch.is_some() || self.branch.is_some() || self.pull_request.is_some()
+    }
+
+    /// Cutoff for a kind of report.
+    pub fn for_class(&self, class: ReportClass) -> Option<DateTime<Utc>> {
+        match class {
+            ReportClass::DefaultBranch => self.default_branch,
+            ReportClass::Branch => self.branch,
+            ReportClass::PullRequest => self.pull_request,
+        }
+    }
+}
+
+/// Totals of one retention run.
+#[derive(Debug, Default)]
+pub struct RetentionRunSummary {
+    pub reports_deleted: u64,
+    pub artifact_jobs: u64,
+    pub objects_deleted: u64,
+    pub reports_purged: u64,
+}
+
+/// Run retention on a schedule until the process exits.
+///
+/// Does nothing unless `TSIO_RETENTION_ENABLED` is set. In dry-run mode each
+/// run logs what would be removed.
+pub async fn run(pool: DbPool, storage: Storage, settings: RetentionSettings) {
+    if !settings.enabled {
+        info!("Retention disabled");
+        return;
+    }
+
+    let mut interval = tokio::time::interval(Duration::from_secs(settings.interval_hours * 3600));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    info!(
+        "Retention started (every {}h{})",
+        settings.interval_hours,
+        if settings.dry_run { ", dry run" } else { "" }
+    );
+
+    loop {
+        interval.tick().await;
+
+        if settings.dry_run {
+            match preview(&pool, &settings, Utc::now()).await {
+                Ok(preview) => log_preview(&preview),
+                Err(e) => error!("Retention dry run failed: {}", e),
+            }
+            continue;
+        }
+
+        match apply(&pool, &storage, &settings, Utc::now()).await {
+            Ok(summary) => info!(
+                "Retention run complete: {} reports deleted, {} jobs' artifacts removed, {} objects deleted, {} reports purged",
+                summary.reports_deleted,
+                summary.artifact_jobs,
+                summary.objects_deleted,
+                summary.reports_purged
+            ),
+            Err(e) => error!("Retention run failed: {}", e),
+        }
+    }
+}
+
+fn log_preview(preview: &RetentionPreview) {
+    for class in &preview.classes {
+        info!(
+            "Retention dry run: {} {} reports would be deleted",
+            class.reports,
+            class.class.as_str()
+        );
+    }
+    info!(
+        "Retention dry run: {} files ({} bytes) from {} jobs would be deleted, {} reports would be purged",
+        preview.artifact_files,
+        preview.artifact_bytes,
+        preview.artifact_jobs,
+        preview.purgeable_reports
+    );
+}
+
+/// Report what a run would remove, without removing anything.
+pub async fn preview(
+    pool: &DbPool,
+    settings: &RetentionSettings,
+    now: DateTime<Utc>,
+) -> AppResult<RetentionPreview> {
+    let cutoffs = RetentionCutoffs::new(settings, now);
+
+    let counts = pool.count_expired_reports(&cutoffs).await?;
+    let classes = [
+        (ReportClass::DefaultBranch, settings.default_branch_days),
+        (ReportClass::Branch, settings.branch_days),
+        (ReportClass::PullRequest, settings.pr_days),
+    ]
+    .into_iter()
+    .map(|(class, retention_days)| RetentionClassPreview {
+        class,
+        retention_days,
+        cutoff: cutoffs.for_class(class),
+        reports: counts
+            .iter()
+            .find(|c| c.class == class.as_str())
+            .map_or(0, |c| c.reports),
+    })
+    .collect();
+
+    let sample_report_ids = pool
+        .find_expired_reports(&cutoffs, PREVIEW_SAMPLE_SIZE)
+        .await?;
+    let artifacts = pool.count_expired_artifacts(&cutoffs).await?;
+    let purgeable_reports = pool.count_purgeable_reports(&cutoffs).await?;
+
+    Ok(RetentionPreview {
+        dry_run: settings.dry_run,
+        enabled: settings.enabled,
+        generated_at: now,
+        classes,
+        sample_report_ids,
+        artifact_retention_days: settings.artifact_days,
+        artifact_cutoff: cutoffs.artifacts,
+        artifact_jobs: artifacts.jobs,
+        artifact_files: artifacts.files,
+        artifact_bytes: artifacts.bytes,
+        purge_grace_days: settings.purge_grace_days,
+        purge_cutoff: cutoffs.purge,
+        purgeable_reports,
+    })
+}
+
+/// Delete the storage prefixes of jobs.
+async fn delete_job_files(storage: &Storage, jobs: &[JobKey]) -> AppResult<u64> {
+    let mut deleted = 0;
+    for job in jobs {
+        if let Some(job_id) = job.job_id {
+            let prefix = Storage::job_key_prefix(&job.report_id.to_string(), &job_id.to_string());
+            deleted += storage.delete_prefix(&prefix).await?;
+        }
+    }
+    Ok(deleted)
+}
+
+/// Apply retention once.
+pub async fn apply(
+    pool: &DbPool,
+    storage: &Storage,
+    settings: &RetentionSettings,
+    now: DateTime<Utc>,
+) -> AppResult<RetentionRunSummary> {
+    let cutoffs = RetentionCutoffs::new(settings, now);
+    let mut summary = RetentionRunSummary::default();
+
+    // 1. Expired reports
+    if cutoffs.expires_reports() {
+        loop {
+            let jobs = pool
+                .soft_delete_expired_reports(&cutoffs, BATCH_SIZE)
+                .await?;
+            let mut reports: Vec<_> = jobs.iter().map(|j| j.report_id).collect();
+            reports.dedup();
+
+            summary.reports_deleted += reports.len() as u64;
+            summary.objects_deleted += delete_job_files(storage, &jobs).await?;
+
+            if (reports.len() as u64) < BATCH_SIZE {
+                break;
+            }
+        }
+    }
+
+    // 2. Expired artifacts of kept reports
+    if let Some(cutoff) = cutoffs.artifacts {
+        loop {
+            let (jobs, keys) = pool.clear_expired_artifacts(cutoff, BATCH_SIZE).await?;
+            summary.artifact_jobs += jobs;
+            summary.objects_deleted += storage.delete(&keys).await?;
+
+            if jobs < BATCH_SIZE {
+                break;
+            }
+        }
+    }
+
+    // 3. Purge reports past the grace period
+    loop {
+        let jobs = pool.find_purgeable_reports(&cutoffs, BATCH_SIZE).await?;
+        if jobs.is_empty() {
+            break;
+        }
+
+        summary.objects_deleted += delete_job_files(storage, &jobs).await?;
+
+        let mut reports: Vec<_> = jobs.iter().map(|j| j.report_id).collect();
+        reports.dedup();
+        let purged = pool.purge_reports(&reports, cutoffs.purge).await?;
+        summary.reports_purged += purged;
+
+        // Stop if another instance purged or restored the batch first
+        if purged == 0 || (reports.len() as u64) < BATCH_SIZE {
+            break;
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> RetentionSettings {
+        RetentionSettings {
+            enabled: true,
+            dry_run: false,
+            default_branches: vec!["main".to_string(), "refs/heads/release".to_string()],
+            default_branch_days: 365,
+            branch_days: 0,
+            pr_days: 30,
+            artifact_days: 14,
+            purge_grace_days: 7,
+            interval_hours: 24,
+        }
+    }
+
+    #[test]
+    fn test_retention_cutoffs() {
+        let now = Utc::now();
+        let cutoffs = RetentionCutoffs::new(&settings(), now);
+
+        assert_eq!(
+            cutoffs.default_branch,
+            Some(now - chrono::Duration::days(365))
+        );
+        assert_eq!(cutoffs.branch, None);
+        assert_eq!(
+            cutoffs.for_class(ReportClass::PullRequest),
+            Some(now - chrono::Duration::days(30))
+        );
+        assert_eq!(cutoffs.artifacts, Some(now - chrono::Duration::days(14)));
+        assert_eq!(cutoffs.purge, now - chrono::Duration::days(7));
+        assert!(cutoffs.expires_reports());
+        assert_eq!(
+            cutoffs.default_refs,
+            vec!["main", "refs/heads/main", "release", "refs/heads/release"]
+        );
+
+        let keep_all = RetentionSettings {
+            default_branch_days: 0,
+            pr_days: 0,
+            ..settings()
+        };
+        assert!(!RetentionCutoffs::new(&keep_all, now).expires_reports());
+    }
+}
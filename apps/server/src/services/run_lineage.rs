@@ -0,0 +1,223 @@
+//! Merging test results across attempts of a workflow run.
+//!
+//! Within one attempt a test's status is its final retry; when several jobs
+//! of the attempt ran the same test, the worst status counts. Across attempts
+//! the last attempt a test ran in wins, so tests skipped by "re-run failed
+//! jobs" keep their earlier result.
+
+use std::collections::BTreeMap;
+
+use crate::models::{Framework, ReportOutcome, RunSummary, RunTestResult, TestAttemptStatus};
+
+/// Final status of a test in one job of an attempt.
+#[derive(Debug, Clone)]
+pub struct AttemptTest<'a> {
+    pub run_attempt: i32,
+    pub framework: Framework,
+    pub full_title: &'a str,
+    pub status: &'a str,
+    pub quarantined: bool,
+}
+
+/// Parse a GitHub run attempt, defaulting to the first attempt.
+pub fn parse_run_attempt(run_attempt: Option<&str>) -> i32 {
+    run_attempt
+        .and_then(|a| a.trim().parse().ok())
+        .filter(|a| *a >= 1)
+        .unwrap_or(1)
+}
+
+/// Whether a final test status is a failure.
+pub fn is_failure(status: &str) -> bool {
+    matches!(status, "failed" | "timedOut")
+}
+
+fn is_pass(status: &str) -> bool {
+    matches!(status, "passed" | "flaky")
+}
+
+/// Rank statuses so the worst one wins within an attempt.
+fn severity(status: &str) -> u8 {
+    match status {
+        "failed" | "timedOut" => 4,
+        "flaky" => 3,
+        "passed" => 2,
+        "skipped" => 1,
+        _ => 0,
+    }
+}
+
+/// Status and quarantine flag of a test per attempt.
+type AttemptStatuses<'a> = BTreeMap<i32, (&'a str, bool)>;
+
+/// Merge per-job statuses into one result per test, sorted by title.
+pub fn merge_attempts(tests: &[AttemptTest<'_>]) -> Vec<RunTestResult> {
+    let mut by_test: BTreeMap<(&str, &str), (Framework, AttemptStatuses<'_>)> = BTreeMap::new();
+
+    for t in tests {
+        let (_, attempts) = by_test
+            .entry((t.full_title, t.framework.as_str()))
+            .or_insert_with(|| (t.framework, BTreeMap::new()));
+        let entry = attempts
+            .entry(t.run_attempt)
+            .or_insert((t.status, t.quarantined));
+        if severity(t.status) > severity(entry.0) {
+            *entry = (t.status, t.quarantined);
+        } else if t.status == entry.0 {
+            entry.1 |= t.quarantined;
+        }
+    }
+
+    by_test
+        .into_iter()
+        .filter_map(|((full_title, _), (framework, attempts))| {
+            let (&final_attempt, &(status, quarantined)) = attempts.last_key_value()?;
+            let passed_on_rerun = is_pass(status)
+                && attempts
+                    .range(..final_attempt)
+                    .any(|(_, (s, _))| is_failure(s));
+
+            Some(RunTestResult {
+                full_title: full_title.to_string(),
+                framework,
+                status: status.to_string(),
+                final_attempt,
+                passed_on_rerun,
+                quarantined: quarantined && is_failure(status),
+                attempts: attempts
+                    .iter()
+                    .map(|(&run_attempt, (s, _))| TestAttemptStatus {
+                        run_attempt,
+                        status: s.to_string(),
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Count merged results and derive the run's final outcome.
+pub fn summarize(tests: &[RunTestResult]) -> RunSummary {
+    let mut summary = RunSummary {
+        total: tests.len() as i32,
+        ..Default::default()
+    };
+
+    for t in tests {
+        match t.status.as_str() {
+            "passed" => summary.passed += 1,
+            "flaky" => summary.flaky += 1,
+            "skipped" => summary.skipped += 1,
+            s if is_failure(s) && t.quarantined => summary.quarantined += 1,
+            s if is_failure(s) => summary.failed += 1,
+            _ => {}
+        }
+        if t.passed_on_rerun {
+            summary.passed_on_rerun += 1;
+        }
+    }
+
+    summary.outcome = ReportOutcome::from_counts(summary.failed, summary.quarantined);
+    summary
+}
+
+/// Whether a merged result is worth listing: it did not pass cleanly in
+/// every attempt.
+pub fn needs_attention(test: &RunTestResult) -> bool {
+    test.passed_on_rerun || !matches!(test.status.as_str(), "passed" | "skipped")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test<'a>(run_attempt: i32, full_title: &'a str, status: &'a str) -> AttemptTest<'a> {
+        AttemptTest {
+            run_attempt,
+            framework: Framework::Playwright,
+            full_title,
+            status,
+            quarantined: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_run_attempt() {
+        assert_eq!(parse_run_attempt(Some("3")), 3);
+        assert_eq!(parse_run_attempt(Some("0")), 1);
+        assert_eq!(parse_run_attempt(Some("x")), 1);
+        assert_eq!(parse_run_attempt(None), 1);
+    }
+
+    #[test]
+    fn test_last_attempt_wins() {
+        let merged = merge_attempts(&[
+            test(1, "a", "failed"),
+            test(2, "a", "passed"),
+            test(1, "b", "passed"),
+            test(1, "c", "passed"),
+            test(2, "c", "failed"),
+            test(1, "d", "timedOut"),
+            test(2, "d", "flaky"),
+        ]);
+
+        let titles: Vec<_> = merged.iter().map(|t| t.full_title.as_str()).collect();
+        assert_eq!(titles, vec!["a", "b", "c", "d"]);
+
+        assert_eq!(merged[0].status, "passed");
+        assert_eq!(merged[0].final_attempt, 2);
+        assert!(merged[0].passed_on_rerun);
+        assert_eq!(merged[0].attempts.len(), 2);
+
+        // Only ran in the first attempt
+        assert_eq!(merged[1].final_attempt, 1);
+        assert!(!merged[1].passed_on_rerun);
+
+        assert_eq!(merged[2].status, "failed");
+        assert!(!merged[2].passed_on_rerun);
+        assert!(merged[3].passed_on_rerun);
+
+        let summary = summarize(&merged);
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.passed, 2);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.flaky, 1);
+        assert_eq!(summary.passed_on_rerun, 2);
+        assert_eq!(summary.outcome, ReportOutcome::Failed);
+
+        let listed: Vec<_> = merged
+            .iter()
+            .filter(|t| needs_attention(t))
+            .map(|t| t.full_title.as_str())
+            .collect();
+        assert_eq!(listed, vec!["a", "c", "d"]);
+    }
+
+    #[test]
+    fn test_worst_status_within_attempt() {
+        let mut quarantined = test(2, "a", "failed");
+        quarantined.quarantined = true;
+        let mut cypress = test(1, "a", "failed");
+        cypress.framework = Framework::Cypress;
+
+        let merged = merge_attempts(&[
+            test(1, "a", "failed"),
+            test(2, "a", "passed"),
+            quarantined,
+            cypress,
+        ]);
+
+        assert_eq!(merged.len(), 2);
+        let playwright = merged
+            .iter()
+            .find(|t| t.framework == Framework::Playwright)
+            .unwrap();
+        assert_eq!(playwright.status, "failed");
+        assert!(playwright.quarantined);
+        assert!(!playwright.passed_on_rerun);
+
+        let summary = summarize(&merged);
+        assert_eq!(summary.quarantined, 1);
+        assert_eq!(summary.failed, 1);
+    }
+}
@@ -0,0 +1,149 @@
+//! Global search helpers: pagination cursors and highlight rendering.
+//!
+//! Postgres marks matched terms in `ts_headline` output with private-use
+//! characters rather than HTML tags, so the surrounding text (titles and
+//! error messages often contain markup) can be escaped before the marks are
+//! turned into `<mark>` elements.
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use uuid::Uuid;
+
+/// Start-of-match delimiter passed to `ts_headline`.
+pub const HIGHLIGHT_START: char = '\u{E000}';
+
+/// End-of-match delimiter passed to `ts_headline`.
+pub const HIGHLIGHT_STOP: char = '\u{E001}';
+
+/// Encode the position after the last hit of a page.
+pub fn encode_cursor(rank: f64, id: Uuid) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{}:{}", rank, id))
+}
+
+/// Decode a cursor produced by [`encode_cursor`].
+pub fn decode_cursor(cursor: &str) -> Option<(f64, Uuid)> {
+    let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    let text = String::from_utf8(bytes).ok()?;
+    let (rank, id) = text.split_once(':')?;
+    let rank: f64 = rank.parse().ok().filter(|r: &f64| r.is_finite())?;
+    Some((rank, id.parse().ok()?))
+}
+
+fn push_escaped(html: &mut String, c: char) {
+    match c {
+        '&' => html.push_str("&amp;"),
+        '<' => html.push_str("&lt;"),
+        '>' => html.push_str("&gt;"),
+        '"' => html.push_str("&quot;"),
+        '\'' => html.push_str("&#39;"),
+        c => html.push(c),
+    }
+}
+
+/// HTML-escape text.
+pub fn escape_html(text: &str) -> String {
+    let mut html = String::with_capacity(text.len());
+    for c in text.chars() {
+        push_escaped(&mut html, c);
+    }
+    html
+}
+
+/// Render `ts_headline` output as HTML with matches wrapped in `<mark>`.
+///
+/// Returns `None` when nothing in the text matched.
+pub fn render_highlight(headline: &str) -> Option<String> {
+    if !headline.contains(HIGHLIGHT_START) {
+        return None;
+    }
+
+    let mut html = String::with_capacity(headline.len() + 16);
+    for c in headline.chars() {
+        match c {
+            HIGHLIGHT_START => html.push_str("<mark>"),
+            HIGHLIGHT_STOP => html.push_str("</mark>"),
+            c => push_escaped(&mut html, c),
+        }
+    }
+    Some(html)
+}
+
+/// Highlight occurrences of the query's terms in text (ASCII case-insensitive).
+///
+/// Used where full-text headlines don't apply, such as file paths matched by
+/// trigram similarity. Excluded (`-term`) terms and `or` are ignored.
+pub fn highlight_terms(text: &str, query: &str) -> Option<String> {
+    let haystack = text.to_ascii_lowercase();
+    let mut marked = vec![false; text.len()];
+
+    for term in query.split_whitespace() {
+        let term = term.trim_matches('"');
+        if term.is_empty() || term.starts_with('-') || term.eq_ignore_ascii_case("or") {
+            continue;
+        }
+        let term = term.to_ascii_lowercase();
+        for (start, _) in haystack.match_indices(&term) {
+            marked[start..start + term.len()].fill(true);
+        }
+    }
+
+    let mut headline = String::with_capacity(text.len() + 8);
+    let mut in_match = false;
+    for (i, c) in text.char_indices() {
+        if marked[i] != in_match {
+            in_match = marked[i];
+            headline.push(if in_match {
+                HIGHLIGHT_START
+            } else {
+                HIGHLIGHT_STOP
+            });
+        }
+        headline.push(c);
+    }
+    if in_match {
+        headline.push(HIGHLIGHT_STOP);
+    }
+
+    render_highlight(&headline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let id = Uuid::now_v7();
+        let cursor = encode_cursor(0.123_456_789_012_345_67, id);
+        assert_eq!(decode_cursor(&cursor), Some((0.123_456_789_012_345_67, id)));
+
+        assert_eq!(decode_cursor("not a cursor!"), None);
+        assert_eq!(decode_cursor(&URL_SAFE_NO_PAD.encode("NaN:x")), None);
+    }
+
+    #[test]
+    fn test_render_highlight() {
+        let headline = format!(
+            "expected <div> to be {}visible{} & {}enabled{}",
+            HIGHLIGHT_START, HIGHLIGHT_STOP, HIGHLIGHT_START, HIGHLIGHT_STOP
+        );
+        assert_eq!(
+            render_highlight(&headline).unwrap(),
+            "expected &lt;div&gt; to be <mark>visible</mark> &amp; <mark>enabled</mark>"
+        );
+        assert_eq!(render_highlight("no matches"), None);
+        assert_eq!(escape_html("a < b"), "a &lt; b");
+    }
+
+    #[test]
+    fn test_highlight_terms() {
+        assert_eq!(
+            highlight_terms("e2e/Login/login.spec.ts", "login -spec or").unwrap(),
+            "e2e/<mark>Login</mark>/<mark>login</mark>.spec.ts"
+        );
+        assert_eq!(
+            highlight_terms("e2e/checkout.spec.ts", "\"check\" out").unwrap(),
+            "e2e/<mark>checkout</mark>.spec.ts"
+        );
+        assert_eq!(highlight_terms("e2e/cart.spec.ts", "login"), None);
+    }
+}
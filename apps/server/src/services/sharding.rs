@@ -0,0 +1,104 @@
+//! Shard planning from historical spec file durations.
+//!
+//! Files are assigned with the longest-processing-time-first heuristic: sort
+//! by estimated duration, longest first, and give each file to the shard with
+//! the least work so far. The slowest shard is guaranteed to be within 4/3 of
+//! the optimum, which is plenty given how noisy test durations are.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Estimate for unseen files when there is no history at all.
+pub const DEFAULT_UNSEEN_FILE_MS: i64 = 30_000;
+
+/// Estimate the duration of files without history.
+///
+/// Uses the median of the known file durations, so a new file is assumed to
+/// be a typical file of the suite.
+pub fn unseen_file_estimate(known_durations: &[i64]) -> i64 {
+    if known_durations.is_empty() {
+        return DEFAULT_UNSEEN_FILE_MS;
+    }
+
+    let mut sorted = known_durations.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Assign weighted items to `shards` bins, balancing total weight.
+///
+/// Returns, per shard, the indexes of the assigned items in assignment order
+/// and the shard's total weight. Ties are broken by item index and shard
+/// index, so the same input always produces the same plan.
+pub fn plan_shards(durations: &[i64], shards: usize) -> Vec<(Vec<usize>, i64)> {
+    let mut plan: Vec<(Vec<usize>, i64)> = vec![(Vec::new(), 0); shards];
+    if shards == 0 {
+        return plan;
+    }
+
+    let mut order: Vec<usize> = (0..durations.len()).collect();
+    order.sort_by_key(|&i| (Reverse(durations[i]), i));
+
+    // Min-heap of (load, shard index)
+    let mut heap: BinaryHeap<Reverse<(i64, usize)>> =
+        (0..shards).map(|s| Reverse((0, s))).collect();
+
+    for item in order {
+        let Some(Reverse((load, shard))) = heap.pop() else {
+            break;
+        };
+        let load = load + durations[item].max(0);
+        plan[shard].0.push(item);
+        plan[shard].1 = load;
+        heap.push(Reverse((load, shard)));
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unseen_file_estimate() {
+        assert_eq!(unseen_file_estimate(&[]), DEFAULT_UNSEEN_FILE_MS);
+        assert_eq!(unseen_file_estimate(&[30, 10, 20]), 20);
+        assert_eq!(unseen_file_estimate(&[40, 10, 20, 30]), 25);
+    }
+
+    #[test]
+    fn test_plan_shards_balances_load() {
+        let durations = [70, 60, 50, 40, 30, 20, 10];
+        let plan = plan_shards(&durations, 3);
+
+        let loads: Vec<i64> = plan.iter().map(|(_, load)| *load).collect();
+        assert_eq!(loads.iter().sum::<i64>(), 280);
+        assert!(loads.iter().max().unwrap() - loads.iter().min().unwrap() <= 20);
+
+        let mut assigned: Vec<usize> = plan.iter().flat_map(|(items, _)| items.clone()).collect();
+        assigned.sort_unstable();
+        assert_eq!(assigned, (0..durations.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_plan_shards_more_shards_than_files() {
+        let plan = plan_shards(&[5, 10], 4);
+        assert_eq!(plan.len(), 4);
+        assert_eq!(plan[0], (vec![1], 10));
+        assert_eq!(plan[1], (vec![0], 5));
+        assert!(plan[2].0.is_empty() && plan[3].0.is_empty());
+    }
+
+    #[test]
+    fn test_plan_shards_is_deterministic() {
+        let durations = [10, 10, 10, 10];
+        assert_eq!(plan_shards(&durations, 2), plan_shards(&durations, 2));
+        assert_eq!(plan_shards(&durations, 2)[0].0, vec![0, 2]);
+    }
+}
@@ -3,14 +3,21 @@
 //! Handles all S3 operations including presigned URLs, delete, and listing.
 //! Supports both AWS S3 and MinIO for development.
 
+use std::path::Path;
+
 use aws_config::BehaviorVersion;
 use aws_sdk_s3::Client;
 use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{Delete, ObjectIdentifier};
 use tracing::info;
 
 use crate::config::StorageSettings;
 use crate::error::{AppError, AppResult};
 
+/// Maximum number of keys per S3 `DeleteObjects` request.
+const DELETE_BATCH_SIZE: usize = 1000;
+
 /// S3 storage client wrapper.
 #[derive(Clone)]
 pub struct Storage {
@@ -128,7 +135,35 @@ impl Storage {
     /// * `data` - The file contents as bytes
     /// * `content_type` - Optional content type for the upload
     pub async fn put(&self, key: &str, data: Vec<u8>, content_type: Option<&str>) -> AppResult<()> {
-        let body = aws_sdk_s3::primitives::ByteStream::from(data);
+        self.put_body(key, ByteStream::from(data), content_type)
+            .await
+    }
+
+    /// Upload a file on disk to S3, streaming it instead of reading it into
+    /// memory.
+    ///
+    /// # Arguments
+    /// * `key` - The S3 object key where the file will be uploaded
+    /// * `path` - The local file to upload
+    /// * `content_type` - Optional content type for the upload
+    pub async fn put_file(
+        &self,
+        key: &str,
+        path: &Path,
+        content_type: Option<&str>,
+    ) -> AppResult<()> {
+        let body = ByteStream::from_path(path)
+            .await
+            .map_err(|e| AppError::Storage(format!("Failed to read {}: {}", path.display(), e)))?;
+        self.put_body(key, body, content_type).await
+    }
+
+    async fn put_body(
+        &self,
+        key: &str,
+        body: ByteStream,
+        content_type: Option<&str>,
+    ) -> AppResult<()> {
         let mut request = self
             .client
             .put_object()
@@ -184,6 +219,123 @@ impl Storage {
         Ok((data, content_type))
     }
 
+    /// List all object keys under a prefix.
+    ///
+    /// # Arguments
+    /// * `prefix` - The S3 key prefix to list
+    pub async fn list(&self, prefix: &str) -> AppResult<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut continuation_token: Option<String> = None;
+
+        loop {
+            let response = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token.take())
+                .send()
+                .await
+                .map_err(|e| {
+                    AppError::Storage(format!(
+                        "Failed to list S3 objects: {}",
+                        e.into_service_error()
+                    ))
+                })?;
+
+            keys.extend(
+                response
+                    .contents()
+                    .iter()
+                    .filter_map(|o| o.key().map(String::from)),
+            );
+
+            match response.next_continuation_token() {
+                Some(token) if response.is_truncated().unwrap_or(false) => {
+                    continuation_token = Some(token.to_string());
+                }
+                _ => break,
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// Delete objects by key, in batches.
+    ///
+    /// Missing keys are not an error. Returns the number of keys deleted.
+    ///
+    /// # Arguments
+    /// * `keys` - The S3 object keys to delete
+    pub async fn delete(&self, keys: &[String]) -> AppResult<u64> {
+        let mut deleted = 0;
+
+        for batch in keys.chunks(DELETE_BATCH_SIZE) {
+            let objects = batch
+                .iter()
+                .map(|key| ObjectIdentifier::builder().key(key).build())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| AppError::Storage(format!("Invalid S3 key: {}", e)))?;
+            let delete = Delete::builder()
+                .set_objects(Some(objects))
+                .quiet(true)
+                .build()
+                .map_err(|e| AppError::Storage(format!("Invalid S3 delete request: {}", e)))?;
+
+            let response = self
+                .client
+                .delete_objects()
+                .bucket(&self.bucket)
+                .delete(delete)
+                .send()
+                .await
+                .map_err(|e| {
+                    AppError::Storage(format!(
+                        "Failed to delete S3 objects: {}",
+                        e.into_service_error()
+                    ))
+                })?;
+
+            // Quiet mode only reports failures
+            if let Some(error) = response.errors().first() {
+                return Err(AppError::Storage(format!(
+                    "Failed to delete {} S3 objects (first: {}: {})",
+                    response.errors().len(),
+                    error.key().unwrap_or_default(),
+                    error.message().unwrap_or_default()
+                )));
+            }
+
+            deleted += batch.len() as u64;
+        }
+
+        Ok(deleted)
+    }
+
+    /// Delete every object under a prefix.
+    ///
+    /// Returns the number of objects deleted.
+    ///
+    /// # Arguments
+    /// * `prefix` - The S3 key prefix to delete (e.g. from [`Storage::job_key_prefix`])
+    pub async fn delete_prefix(&self, prefix: &str) -> AppResult<u64> {
+        // Keep the trailing slash so "reports/a/jobs/b" doesn't match "reports/a/jobs/bc"
+        let prefix = format!("{}/", prefix.trim_end_matches('/'));
+        let keys = self.list(&prefix).await?;
+        self.delete(&keys).await
+    }
+
+    /// Build an S3 key prefix for all files of a report.
+    ///
+    /// # Arguments
+    /// * `report_id` - The report UUID
+    ///
+    /// # Returns
+    /// S3 key prefix in format: reports/{report_id}
+    pub fn report_key_prefix(report_id: &str) -> String {
+        format!("reports/{}", report_id)
+    }
+
     /// Build an S3 key prefix for a job's files.
     ///
     /// # Arguments
@@ -214,6 +366,12 @@ impl Storage {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_report_key_prefix() {
+        let prefix = Storage::report_key_prefix("report-123");
+        assert_eq!(prefix, "reports/report-123");
+    }
+
     #[test]
     fn test_job_key_prefix() {
         let prefix = Storage::job_key_prefix("report-123", "job-456");
@@ -0,0 +1,65 @@
+//! Helpers for user-supplied text and test titles.
+
+/// Trim an optional string, treating blank values as absent.
+pub fn non_empty(value: Option<impl AsRef<str>>) -> Option<String> {
+    value
+        .map(|v| v.as_ref().trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Split the `[project] ` prefix extraction adds to Playwright titles off a
+/// full title, returning the project (if any) and the remaining title.
+pub fn split_project(full_title: &str) -> (Option<&str>, &str) {
+    full_title
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once("] "))
+        .map_or((None, full_title), |(project, title)| {
+            (Some(project), title)
+        })
+}
+
+/// Strip the `[project] ` prefix extraction adds to Playwright titles.
+pub fn strip_project(full_title: &str) -> &str {
+    split_project(full_title).1
+}
+
+/// Escape regular expression metacharacters.
+pub fn escape_regex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_empty() {
+        assert_eq!(non_empty(Some("  a b ")), Some("a b".to_string()));
+        assert_eq!(non_empty(Some(" ".to_string())), None);
+        assert_eq!(non_empty(None::<&str>), None);
+    }
+
+    #[test]
+    fn test_split_project() {
+        assert_eq!(
+            split_project("[chromium] logs in"),
+            (Some("chromium"), "logs in")
+        );
+        assert_eq!(split_project("logs in"), (None, "logs in"));
+        assert_eq!(split_project("[wip] "), (Some("wip"), ""));
+        assert_eq!(split_project("[no space]x"), (None, "[no space]x"));
+        assert_eq!(strip_project("[Mobile Safari] a > b"), "a > b");
+    }
+
+    #[test]
+    fn test_escape_regex() {
+        assert_eq!(escape_regex("a.b (c)"), r"a\.b \(c\)");
+    }
+}
@@ -0,0 +1,193 @@
+//! Triage carry-forward for failing tests.
+//!
+//! A triage record applies directly to the test case it was made for. It also
+//! carries forward to later failures of the same test with the same failure
+//! signature, so a classification doesn't have to be repeated on every run.
+//! Only records made before a failure carry forward to it.
+//! Direct records win over inherited ones; otherwise the most recently
+//! updated record is used.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::models::{TriageRecord, TriageState};
+
+/// Resolves the triage state of test cases from candidate records.
+#[derive(Debug, Default)]
+pub struct TriageResolver {
+    records: Vec<TriageRecord>,
+}
+
+impl TriageResolver {
+    /// Build a resolver from the records that apply to a repository.
+    ///
+    /// Records without a repository apply everywhere.
+    pub fn new(records: Vec<TriageRecord>, repository: Option<&str>) -> Self {
+        let records = records
+            .into_iter()
+            .filter(|r| {
+                r.repository
+                    .as_deref()
+                    .is_none_or(|r| Some(r) == repository)
+            })
+            .collect();
+        Self { records }
+    }
+
+    /// Resolve the triage state of a test case created at `created_at`.
+    pub fn resolve(
+        &self,
+        test_case_id: Uuid,
+        full_title: &str,
+        failure_signature: Option<&str>,
+        created_at: DateTime<Utc>,
+    ) -> Option<TriageState> {
+        let direct = self
+            .records
+            .iter()
+            .filter(|r| r.test_case_id == Some(test_case_id))
+            .max_by_key(|r| r.updated_at);
+        if let Some(record) = direct {
+            return Some(TriageState {
+                record: record.clone(),
+                inherited: false,
+            });
+        }
+
+        let signature = failure_signature?;
+        self.records
+            .iter()
+            .filter(|r| {
+                r.failure_signature.as_deref() == Some(signature)
+                    && r.full_title.as_deref().is_none_or(|t| t == full_title)
+                    && r.created_at < created_at
+            })
+            .max_by_key(|r| r.updated_at)
+            .map(|record| TriageState {
+                record: record.clone(),
+                inherited: true,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TriageClassification;
+    use chrono::{Duration, Utc};
+
+    fn record(
+        test_case_id: Option<Uuid>,
+        full_title: Option<&str>,
+        failure_signature: Option<&str>,
+        age_minutes: i64,
+    ) -> TriageRecord {
+        let at = Utc::now() - Duration::minutes(age_minutes);
+        TriageRecord {
+            id: Uuid::now_v7(),
+            test_case_id,
+            full_title: full_title.map(str::to_string),
+            failure_signature: failure_signature.map(str::to_string),
+            repository: None,
+            classification: TriageClassification::ProductBug,
+            notes: None,
+            issue_url: None,
+            author: "alice".to_string(),
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    #[test]
+    fn test_direct_record_wins() {
+        let case_id = Uuid::now_v7();
+        let direct = record(Some(case_id), Some("A > b"), Some("sig"), 10);
+        let newer = record(None, None, Some("sig"), 1);
+        let direct_id = direct.id;
+        let resolver = TriageResolver::new(vec![newer, direct], None);
+
+        let state = resolver
+            .resolve(case_id, "A > b", Some("sig"), Utc::now())
+            .unwrap();
+        assert_eq!(state.record.id, direct_id);
+        assert!(!state.inherited);
+    }
+
+    #[test]
+    fn test_carries_forward_same_title_and_signature() {
+        let earlier_case = Uuid::now_v7();
+        let old = record(Some(earlier_case), Some("A > b"), Some("sig"), 10);
+        let new = record(Some(earlier_case), Some("A > b"), Some("sig"), 5);
+        let new_id = new.id;
+        let resolver = TriageResolver::new(vec![old, new], None);
+
+        let state = resolver
+            .resolve(Uuid::now_v7(), "A > b", Some("sig"), Utc::now())
+            .unwrap();
+        assert_eq!(state.record.id, new_id);
+        assert!(state.inherited);
+
+        // Different title, different signature, or no failure: no carry-forward
+        assert!(
+            resolver
+                .resolve(Uuid::now_v7(), "A > c", Some("sig"), Utc::now())
+                .is_none()
+        );
+        assert!(
+            resolver
+                .resolve(Uuid::now_v7(), "A > b", Some("other"), Utc::now())
+                .is_none()
+        );
+        assert!(
+            resolver
+                .resolve(Uuid::now_v7(), "A > b", None, Utc::now())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_signature_record_and_repository_scope() {
+        let mut scoped = record(None, None, Some("sig"), 1);
+        scoped.repository = Some("org/repo".to_string());
+        let resolver = TriageResolver::new(vec![scoped.clone()], Some("org/repo"));
+        assert!(
+            resolver
+                .resolve(Uuid::now_v7(), "Any > test", Some("sig"), Utc::now())
+                .is_some()
+        );
+
+        let resolver = TriageResolver::new(vec![scoped], Some("org/other"));
+        assert!(
+            resolver
+                .resolve(Uuid::now_v7(), "Any > test", Some("sig"), Utc::now())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_only_earlier_records_carry_forward() {
+        let earlier_case = Uuid::now_v7();
+        let triage = record(Some(earlier_case), Some("A > b"), Some("sig"), 5);
+        let resolver = TriageResolver::new(vec![triage], None);
+
+        // A failure from before the record was made keeps its own state
+        let failed_at = Utc::now() - Duration::minutes(10);
+        assert!(
+            resolver
+                .resolve(Uuid::now_v7(), "A > b", Some("sig"), failed_at)
+                .is_none()
+        );
+        assert!(
+            resolver
+                .resolve(Uuid::now_v7(), "A > b", Some("sig"), Utc::now())
+                .is_some()
+        );
+
+        // A record made for the case itself applies whenever it was made
+        assert!(
+            resolver
+                .resolve(earlier_case, "A > b", Some("sig"), failed_at)
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Background sweeper enforcing upload timeouts.
+//!
+//! Jobs whose html, screenshot or JSON uploads stay `started` without
+//! activity for longer than `TSIO_FEATURE_UPLOAD_TIMEOUT_MS` are marked
+//! `timedout`, and reports left initializing or uploading are finalized so
+//! they don't wait forever on jobs that never upload. Open reports are
+//! finalized once idle for `TSIO_FEATURE_OPEN_REPORT_IDLE_TIMEOUT_MS`. Every
+//! instance runs the sweeper; a Postgres advisory lock makes sure only one
+//! sweeps at a time.
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use chrono::Utc;
+use tracing::{error, info, warn};
+
+use crate::config::FeatureSettings;
+use crate::db::DbPool;
+use crate::models::{WsEvent, WsEventMessage};
+use crate::services::EventBroadcaster;
+use crate::services::pipeline;
+
+/// Longest wait between sweeps.
+const MAX_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Run the sweeper until the process exits.
+///
+/// Does nothing when both the upload timeout and the open report idle
+/// timeout are 0 (disabled).
+pub async fn run(pool: DbPool, broadcaster: EventBroadcaster, features: FeatureSettings) {
+    let timeout_ms = features.upload_timeout_ms;
+    let idle_timeout_ms = features.open_report_idle_timeout_ms;
+    let Some(shortest_ms) = [timeout_ms, idle_timeout_ms]
+        .into_iter()
+        .filter(|&ms| ms > 0)
+        .min()
+    else {
+        info!("Upload and open report idle timeouts disabled, sweeper not started");
+        return;
+    };
+
+    // Sweep often enough that uploads time out close to the configured limit
+    let period =
+        Duration::from_millis(shortest_ms / 4).clamp(Duration::from_secs(1), MAX_SWEEP_INTERVAL);
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    info!(
+        "Upload timeout sweeper started (timeout={}ms, open report idle timeout={}ms, every {}s)",
+        timeout_ms,
+        idle_timeout_ms,
+        period.as_secs()
+    );
+
+    loop {
+        interval.tick().await;
+        sweep(&pool, &broadcaster, &features).await;
+    }
+}
+
+/// Sweep once, broadcasting updates for affected jobs, reports and pipeline
+/// runs.
+async fn sweep(pool: &DbPool, broadcaster: &EventBroadcaster, features: &FeatureSettings) {
+    let now = Utc::now();
+    let cutoff = |ms: u64| (ms > 0).then(|| now - chrono::Duration::milliseconds(ms as i64));
+
+    let sweep = match pool
+        .sweep_upload_timeouts(
+            cutoff(features.upload_timeout_ms),
+            cutoff(features.open_report_idle_timeout_ms),
+        )
+        .await
+    {
+        Ok(Some(sweep)) => sweep,
+        // Another instance is sweeping
+        Ok(None) => return,
+        Err(e) => {
+            error!("Upload timeout sweep failed: {}", e);
+            return;
+        }
+    };
+
+    let mut report_ids = BTreeSet::new();
+    let mut pipeline_ids = BTreeSet::new();
+
+    for job in &sweep.jobs {
+        warn!(
+            "Uploads timed out: report_id={}, job_id={}, status={}",
+            job.test_report_id, job.id, job.status
+        );
+        broadcaster.send(WsEventMessage::new(WsEvent::job_updated(
+            job.test_report_id,
+            job.id,
+            job.status.clone(),
+        )));
+        report_ids.insert(job.test_report_id);
+    }
+
+    for report in &sweep.reports {
+        warn!(
+            "Report {} stalled past the upload or idle timeout, marked {}",
+            report.id, report.status
+        );
+
+        // Finalized with partial results; flag regressions as on normal completion
+        if report.status == "complete"
+            && let Err(e) = pool.detect_duration_regressions(report.id, features).await
+        {
+            warn!(
+                "Failed to detect duration regressions for report {}: {}",
+                report.id, e
+            );
+        }
+        report_ids.insert(report.id);
+        pipeline_ids.extend(report.pipeline_id);
+    }
+
+    for report_id in report_ids {
+        broadcaster.send(WsEventMessage::new(WsEvent::report_updated(report_id)));
+    }
+
+    for pipeline_id in pipeline_ids {
+        pipeline::broadcast_progress(pool, broadcaster, pipeline_id).await;
+    }
+}
@@ -0,0 +1,298 @@
+//! Streaming zip writer.
+//!
+//! Writes uncompressed ("stored") entries one after another so an archive can
+//! be sent while it's being built, without seeking back to patch headers.
+//! Each entry's data is hashed up front, so its local header carries the
+//! final CRC and sizes. Zip64 records are added only when an archive outgrows
+//! the classic 4 GiB / 65535 entry limits.
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const ZIP64_END_SIGNATURE: u32 = 0x0606_4b50;
+const ZIP64_LOCATOR_SIGNATURE: u32 = 0x0706_4b50;
+const END_SIGNATURE: u32 = 0x0605_4b50;
+
+/// Zip 2.0 (stored entries), or 4.5 when Zip64 fields are needed.
+const VERSION_DEFAULT: u16 = 20;
+const VERSION_ZIP64: u16 = 45;
+/// General purpose flag: file names are UTF-8.
+const FLAG_UTF8: u16 = 1 << 11;
+const ZIP64_EXTRA_ID: u16 = 0x0001;
+
+const MAX_U16: u64 = u16::MAX as u64;
+const MAX_U32: u64 = u32::MAX as u64;
+
+/// An entry already written, kept for the central directory.
+struct Entry {
+    name: String,
+    crc: u32,
+    size: u64,
+    offset: u64,
+}
+
+/// Builds a zip archive as a sequence of byte chunks.
+pub struct ZipStream {
+    dos_time: u16,
+    dos_date: u16,
+    offset: u64,
+    entries: Vec<Entry>,
+}
+
+impl ZipStream {
+    /// Start an archive whose entries are all dated `modified`.
+    pub fn new(modified: DateTime<Utc>) -> Self {
+        let (dos_time, dos_date) = dos_datetime(modified);
+        Self {
+            dos_time,
+            dos_date,
+            offset: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add an entry and return its local header, which must be sent right
+    /// before `data`.
+    pub fn add(&mut self, name: &str, data: &[u8]) -> Vec<u8> {
+        let entry = Entry {
+            name: name.to_string(),
+            crc: crc32fast::hash(data),
+            size: data.len() as u64,
+            offset: self.offset,
+        };
+
+        let zip64 = entry.size >= MAX_U32;
+        let mut header = Vec::with_capacity(30 + name.len() + 20);
+        put_u32(&mut header, LOCAL_HEADER_SIGNATURE);
+        put_u16(
+            &mut header,
+            if zip64 {
+                VERSION_ZIP64
+            } else {
+                VERSION_DEFAULT
+            },
+        );
+        put_u16(&mut header, FLAG_UTF8);
+        put_u16(&mut header, 0); // stored
+        put_u16(&mut header, self.dos_time);
+        put_u16(&mut header, self.dos_date);
+        put_u32(&mut header, entry.crc);
+        put_u32(&mut header, capped_u32(entry.size));
+        put_u32(&mut header, capped_u32(entry.size));
+        put_u16(&mut header, name.len() as u16);
+        put_u16(&mut header, if zip64 { 20 } else { 0 });
+        header.extend_from_slice(name.as_bytes());
+        if zip64 {
+            put_u16(&mut header, ZIP64_EXTRA_ID);
+            put_u16(&mut header, 16);
+            put_u64(&mut header, entry.size);
+            put_u64(&mut header, entry.size);
+        }
+
+        self.offset += header.len() as u64 + entry.size;
+        self.entries.push(entry);
+        header
+    }
+
+    /// Central directory and end records closing the archive.
+    pub fn finish(&mut self) -> Vec<u8> {
+        let mut out = Vec::new();
+        let directory_offset = self.offset;
+
+        for entry in &self.entries {
+            // Zip64 extra field values appear in this order, only when needed
+            let mut extra = Vec::new();
+            if entry.size >= MAX_U32 {
+                put_u64(&mut extra, entry.size);
+                put_u64(&mut extra, entry.size);
+            }
+            if entry.offset >= MAX_U32 {
+                put_u64(&mut extra, entry.offset);
+            }
+            let version = if extra.is_empty() {
+                VERSION_DEFAULT
+            } else {
+                VERSION_ZIP64
+            };
+
+            put_u32(&mut out, CENTRAL_HEADER_SIGNATURE);
+            put_u16(&mut out, version); // made by
+            put_u16(&mut out, version); // needed to extract
+            put_u16(&mut out, FLAG_UTF8);
+            put_u16(&mut out, 0); // stored
+            put_u16(&mut out, self.dos_time);
+            put_u16(&mut out, self.dos_date);
+            put_u32(&mut out, entry.crc);
+            put_u32(&mut out, capped_u32(entry.size));
+            put_u32(&mut out, capped_u32(entry.size));
+            put_u16(&mut out, entry.name.len() as u16);
+            put_u16(
+                &mut out,
+                if extra.is_empty() {
+                    0
+                } else {
+                    extra.len() as u16 + 4
+                },
+            );
+            put_u16(&mut out, 0); // comment length
+            put_u16(&mut out, 0); // disk number
+            put_u16(&mut out, 0); // internal attributes
+            put_u32(&mut out, 0); // external attributes
+            put_u32(&mut out, capped_u32(entry.offset));
+            out.extend_from_slice(entry.name.as_bytes());
+            if !extra.is_empty() {
+                put_u16(&mut out, ZIP64_EXTRA_ID);
+                put_u16(&mut out, extra.len() as u16);
+                out.extend_from_slice(&extra);
+            }
+        }
+
+        let directory_size = out.len() as u64;
+        let count = self.entries.len() as u64;
+
+        if count >= MAX_U16 || directory_size >= MAX_U32 || directory_offset >= MAX_U32 {
+            let zip64_end_offset = directory_offset + directory_size;
+
+            put_u32(&mut out, ZIP64_END_SIGNATURE);
+            put_u64(&mut out, 44); // size of the remaining record
+            put_u16(&mut out, VERSION_ZIP64);
+            put_u16(&mut out, VERSION_ZIP64);
+            put_u32(&mut out, 0); // this disk
+            put_u32(&mut out, 0); // disk with the central directory
+            put_u64(&mut out, count);
+            put_u64(&mut out, count);
+            put_u64(&mut out, directory_size);
+            put_u64(&mut out, directory_offset);
+
+            put_u32(&mut out, ZIP64_LOCATOR_SIGNATURE);
+            put_u32(&mut out, 0); // disk with the zip64 end record
+            put_u64(&mut out, zip64_end_offset);
+            put_u32(&mut out, 1); // total disks
+        }
+
+        put_u32(&mut out, END_SIGNATURE);
+        put_u16(&mut out, 0); // this disk
+        put_u16(&mut out, 0); // disk with the central directory
+        put_u16(&mut out, count.min(MAX_U16) as u16);
+        put_u16(&mut out, count.min(MAX_U16) as u16);
+        put_u32(&mut out, capped_u32(directory_size));
+        put_u32(&mut out, capped_u32(directory_offset));
+        put_u16(&mut out, 0); // comment length
+
+        out
+    }
+}
+
+/// MS-DOS time and date; zip can't represent dates before 1980.
+fn dos_datetime(at: DateTime<Utc>) -> (u16, u16) {
+    if at.year() < 1980 {
+        return (0, (1 << 5) | 1);
+    }
+    let time = (at.hour() << 11) | (at.minute() << 5) | (at.second() / 2);
+    let date = (((at.year() - 1980) as u32).min(127) << 9) | (at.month() << 5) | at.day();
+    (time as u16, date as u16)
+}
+
+/// A 32-bit field value, or the Zip64 marker if it doesn't fit.
+fn capped_u32(value: u64) -> u32 {
+    value.min(MAX_U32) as u32
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read};
+
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn build(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut zip = ZipStream::new(Utc.with_ymd_and_hms(2026, 10, 18, 12, 30, 10).unwrap());
+        let mut out = Vec::new();
+        for (name, data) in entries {
+            out.extend(zip.add(name, data));
+            out.extend_from_slice(data);
+        }
+        out.extend(zip.finish());
+        out
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let archive = build(&[
+            ("report.json", b"{\"id\":1}"),
+            ("artifacts/reports/a/jobs/b/index.html", b"<html></html>"),
+            ("empty.txt", b""),
+            ("caf\u{e9}.txt", "caf\u{e9}".as_bytes()),
+        ]);
+
+        let mut zip = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+        assert_eq!(zip.len(), 4);
+
+        let mut content = String::new();
+        zip.by_name("artifacts/reports/a/jobs/b/index.html")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "<html></html>");
+
+        let mut content = String::new();
+        zip.by_name("caf\u{e9}.txt")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "caf\u{e9}");
+
+        let file = zip.by_name("report.json").unwrap();
+        let modified = file.last_modified().unwrap();
+        assert_eq!(
+            (modified.year(), modified.month(), modified.day()),
+            (2026, 10, 18)
+        );
+        assert_eq!(
+            (modified.hour(), modified.minute(), modified.second()),
+            (12, 30, 10)
+        );
+    }
+
+    #[test]
+    fn test_zip64_entry_count() {
+        let names: Vec<String> = (0..70_000).map(|i| format!("{}.txt", i)).collect();
+        let entries: Vec<(&str, &[u8])> = names.iter().map(|n| (n.as_str(), &b"x"[..])).collect();
+
+        let mut zip = zip::ZipArchive::new(Cursor::new(build(&entries))).unwrap();
+        assert_eq!(zip.len(), 70_000);
+
+        let mut content = String::new();
+        zip.by_name("69999.txt")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "x");
+    }
+
+    #[test]
+    fn test_dos_datetime() {
+        assert_eq!(
+            dos_datetime(Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap()),
+            (0, 0x21)
+        );
+        assert_eq!(
+            dos_datetime(Utc.with_ymd_and_hms(1980, 1, 1, 0, 0, 2).unwrap()),
+            (1, 0x21)
+        );
+    }
+}
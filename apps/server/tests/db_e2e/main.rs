@@ -0,0 +1,19 @@
+//! Database E2E test suite.
+//!
+//! Tests queries that depend on Postgres behavior (raw SQL, rollups, sweeps)
+//! against a real database.
+//! Requires a running PostgreSQL database (docker compose -f docker/docker-compose.dev.yml up -d).
+//!
+//! Run with: cargo test --test db_e2e
+
+mod test_helpers;
+
+mod test_duration_regressions;
+mod test_open_reports;
+mod test_pipeline_runs;
+mod test_release_refs;
+mod test_report_bundles;
+mod test_report_completion;
+mod test_report_rollups;
+mod test_search;
+mod test_upload_sweep;
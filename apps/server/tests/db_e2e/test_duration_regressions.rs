@@ -0,0 +1,95 @@
+//! E2E tests: duration regression detection against earlier reports.
+
+use mattermost_tsio_lib::db::DbPool;
+use mattermost_tsio_lib::models::JobStatus;
+use uuid::Uuid;
+
+use super::test_helpers::*;
+
+/// Create a report with one passing run per `(title, duration_ms)`.
+async fn create_run(pool: &DbPool, repo: &str, tests: &[(&str, i32)]) -> Uuid {
+    let report = create_report(pool, repo, Some(1)).await;
+    let job = create_job(pool, report.id, JobStatus::Complete).await;
+    let suite = create_suite(pool, job.id, "suite", tests.len() as i32, 0).await;
+    for (title, duration_ms) in tests {
+        create_case(pool, &suite, title, "passed", *duration_ms).await;
+    }
+    report.id
+}
+
+/// A test much slower than its baseline is flagged; one within it isn't.
+#[actix_rt::test]
+async fn test_detects_slowdown_over_baseline() {
+    let pool = create_test_pool().await;
+    let repo = unique_repo("regression");
+
+    for _ in 0..3 {
+        create_run(&pool, &repo, &[("slow", 1000), ("steady", 1000)]).await;
+    }
+    let report_id = create_run(&pool, &repo, &[("slow", 5000), ("steady", 1200)]).await;
+
+    let found = pool
+        .detect_duration_regressions(report_id, &feature_settings())
+        .await
+        .unwrap();
+    assert_eq!(found, 1);
+
+    let regressions = pool.get_duration_regressions(report_id).await.unwrap();
+    assert_eq!(regressions.len(), 1);
+    assert_eq!(regressions[0].full_title, "slow");
+    assert_eq!(regressions[0].duration_ms, 5000);
+    assert_eq!(regressions[0].baseline_median_ms, 1000);
+    assert_eq!(regressions[0].sample_count, 3);
+
+    // Re-running replaces the previous result
+    pool.detect_duration_regressions(report_id, &feature_settings())
+        .await
+        .unwrap();
+    assert_eq!(
+        pool.get_duration_regressions(report_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+/// A slowdown within the test's usual p90 isn't a regression.
+#[actix_rt::test]
+async fn test_ignores_slowdown_within_p90() {
+    let pool = create_test_pool().await;
+    let repo = unique_repo("regression-noisy");
+
+    for duration_ms in [1000, 1000, 1000, 4000, 4000] {
+        create_run(&pool, &repo, &[("noisy", duration_ms)]).await;
+    }
+    let report_id = create_run(&pool, &repo, &[("noisy", 3000)]).await;
+
+    let found = pool
+        .detect_duration_regressions(report_id, &feature_settings())
+        .await
+        .unwrap();
+    assert_eq!(found, 0);
+}
+
+/// Tests without enough baseline runs, or in other repositories, aren't flagged.
+#[actix_rt::test]
+async fn test_requires_baseline_samples() {
+    let pool = create_test_pool().await;
+    let repo = unique_repo("regression-new");
+    let other_repo = unique_repo("regression-other");
+
+    for _ in 0..3 {
+        create_run(&pool, &other_repo, &[("new", 1000)]).await;
+    }
+    for _ in 0..2 {
+        create_run(&pool, &repo, &[("new", 1000)]).await;
+    }
+    let report_id = create_run(&pool, &repo, &[("new", 9000)]).await;
+
+    let found = pool
+        .detect_duration_regressions(report_id, &feature_settings())
+        .await
+        .unwrap();
+    assert_eq!(found, 0);
+}
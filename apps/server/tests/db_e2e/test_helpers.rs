@@ -0,0 +1,150 @@
+//! Shared test helpers for database E2E tests.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use mattermost_tsio_lib::config::{Config, FeatureSettings};
+use mattermost_tsio_lib::db::DbPool;
+use mattermost_tsio_lib::db::test_results::{NewTestCase, NewTestSuite};
+use mattermost_tsio_lib::entity::{test_case, test_job, test_report, test_suite};
+use mattermost_tsio_lib::models::{Framework, GitHubMetadata, JobStatus};
+use uuid::Uuid;
+
+static MIGRATIONS_RUN: OnceLock<()> = OnceLock::new();
+
+/// Create a fresh DB pool. Migrations run only once.
+pub async fn create_test_pool() -> DbPool {
+    let mut config = Config::from_env().expect(
+        "Failed to load config. Ensure TSIO_DB_URL or individual DB vars are set, \
+         and that PostgreSQL is running.",
+    );
+    config.database.max_connections = 2;
+    config.database.min_connections = 1;
+
+    let pool = DbPool::new(&config)
+        .await
+        .expect("Failed to connect to database");
+
+    if MIGRATIONS_RUN.get().is_none() {
+        pool.run_migrations()
+            .await
+            .expect("Failed to run migrations");
+        let _ = MIGRATIONS_RUN.set(());
+    }
+
+    pool
+}
+
+/// Generate a unique repository name for test isolation.
+pub fn unique_repo(prefix: &str) -> String {
+    format!(
+        "{}-{}/repo",
+        prefix,
+        Uuid::new_v4().to_string().split('-').next().unwrap()
+    )
+}
+
+/// Feature settings with the default regression thresholds.
+pub fn feature_settings() -> FeatureSettings {
+    FeatureSettings {
+        html_view_enabled: true,
+        search_min_length: 2,
+        upload_max_size: 1024,
+        upload_timeout_ms: 3600000,
+        open_report_idle_timeout_ms: 3600000,
+        regression_threshold_pct: 50,
+        regression_baseline_runs: 20,
+        regression_min_delta_ms: 1000,
+    }
+}
+
+/// Insert a Playwright report for `repository`, with `expected_jobs` jobs or
+/// open when `None`.
+pub async fn create_report(
+    pool: &DbPool,
+    repository: &str,
+    expected_jobs: Option<i32>,
+) -> test_report::Model {
+    let metadata = GitHubMetadata {
+        repository: Some(repository.to_string()),
+        git_ref: Some("refs/heads/main".to_string()),
+        workflow: Some("E2E".to_string()),
+        ..Default::default()
+    };
+    let (report, created) = pool
+        .insert_report(
+            Uuid::now_v7(),
+            expected_jobs,
+            Framework::Playwright,
+            Some(metadata),
+            &BTreeMap::new(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect("Failed to insert report");
+    assert!(created);
+    report
+}
+
+/// Insert a job into a report.
+pub async fn create_job(pool: &DbPool, report_id: Uuid, status: JobStatus) -> test_job::Model {
+    pool.insert_job(Uuid::now_v7(), report_id, None, None, status)
+        .await
+        .expect("Failed to insert job")
+}
+
+/// Insert a suite with the given passed and failed counts.
+pub async fn create_suite(
+    pool: &DbPool,
+    job_id: Uuid,
+    title: &str,
+    passed: i32,
+    failed: i32,
+) -> test_suite::Model {
+    pool.insert_test_suite(NewTestSuite {
+        job_id,
+        title: title.to_string(),
+        file_path: Some(format!("tests/{}.spec.ts", title)),
+        total_count: passed + failed,
+        passed_count: passed,
+        failed_count: failed,
+        skipped_count: 0,
+        flaky_count: 0,
+        quarantined_count: 0,
+        known_issue_count: 0,
+        duration_ms: 1000 * (passed + failed),
+        start_time: None,
+    })
+    .await
+    .expect("Failed to insert test suite")
+}
+
+/// Insert a test case.
+pub async fn create_case(
+    pool: &DbPool,
+    suite: &test_suite::Model,
+    full_title: &str,
+    status: &str,
+    duration_ms: i32,
+) -> test_case::Model {
+    pool.insert_test_case(NewTestCase {
+        suite_id: suite.id,
+        job_id: suite.test_job_id,
+        title: full_title.to_string(),
+        full_title: full_title.to_string(),
+        status: status.to_string(),
+        duration_ms,
+        retry_count: 0,
+        error_message: (status == "failed").then(|| "Error: failed".to_string()),
+        failure_signature: None,
+        quarantined: false,
+        owner: None,
+        known_issue_key: None,
+        sequence: 0,
+        attachments: None,
+    })
+    .await
+    .expect("Failed to insert test case")
+}
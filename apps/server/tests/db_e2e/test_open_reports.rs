@@ -0,0 +1,101 @@
+//! E2E tests: open reports, whose job count grows as jobs register.
+
+use chrono::{Duration, Utc};
+use mattermost_tsio_lib::db::DbPool;
+use mattermost_tsio_lib::models::JobStatus;
+use uuid::Uuid;
+
+use super::test_helpers::*;
+
+async fn add_job(pool: &DbPool, report_id: Uuid) -> Option<Uuid> {
+    let job_id = Uuid::now_v7();
+    pool.insert_open_report_job(job_id, report_id, None, None)
+        .await
+        .unwrap()
+        .map(|job| job.id)
+}
+
+/// Each job registered raises the report's expected job count.
+#[actix_rt::test]
+async fn test_open_report_counts_jobs() {
+    let pool = create_test_pool().await;
+    let report = create_report(&pool, &unique_repo("open"), None).await;
+    assert!(report.is_open);
+    assert_eq!(report.expected_jobs, 0);
+
+    assert!(add_job(&pool, report.id).await.is_some());
+    assert!(add_job(&pool, report.id).await.is_some());
+
+    let report = pool.get_report_by_id(report.id).await.unwrap().unwrap();
+    assert_eq!(report.expected_jobs, 2);
+    assert_eq!(
+        pool.get_jobs_by_report_id(report.id).await.unwrap().len(),
+        2
+    );
+}
+
+/// Closed or fixed-count reports take no open report jobs, and nothing is inserted.
+#[actix_rt::test]
+async fn test_open_report_job_rejected_when_closed() {
+    let pool = create_test_pool().await;
+
+    let fixed = create_report(&pool, &unique_repo("open-fixed"), Some(1)).await;
+    assert!(add_job(&pool, fixed.id).await.is_none());
+
+    let closed = create_report(&pool, &unique_repo("open-closed"), None).await;
+    pool.close_report(closed.id, None).await.unwrap().unwrap();
+    assert!(add_job(&pool, closed.id).await.is_none());
+
+    for report_id in [fixed.id, closed.id] {
+        assert!(
+            pool.get_jobs_by_report_id(report_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+    let fixed = pool.get_report_by_id(fixed.id).await.unwrap().unwrap();
+    assert_eq!(fixed.expected_jobs, 1);
+}
+
+/// A failed job insert doesn't leave the expected job count raised.
+#[actix_rt::test]
+async fn test_open_report_job_insert_is_atomic() {
+    let pool = create_test_pool().await;
+    let report = create_report(&pool, &unique_repo("open-atomic"), None).await;
+    let job_id = add_job(&pool, report.id).await.unwrap();
+
+    // Reusing the job ID fails the insert after the count was raised
+    assert!(
+        pool.insert_open_report_job(job_id, report.id, None, None)
+            .await
+            .is_err()
+    );
+
+    let report = pool.get_report_by_id(report.id).await.unwrap().unwrap();
+    assert_eq!(report.expected_jobs, 1);
+}
+
+/// Idle open reports close once they have jobs; empty ones wait for a job.
+#[actix_rt::test]
+async fn test_idle_open_reports_close_only_with_jobs() {
+    let pool = create_test_pool().await;
+    let empty = create_report(&pool, &unique_repo("open-empty"), None).await;
+    let idle = create_report(&pool, &unique_repo("open-idle"), None).await;
+    let job_id = add_job(&pool, idle.id).await.unwrap();
+    for status in [JobStatus::Processing, JobStatus::Complete] {
+        pool.update_job_status(job_id, status, None).await.unwrap();
+    }
+
+    let idle_cutoff = Utc::now() + Duration::minutes(1);
+    pool.sweep_upload_timeouts(None, Some(idle_cutoff))
+        .await
+        .unwrap()
+        .expect("sweep lock should be free");
+
+    let empty = pool.get_report_by_id(empty.id).await.unwrap().unwrap();
+    assert_eq!(empty.status, "initializing");
+    let idle = pool.get_report_by_id(idle.id).await.unwrap().unwrap();
+    assert_eq!(idle.status, "complete");
+    assert_eq!(idle.missing_jobs, Some(0));
+}
@@ -0,0 +1,60 @@
+//! E2E tests: pipeline runs joined on report registration.
+
+use std::collections::BTreeMap;
+
+use mattermost_tsio_lib::db::DbPool;
+use mattermost_tsio_lib::entity::pipeline_run;
+use mattermost_tsio_lib::models::Framework;
+use mattermost_tsio_lib::services::pipeline;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use super::test_helpers::*;
+
+async fn register(pool: &DbPool, key: &str, group_key: &str) -> (Option<Uuid>, bool) {
+    let identity = pipeline::identity(Some(group_key), None).unwrap();
+    let (report, created) = pool
+        .insert_report(
+            Uuid::now_v7(),
+            Some(1),
+            Framework::Playwright,
+            None,
+            &BTreeMap::new(),
+            None,
+            Some(key.to_string()),
+            identity.as_ref(),
+        )
+        .await
+        .unwrap();
+    (report.pipeline_id, created)
+}
+
+async fn pipeline_exists(pool: &DbPool, group_key: &str) -> bool {
+    pipeline_run::Entity::find()
+        .filter(pipeline_run::Column::GroupKey.eq(group_key))
+        .one(pool.connection())
+        .await
+        .unwrap()
+        .is_some()
+}
+
+/// Retried registrations return the existing report without creating a
+/// pipeline run for their own group.
+#[actix_rt::test]
+async fn test_existing_report_creates_no_pipeline_run() {
+    let pool = create_test_pool().await;
+    let key = unique_repo("pipeline-key");
+    let first = unique_repo("pipeline-first");
+    let retry = unique_repo("pipeline-retry");
+
+    let (pipeline_id, created) = register(&pool, &key, &first).await;
+    assert!(created);
+    assert!(pipeline_id.is_some());
+
+    let (retried_pipeline_id, created) = register(&pool, &key, &retry).await;
+    assert!(!created);
+    assert_eq!(retried_pipeline_id, pipeline_id);
+
+    assert!(pipeline_exists(&pool, &first).await);
+    assert!(!pipeline_exists(&pool, &retry).await);
+}
@@ -0,0 +1,64 @@
+//! E2E tests: release refs matched by branch prefix.
+
+use std::collections::BTreeMap;
+
+use mattermost_tsio_lib::db::DbPool;
+use mattermost_tsio_lib::models::{Framework, GitHubMetadata, JobStatus};
+use uuid::Uuid;
+
+use super::test_helpers::*;
+
+/// Insert a complete report on `git_ref`.
+async fn create_complete_report(pool: &DbPool, repository: &str, git_ref: &str) {
+    let metadata = GitHubMetadata {
+        repository: Some(repository.to_string()),
+        git_ref: Some(git_ref.to_string()),
+        ..Default::default()
+    };
+    let (report, _) = pool
+        .insert_report(
+            Uuid::now_v7(),
+            Some(1),
+            Framework::Playwright,
+            Some(metadata),
+            &BTreeMap::new(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    create_job(pool, report.id, JobStatus::Complete).await;
+    pool.close_report(report.id, None).await.unwrap().unwrap();
+}
+
+/// Refs match from the start of the branch name, bare or fully qualified.
+#[actix_rt::test]
+async fn test_complete_report_refs_match_prefix() {
+    let pool = create_test_pool().await;
+    let repository = unique_repo("release-refs");
+
+    for git_ref in [
+        "refs/heads/release-10.4",
+        "release-10.3",
+        "refs/tags/release-10.2",
+        "refs/heads/pre-release-10.1",
+        "refs/heads/release_10.0",
+    ] {
+        create_complete_report(&pool, &repository, git_ref).await;
+    }
+
+    let mut refs = pool
+        .get_complete_report_refs("release-", Some(&repository))
+        .await
+        .unwrap();
+    refs.sort();
+    assert_eq!(
+        refs,
+        vec![
+            "refs/heads/release-10.4",
+            "refs/tags/release-10.2",
+            "release-10.3"
+        ]
+    );
+}
@@ -0,0 +1,81 @@
+//! E2E tests: rows collected for report bundles.
+
+use chrono::Utc;
+use mattermost_tsio_lib::db::DbPool;
+use mattermost_tsio_lib::entity::triage_record;
+use mattermost_tsio_lib::models::JobStatus;
+use sea_orm::{ActiveModelTrait, IntoActiveModel};
+use uuid::Uuid;
+
+use super::test_helpers::*;
+
+async fn create_triage(pool: &DbPool, test_case_id: Uuid) -> triage_record::Model {
+    let now = Utc::now();
+    triage_record::Model {
+        id: Uuid::now_v7(),
+        test_case_id: Some(test_case_id),
+        full_title: None,
+        failure_signature: None,
+        repository: None,
+        classification: "flaky".to_string(),
+        notes: Some("retries on slow CI runners".to_string()),
+        issue_url: None,
+        author: "e2e".to_string(),
+        author_key_id: "e2e".to_string(),
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    }
+    .into_active_model()
+    .insert(pool.connection())
+    .await
+    .expect("Failed to insert triage record")
+}
+
+/// Each job's rows carry the triage records of its own test cases.
+#[actix_rt::test]
+async fn test_job_rows_include_triage_records() {
+    let pool = create_test_pool().await;
+    let report = create_report(&pool, &unique_repo("bundle"), Some(2)).await;
+
+    let first = create_job(&pool, report.id, JobStatus::Complete).await;
+    let suite = create_suite(&pool, first.id, "login", 0, 1).await;
+    let case = create_case(&pool, &suite, "login fails", "failed", 1000).await;
+    let triage = create_triage(&pool, case.id).await;
+
+    let second = create_job(&pool, report.id, JobStatus::Complete).await;
+    let suite = create_suite(&pool, second.id, "cart", 0, 1).await;
+    let other_case = create_case(&pool, &suite, "cart fails", "failed", 1000).await;
+    create_triage(&pool, other_case.id).await;
+
+    let rows = pool.get_job_rows(first.id).await.unwrap();
+    assert_eq!(rows.cases.len(), 1);
+    assert_eq!(rows.triage_records.len(), 1);
+    assert_eq!(rows.triage_records[0].id, triage.id);
+}
+
+/// Report rows carry the report's duration regressions.
+#[actix_rt::test]
+async fn test_report_rows_include_duration_regressions() {
+    let pool = create_test_pool().await;
+    let repo = unique_repo("bundle-regressions");
+    for _ in 0..3 {
+        let report = create_report(&pool, &repo, Some(1)).await;
+        let job = create_job(&pool, report.id, JobStatus::Complete).await;
+        let suite = create_suite(&pool, job.id, "suite", 1, 0).await;
+        create_case(&pool, &suite, "slow", "passed", 1000).await;
+    }
+    let report = create_report(&pool, &repo, Some(1)).await;
+    let job = create_job(&pool, report.id, JobStatus::Complete).await;
+    let suite = create_suite(&pool, job.id, "suite", 1, 0).await;
+    let case = create_case(&pool, &suite, "slow", "passed", 9000).await;
+
+    pool.detect_duration_regressions(report.id, &feature_settings())
+        .await
+        .unwrap();
+
+    let rows = pool.get_report_rows(report.id).await.unwrap();
+    assert!(rows.oidc_claims.is_none());
+    assert_eq!(rows.duration_regressions.len(), 1);
+    assert_eq!(rows.duration_regressions[0].test_case_id, case.id);
+}
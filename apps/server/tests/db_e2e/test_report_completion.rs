@@ -0,0 +1,45 @@
+//! E2E tests: reopening closed reports.
+
+use mattermost_tsio_lib::models::{JobStatus, ReportStatus};
+use mattermost_tsio_lib::services::EventBroadcaster;
+use mattermost_tsio_lib::services::report_completion;
+
+use super::test_helpers::*;
+
+/// A reopened report stays open for new jobs, even when all of its expected
+/// jobs had finished, and closes again once a new job finishes.
+#[actix_rt::test]
+async fn test_reopen_keeps_report_open() {
+    let pool = create_test_pool().await;
+    let broadcaster = EventBroadcaster::new();
+
+    let finished = create_report(&pool, &unique_repo("reopen-done"), Some(1)).await;
+    create_job(&pool, finished.id, JobStatus::Complete).await;
+    pool.close_report(finished.id, None).await.unwrap().unwrap();
+
+    let report = report_completion::reopen(&pool, &broadcaster, finished.id)
+        .await
+        .unwrap();
+    assert_eq!(report.status, ReportStatus::Uploading.as_str());
+    assert_eq!(report.missing_jobs, None);
+
+    create_job(&pool, finished.id, JobStatus::Complete).await;
+    report_completion::check(&pool, &broadcaster, &feature_settings(), finished.id)
+        .await
+        .unwrap();
+    let report = pool.get_report_by_id(finished.id).await.unwrap().unwrap();
+    assert_eq!(report.status, ReportStatus::Complete.as_str());
+
+    let missing = create_report(&pool, &unique_repo("reopen-missing"), Some(2)).await;
+    create_job(&pool, missing.id, JobStatus::Complete).await;
+    pool.close_report(missing.id, Some(1))
+        .await
+        .unwrap()
+        .unwrap();
+
+    let report = report_completion::reopen(&pool, &broadcaster, missing.id)
+        .await
+        .unwrap();
+    assert_eq!(report.status, ReportStatus::Uploading.as_str());
+    assert_eq!(report.missing_jobs, None);
+}
@@ -0,0 +1,133 @@
+//! E2E tests: report rollups and the queries built on them.
+
+use mattermost_tsio_lib::models::{JobStatus, ListReportsQuery, TrendsQuery};
+use serde_json::json;
+
+use super::test_helpers::*;
+
+fn trends_query(repository: &str) -> TrendsQuery {
+    serde_json::from_value(json!({ "repository": repository })).unwrap()
+}
+
+fn failing_reports_query(repository: &str, has_failures: bool) -> ListReportsQuery {
+    serde_json::from_value(json!({ "repository": repository, "has_failures": has_failures }))
+        .unwrap()
+}
+
+/// Refreshing sums the suites of every job and replaces the previous rollup.
+#[actix_rt::test]
+async fn test_refresh_rollup_sums_jobs() {
+    let pool = create_test_pool().await;
+    let repo = unique_repo("rollup");
+    let report = create_report(&pool, &repo, Some(2)).await;
+
+    let first = create_job(&pool, report.id, JobStatus::Complete).await;
+    create_suite(&pool, first.id, "login", 3, 1).await;
+    pool.refresh_report_rollup(report.id).await.unwrap();
+
+    let trends = pool.query_trends(&trends_query(&repo)).await.unwrap();
+    assert_eq!(trends.len(), 1);
+    assert_eq!(trends[0].report_count, 1);
+    assert_eq!(
+        (trends[0].total, trends[0].passed, trends[0].failed),
+        (4, 3, 1)
+    );
+
+    // A second job's results are added on the next refresh, not duplicated
+    let second = create_job(&pool, report.id, JobStatus::Complete).await;
+    create_suite(&pool, second.id, "cart", 2, 0).await;
+    pool.refresh_report_rollup(report.id).await.unwrap();
+    pool.refresh_report_rollup(report.id).await.unwrap();
+
+    let trends = pool.query_trends(&trends_query(&repo)).await.unwrap();
+    assert_eq!(trends.len(), 1);
+    assert_eq!(trends[0].report_count, 1);
+    assert_eq!(
+        (trends[0].total, trends[0].passed, trends[0].failed),
+        (6, 5, 1)
+    );
+    assert_eq!(trends[0].duration_ms, 0);
+}
+
+/// Deleted jobs drop out of the rollup on the next refresh.
+#[actix_rt::test]
+async fn test_refresh_rollup_skips_deleted_jobs() {
+    let pool = create_test_pool().await;
+    let repo = unique_repo("rollup-deleted");
+    let report = create_report(&pool, &repo, Some(2)).await;
+
+    let kept = create_job(&pool, report.id, JobStatus::Complete).await;
+    create_suite(&pool, kept.id, "login", 3, 0).await;
+    let deleted = create_job(&pool, report.id, JobStatus::Complete).await;
+    create_suite(&pool, deleted.id, "cart", 1, 2).await;
+    pool.refresh_report_rollup(report.id).await.unwrap();
+
+    assert!(pool.soft_delete_job(report.id, deleted.id).await.unwrap());
+    pool.refresh_report_rollup(report.id).await.unwrap();
+
+    let trends = pool.query_trends(&trends_query(&repo)).await.unwrap();
+    assert_eq!(trends.len(), 1);
+    assert_eq!(
+        (trends[0].total, trends[0].passed, trends[0].failed),
+        (3, 3, 0)
+    );
+}
+
+/// Trends only count live reports matching the filters.
+#[actix_rt::test]
+async fn test_trends_filters() {
+    let pool = create_test_pool().await;
+    let repo = unique_repo("trends");
+
+    for failed in [0, 2] {
+        let report = create_report(&pool, &repo, Some(1)).await;
+        let job = create_job(&pool, report.id, JobStatus::Complete).await;
+        create_suite(&pool, job.id, "login", 5, failed).await;
+        pool.refresh_report_rollup(report.id).await.unwrap();
+    }
+    let deleted = create_report(&pool, &repo, Some(1)).await;
+    let job = create_job(&pool, deleted.id, JobStatus::Complete).await;
+    create_suite(&pool, job.id, "login", 1, 1).await;
+    pool.refresh_report_rollup(deleted.id).await.unwrap();
+    assert!(pool.soft_delete_report(deleted.id).await.unwrap());
+
+    let trends = pool.query_trends(&trends_query(&repo)).await.unwrap();
+    assert_eq!(trends.len(), 1);
+    assert_eq!(trends[0].report_count, 2);
+    assert_eq!((trends[0].total, trends[0].failed), (12, 2));
+
+    let other_workflow: TrendsQuery =
+        serde_json::from_value(json!({ "repository": repo, "workflow": "Nightly" })).unwrap();
+    assert!(pool.query_trends(&other_workflow).await.unwrap().is_empty());
+}
+
+/// `has_failures` reads the rollup's failed count.
+#[actix_rt::test]
+async fn test_has_failures_filter() {
+    let pool = create_test_pool().await;
+    let repo = unique_repo("failures");
+
+    let passing = create_report(&pool, &repo, Some(1)).await;
+    let job = create_job(&pool, passing.id, JobStatus::Complete).await;
+    create_suite(&pool, job.id, "login", 2, 0).await;
+    pool.refresh_report_rollup(passing.id).await.unwrap();
+
+    let failing = create_report(&pool, &repo, Some(1)).await;
+    let job = create_job(&pool, failing.id, JobStatus::Complete).await;
+    create_suite(&pool, job.id, "login", 1, 1).await;
+    pool.refresh_report_rollup(failing.id).await.unwrap();
+
+    let (reports, total) = pool
+        .list_reports(&failing_reports_query(&repo, true))
+        .await
+        .unwrap();
+    assert_eq!(total, 1);
+    assert_eq!(reports[0].id, failing.id);
+
+    let (reports, total) = pool
+        .list_reports(&failing_reports_query(&repo, false))
+        .await
+        .unwrap();
+    assert_eq!(total, 1);
+    assert_eq!(reports[0].id, passing.id);
+}
@@ -0,0 +1,46 @@
+//! E2E tests: global test search.
+
+use mattermost_tsio_lib::models::{GlobalSearchQuery, JobStatus};
+use serde_json::json;
+
+use super::test_helpers::*;
+
+fn search_query(q: &str, repository: &str) -> GlobalSearchQuery {
+    serde_json::from_value(json!({ "q": q, "repository": repository })).unwrap()
+}
+
+/// Titles match by word and by partial word, file paths by trigram.
+#[actix_rt::test]
+async fn test_search_matches_titles_and_paths() {
+    let pool = create_test_pool().await;
+    let repo = unique_repo("search");
+    let report = create_report(&pool, &repo, Some(1)).await;
+    let job = create_job(&pool, report.id, JobStatus::Complete).await;
+    let checkout = create_suite(&pool, job.id, "checkout", 1, 0).await;
+    let paid = create_case(&pool, &checkout, "Checkout > pays with card", "passed", 100).await;
+    let login = create_suite(&pool, job.id, "login", 1, 0).await;
+    let signed_in = create_case(&pool, &login, "Login > signs in", "passed", 100).await;
+
+    let ids = |rows: Vec<mattermost_tsio_lib::db::test_results::SearchRow>| {
+        rows.into_iter().map(|r| r.id).collect::<Vec<_>>()
+    };
+
+    let rows = pool
+        .search_test_cases(&search_query("card", &repo), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(ids(rows), vec![paid.id]);
+
+    let rows = pool
+        .search_test_cases(&search_query("sign", &repo), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(ids(rows), vec![signed_in.id]);
+
+    // Suite file paths are "tests/{title}.spec.ts"
+    let rows = pool
+        .search_test_cases(&search_query("login.spec", &repo), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(ids(rows), vec![signed_in.id]);
+}
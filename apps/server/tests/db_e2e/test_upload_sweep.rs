@@ -0,0 +1,77 @@
+//! E2E tests: upload timeout sweep.
+
+use chrono::{Duration, Utc};
+use mattermost_tsio_lib::db::DbPool;
+use mattermost_tsio_lib::models::{JobStatus, UploadStatus};
+use uuid::Uuid;
+
+use super::test_helpers::*;
+
+/// Sweep with a cutoff in the future, so every upload and report counts as idle.
+async fn sweep_all(pool: &DbPool) {
+    let cutoff = Utc::now() + Duration::minutes(1);
+    pool.sweep_upload_timeouts(Some(cutoff), None)
+        .await
+        .unwrap()
+        .expect("sweep lock should be free");
+}
+
+async fn report_status(pool: &DbPool, report_id: Uuid) -> String {
+    pool.get_report_by_id(report_id)
+        .await
+        .unwrap()
+        .expect("report exists")
+        .status
+}
+
+/// A stalled JSON upload times out, fails its job and finalizes the report.
+#[actix_rt::test]
+async fn test_sweep_times_out_stalled_upload() {
+    let pool = create_test_pool().await;
+    let report = create_report(&pool, &unique_repo("sweep-stalled"), Some(2)).await;
+    let job = create_job(&pool, report.id, JobStatus::Pending).await;
+    pool.set_json_upload_status(job.id, UploadStatus::Started)
+        .await
+        .unwrap();
+
+    sweep_all(&pool).await;
+
+    let job = pool.get_job_by_id(job.id).await.unwrap().unwrap();
+    assert_eq!(job.json_upload_status.as_deref(), Some("timedout"));
+    assert_eq!(job.status, "failed");
+
+    let report = pool.get_report_by_id(report.id).await.unwrap().unwrap();
+    assert_eq!(report.status, "failed");
+    assert_eq!(report.missing_jobs, Some(2));
+}
+
+/// A report whose jobs all finished is finalized with the missing jobs recorded.
+#[actix_rt::test]
+async fn test_sweep_finalizes_finished_report() {
+    let pool = create_test_pool().await;
+    let report = create_report(&pool, &unique_repo("sweep-finished"), Some(3)).await;
+    create_job(&pool, report.id, JobStatus::Complete).await;
+    create_job(&pool, report.id, JobStatus::Failed).await;
+
+    sweep_all(&pool).await;
+
+    let report = pool.get_report_by_id(report.id).await.unwrap().unwrap();
+    assert_eq!(report.status, "complete");
+    assert_eq!(report.missing_jobs, Some(1));
+}
+
+/// Idle reports without jobs, or with jobs that never started an upload, are left alone.
+#[actix_rt::test]
+async fn test_sweep_keeps_reports_without_timed_out_uploads() {
+    let pool = create_test_pool().await;
+    let empty = create_report(&pool, &unique_repo("sweep-empty"), Some(1)).await;
+    let waiting = create_report(&pool, &unique_repo("sweep-waiting"), Some(2)).await;
+    create_job(&pool, waiting.id, JobStatus::Complete).await;
+    create_job(&pool, waiting.id, JobStatus::Pending).await;
+
+    sweep_all(&pool).await;
+
+    assert_eq!(report_status(&pool, empty.id).await, "initializing");
+    assert_ne!(report_status(&pool, waiting.id).await, "complete");
+    assert_ne!(report_status(&pool, waiting.id).await, "failed");
+}
@@ -194,18 +194,22 @@ impl MockOidcProvider {
         let issuer_url = format!("http://127.0.0.1:{}", port);
 
         let state_data = state.clone();
-        let server = HttpServer::new(move || {
-            App::new()
-                .app_data(web::Data::new(state_data.clone()))
-                .service(jwks_endpoint)
-        })
-        .listen(listener)
-        .expect("failed to listen")
-        .disable_signals()
-        .run();
-
-        // Fire and forget — server lives for the process lifetime
-        tokio::spawn(server);
+        // Run on a thread of its own: each test has its own runtime, and the
+        // shared provider must outlive the test that started it
+        std::thread::spawn(move || {
+            actix_rt::System::new().block_on(async move {
+                HttpServer::new(move || {
+                    App::new()
+                        .app_data(web::Data::new(state_data.clone()))
+                        .service(jwks_endpoint)
+                })
+                .listen(listener)
+                .expect("failed to listen")
+                .disable_signals()
+                .run()
+                .await
+            })
+        });
 
         MockOidcProvider { issuer_url, state }
     }
@@ -43,8 +43,10 @@ export const ReportCard = memo(function ReportCard({
           detox: 'Detox',
         }[report.framework] || report.framework;
 
-      // Jobs progress and upload status
-      const complete = report.jobs_complete >= report.expected_jobs;
+      // Jobs progress and upload status; open reports keep adding jobs
+      const complete = report.open
+        ? ['complete', 'failed', 'cancelled'].includes(report.status)
+        : report.jobs_complete >= report.expected_jobs;
 
       // Check if report is timed out (not complete after timeout period)
       const timeoutMs = uploadTimeoutMs ?? DEFAULT_UPLOAD_TIMEOUT_MS;
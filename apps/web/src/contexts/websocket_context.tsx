@@ -80,6 +80,17 @@ export function WebSocketProvider({ children }: WebSocketProviderProps) {
             queryKey: ['report', event.payload.report_id, 'suites'],
           });
           break;
+
+        case 'report_deleted':
+          // Drop cached data for the report and refresh the list
+          queryClient.removeQueries({
+            queryKey: ['report-with-jobs', event.payload.report_id],
+          });
+          queryClient.removeQueries({
+            queryKey: ['report', event.payload.report_id],
+          });
+          queryClient.invalidateQueries({ queryKey: ['reports'] });
+          break;
       }
     },
     [queryClient],
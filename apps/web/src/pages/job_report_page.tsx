@@ -16,6 +16,7 @@ import {
   FlaskConical,
   AlertCircle,
   CheckCircle,
+  XCircle,
   Clock,
   FileText,
   Code,
@@ -182,6 +183,7 @@ export function JobReportPage() {
             <span className="inline-flex items-center gap-1">
               <CheckCircle className="h-3.5 w-3.5" />
               {completedJobs}/{report.expected_jobs} {report.expected_jobs === 1 ? 'job' : 'jobs'}
+              {report.open && ' (open)'}
             </span>
           </div>
 
@@ -398,6 +400,13 @@ function ReportStatusBadge({ status }: ReportStatusBadgeProps) {
           Failed
         </span>
       );
+    case 'cancelled':
+      return (
+        <span className="inline-flex items-center gap-1 px-2 py-0.5 rounded-full text-xs font-medium bg-gray-100 text-gray-700 dark:bg-gray-700 dark:text-gray-300">
+          <XCircle className="h-3 w-3" />
+          Cancelled
+        </span>
+      );
     case 'processing':
       return (
         <span className="inline-flex items-center gap-1 px-2 py-0.5 rounded-full text-xs font-medium bg-blue-100 text-blue-700 dark:bg-blue-900/50 dark:text-blue-300">
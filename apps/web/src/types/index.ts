@@ -102,7 +102,9 @@ export interface ReportSummary {
   status: ReportStatus;
   framework: Framework;
   expected_jobs: number;
+  open?: boolean;
   jobs_complete: number;
+  missing_jobs?: number;
   test_stats?: TestStats;
   github_metadata?: GitHubMetadata;
   oidc_claims?: ReportOidcClaims;
@@ -203,7 +205,13 @@ export interface TestSpecListResponse {
 
 // Job-based report types (Phase 6)
 export type JobStatus = 'html_uploaded' | 'json_uploaded' | 'processing' | 'complete' | 'failed';
-export type ReportStatus = 'initializing' | 'uploading' | 'processing' | 'complete' | 'failed';
+export type ReportStatus =
+  | 'initializing'
+  | 'uploading'
+  | 'processing'
+  | 'complete'
+  | 'failed'
+  | 'cancelled';
 export type Framework = 'playwright' | 'cypress' | 'detox';
 
 export interface JobEnvironment {
@@ -230,6 +238,8 @@ export interface ReportWithJobs {
   framework: Framework;
   status: ReportStatus;
   expected_jobs: number;
+  open?: boolean;
+  missing_jobs?: number;
   github_metadata?: GitHubMetadata;
   oidc_claims?: ReportOidcClaims;
   created_at: string;
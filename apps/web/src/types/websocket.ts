@@ -8,9 +8,11 @@ export type WsEventType =
   | 'report_updated'
   | 'job_created'
   | 'job_updated'
-  | 'suites_available';
+  | 'suites_available'
+  | 'report_deleted'
+  | 'pipeline_updated';
 
-// Test statistics included in report_updated events
+// Test statistics included in report_updated and pipeline_updated events
 export interface TestStatsPayload {
   passed: number;
   failed: number;
@@ -38,6 +40,8 @@ export interface ReportUpdatedPayload {
   report_id: string;
   status: string;
   completed_jobs?: number;
+  expected_jobs?: number;
+  open?: boolean;
   test_stats?: TestStatsPayload;
   updated_at: string;
 }
@@ -69,13 +73,31 @@ export interface SuitesAvailablePayload {
   suite_count: number;
 }
 
+// Payload for report_deleted event
+export interface ReportDeletedPayload {
+  report_id: string;
+  deleted_at: string;
+}
+
+// Payload for pipeline_updated event
+export interface PipelineUpdatedPayload {
+  pipeline_id: string;
+  status: 'running' | 'passed' | 'failed' | 'cancelled';
+  report_count: number;
+  reports_closed: number;
+  test_stats: TestStatsPayload;
+  updated_at: string;
+}
+
 // Union type for all event payloads
 export type WsEventPayload =
   | ReportCreatedPayload
   | ReportUpdatedPayload
   | JobCreatedPayload
   | JobUpdatedPayload
-  | SuitesAvailablePayload;
+  | SuitesAvailablePayload
+  | ReportDeletedPayload
+  | PipelineUpdatedPayload;
 
 // Individual event types
 export interface ReportCreatedEvent {
@@ -108,10 +130,24 @@ export interface SuitesAvailableEvent {
   timestamp: string;
 }
 
+export interface ReportDeletedEvent {
+  type: 'report_deleted';
+  payload: ReportDeletedPayload;
+  timestamp: string;
+}
+
+export interface PipelineUpdatedEvent {
+  type: 'pipeline_updated';
+  payload: PipelineUpdatedPayload;
+  timestamp: string;
+}
+
 // Union type for all WebSocket events
 export type WsEventMessage =
   | ReportCreatedEvent
   | ReportUpdatedEvent
   | JobCreatedEvent
   | JobUpdatedEvent
-  | SuitesAvailableEvent;
+  | SuitesAvailableEvent
+  | ReportDeletedEvent
+  | PipelineUpdatedEvent;
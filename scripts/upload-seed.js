@@ -511,7 +511,8 @@ async function registerReport(framework, expectedJobs, githubContext) {
 
   const response = await makeRequest(url, { method: "POST", headers }, body);
 
-  if (response.statusCode !== 201) {
+  // 200 returns the report already registered for this workflow run
+  if (response.statusCode !== 201 && response.statusCode !== 200) {
     throw new Error(
       `Failed to register report (${response.statusCode}): ${response.body}`,
     );